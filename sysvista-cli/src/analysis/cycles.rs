@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use crate::output::schema::{CycleEdge, DependencyCycle, DetectedComponent, DetectedEdge, Grouping, SysVistaOutput};
+
+/// Levels and labels of the cycles recorded in `analysis.cycles`
+const LEVELS: &[Grouping] = &[Grouping::Component, Grouping::File, Grouping::Package];
+const LABELS: &[&str] = &["imports", "calls"];
+
+impl Grouping {
+    /// Group of a component; components outside a workspace package fall
+    /// back to their directory.
+    pub fn key(self, comp: &DetectedComponent) -> String {
+        let directory = || match comp.source.file.rsplit_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => ".".to_string(),
+        };
+        match self {
+            Grouping::Component => comp.id.clone(),
+            Grouping::File => comp.source.file.clone(),
+            Grouping::Directory => directory(),
+            Grouping::Package => comp.package.clone().unwrap_or_else(directory),
+        }
+    }
+}
+
+/// Groups that all reach each other, and the edges between them.
+pub struct Cycle<'a> {
+    pub members: Vec<String>,
+    pub edges: Vec<&'a DetectedEdge>,
+}
+
+/// Dependency cycles among the groups of `output`, following edges with one
+/// of `labels` (any edge when empty), largest first.
+pub fn find_cycles<'a>(output: &'a SysVistaOutput, grouping: Grouping, labels: &[String]) -> Vec<Cycle<'a>> {
+    let group_of: HashMap<&str, String> = output
+        .components
+        .iter()
+        .map(|c| (c.id.as_str(), grouping.key(c)))
+        .collect();
+    let mut groups: Vec<&str> = group_of.values().map(String::as_str).collect();
+    groups.sort();
+    groups.dedup();
+    let index: HashMap<&str, usize> = groups.iter().enumerate().map(|(i, g)| (*g, i)).collect();
+
+    let mut links: Vec<(usize, usize, &DetectedEdge)> = Vec::new();
+    for edge in &output.edges {
+        if !labels.is_empty() && !labels.iter().any(|l| edge.has_label(l)) {
+            continue;
+        }
+        if let (Some(from), Some(to)) = (group_of.get(edge.from_id.as_str()), group_of.get(edge.to_id.as_str()))
+            && from != to
+        {
+            links.push((index[from.as_str()], index[to.as_str()], edge));
+        }
+    }
+
+    let pairs: Vec<(usize, usize)> = links.iter().map(|&(a, b, _)| (a, b)).collect();
+    strongly_connected(groups.len(), &pairs)
+        .into_iter()
+        .map(|members| {
+            let edges = links
+                .iter()
+                .filter(|(a, b, _)| members.contains(a) && members.contains(b))
+                .map(|&(_, _, edge)| edge)
+                .collect();
+            Cycle {
+                members: members.iter().map(|&i| groups[i].to_string()).collect(),
+                edges,
+            }
+        })
+        .collect()
+}
+
+/// Cycles over `imports` and `calls` between components, files and packages,
+/// largest first.
+pub fn dependency_cycles(output: &SysVistaOutput) -> Vec<DependencyCycle> {
+    let labels: Vec<String> = LABELS.iter().map(|l| l.to_string()).collect();
+    let mut cycles: Vec<DependencyCycle> = LEVELS
+        .iter()
+        .flat_map(|&level| {
+            find_cycles(output, level, &labels).into_iter().map(move |cycle| DependencyCycle {
+                level,
+                members: cycle.members,
+                edges: cycle
+                    .edges
+                    .iter()
+                    .map(|e| CycleEdge {
+                        from_id: e.from_id.clone(),
+                        to_id: e.to_id.clone(),
+                        labels: e.labels().into_iter().filter(|l| LABELS.contains(l)).map(str::to_string).collect(),
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+    cycles.sort_by_key(|c| std::cmp::Reverse(c.members.len()));
+    cycles
+}
+
+/// Strongly connected components of more than one node (Tarjan, without
+/// recursion), largest first.
+pub fn strongly_connected(node_count: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut adjacency = vec![Vec::new(); node_count];
+    for &(from, to) in edges {
+        adjacency[from].push(to);
+    }
+
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; node_count];
+    let mut low = vec![0; node_count];
+    let mut on_stack = vec![false; node_count];
+    let mut stack = Vec::new();
+    let mut next = 0;
+    let mut components = Vec::new();
+
+    for root in 0..node_count {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // (node, position of the next neighbour to visit)
+        let mut work = vec![(root, 0)];
+        index[root] = next;
+        low[root] = next;
+        next += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&mut (node, ref mut child)) = work.last_mut() {
+            if let Some(&target) = adjacency[node].get(*child) {
+                *child += 1;
+                if index[target] == UNVISITED {
+                    index[target] = next;
+                    low[target] = next;
+                    next += 1;
+                    stack.push(target);
+                    on_stack[target] = true;
+                    work.push((target, 0));
+                } else if on_stack[target] {
+                    low[node] = low[node].min(index[target]);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+            if low[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+    }
+
+    components.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::{component, edge, output};
+    use crate::output::schema::ComponentKind;
+
+    #[test]
+    fn finds_strongly_connected_components() {
+        // 0 → 1 → 2 → 0 and 3 ⇄ 4, with 5 hanging off the first cycle
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 5), (3, 4), (4, 3)];
+        assert_eq!(strongly_connected(6, &edges), vec![vec![0, 1, 2], vec![3, 4]]);
+        assert!(strongly_connected(3, &[(0, 1), (1, 2)]).is_empty());
+    }
+
+    #[test]
+    fn finds_cycles_between_files_and_packages() {
+        let at = |id: &str, file: &str, package: &str| DetectedComponent {
+            package: Some(package.to_string()),
+            ..component(id, ComponentKind::Service, file)
+        };
+        let output = output(
+            vec![
+                // Two files that use each other through different components
+                at("create_order", "orders/api.py", "orders"),
+                at("Order", "orders/api.py", "orders"),
+                at("charge", "orders/billing.py", "orders"),
+                at("Invoice", "orders/billing.py", "orders"),
+                // Two packages that use each other through different files
+                at("send", "mail/send.py", "mail"),
+                at("template", "mail/template.py", "mail"),
+            ],
+            vec![
+                edge("create_order", "charge", "calls"),
+                edge("Invoice", "Order", "imports"),
+                edge("charge", "send", "calls"),
+                edge("template", "Order", "imports"),
+                // Other labels never close a cycle
+                edge("send", "create_order", "references"),
+            ],
+        );
+
+        let cycles = dependency_cycles(&output);
+        let found: Vec<(Grouping, Vec<&str>)> = cycles
+            .iter()
+            .map(|c| (c.level, c.members.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Grouping::File, vec!["orders/api.py", "orders/billing.py"]),
+                (Grouping::Package, vec!["mail", "orders"]),
+            ]
+        );
+        let package_edges: Vec<(&str, &str)> =
+            cycles[1].edges.iter().map(|e| (e.from_id.as_str(), e.to_id.as_str())).collect();
+        assert_eq!(package_edges, vec![("charge", "send"), ("template", "Order")]);
+
+        // With no labels given, the references edge counts as well
+        let by_directory = find_cycles(&output, Grouping::Directory, &[]);
+        assert_eq!(by_directory[0].members, vec!["mail", "orders"]);
+        assert_eq!(by_directory[0].edges.len(), 3);
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::output::schema::{ComponentKind, DeadComponent, DetectedComponent, SysVistaOutput};
+use crate::scanner::relationships::is_outbound;
+
+/// Components nothing uses, in scan order: models no other component points
+/// at (and that feed no transport), services nothing calls, dispatches to,
+/// imports or references from code and that handle no transport, transforms
+/// nothing calls and prompts nothing invokes. Entry points from
+/// `sysvista.toml` are never dead.
+pub fn dead_components(output: &SysVistaOutput) -> Vec<DeadComponent> {
+    let by_id: HashMap<&str, &DetectedComponent> = output.components.iter().map(|c| (c.id.as_str(), c)).collect();
+    // Datastores and outbound endpoints are where code goes, not code that uses a service
+    let from_code = |id: &str| by_id.get(id).is_some_and(|c| c.kind != ComponentKind::Datastore && !is_outbound(c));
+
+    // (component, relation) pairs that keep a component alive
+    let mut used: HashSet<(&str, &str)> = HashSet::new();
+    for edge in &output.edges {
+        if edge.from_id == edge.to_id {
+            continue;
+        }
+        for label in edge.labels() {
+            used.insert((edge.to_id.as_str(), "incoming"));
+            used.insert((edge.to_id.as_str(), label));
+            match label {
+                "handles" => {
+                    used.insert((edge.from_id.as_str(), label));
+                }
+                "consumes" => {
+                    used.insert((edge.from_id.as_str(), "incoming"));
+                }
+                "imports" | "references" if from_code(&edge.from_id) => {
+                    used.insert((edge.to_id.as_str(), "used_from_code"));
+                }
+                _ => {}
+            }
+        }
+    }
+    let has = |comp: &DetectedComponent, labels: &[&str]| labels.iter().any(|l| used.contains(&(comp.id.as_str(), *l)));
+
+    output
+        .components
+        .iter()
+        .filter(|c| !c.metadata.contains_key("entry_point"))
+        .filter_map(|c| {
+            let reason = match c.kind {
+                ComponentKind::Model if !has(c, &["incoming"]) => "model is never referenced, consumed, produced or persisted",
+                ComponentKind::Service if !has(c, &["calls", "dispatches", "handles", "used_from_code"]) => {
+                    "service is never called, imported or referenced and handles no transport"
+                }
+                ComponentKind::Transform if !has(c, &["calls"]) => "transform is never called",
+                ComponentKind::Prompt if !has(c, &["invokes_prompt", "calls"]) => "prompt is never invoked",
+                _ => return None,
+            };
+            Some(DeadComponent {
+                id: c.id.clone(),
+                reason: reason.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::{component, edge, output};
+
+    #[test]
+    fn flags_components_nothing_uses() {
+        let at = |id: &str, kind| component(id, kind, &format!("app/{id}.py"));
+        let mut nightly = at("nightly_export", ComponentKind::Service);
+        nightly.metadata.insert("entry_point".to_string(), "nightly_*".to_string());
+        let output = output(
+            vec![
+                at("route", ComponentKind::Transport),
+                at("create_user", ComponentKind::Service),
+                at("legacy_sync", ComponentKind::Service),
+                nightly,
+                at("User", ComponentKind::Model),
+                at("UserCreate", ComponentKind::Model),
+                at("OldAudit", ComponentKind::Model),
+                at("to_dto", ComponentKind::Transform),
+                at("welcome", ComponentKind::Prompt),
+                at("unused_prompt", ComponentKind::Prompt),
+            ],
+            vec![
+                edge("create_user", "route", "handles"),
+                edge("create_user", "User", "persists"),
+                edge("UserCreate", "route", "consumes"),
+                edge("create_user", "welcome", "invokes_prompt"),
+                // Referencing a model does not call a transform
+                edge("to_dto", "User", "transforms"),
+                edge("legacy_sync", "legacy_sync", "calls"),
+            ],
+        );
+
+        let dead = dead_components(&output);
+        let ids: Vec<&str> = dead.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["legacy_sync", "OldAudit", "to_dto", "unused_prompt"]);
+        assert_eq!(dead[0].reason, "service is never called, imported or referenced and handles no transport");
+    }
+
+    #[test]
+    fn services_imported_or_referenced_from_code_are_used() {
+        let at = |id: &str, kind| component(id, kind, &format!("app/{id}.py"));
+        let mut endpoint = at("billing_api", ComponentKind::Transport);
+        endpoint.metadata.insert("direction".to_string(), "outbound".to_string());
+        let output = output(
+            vec![
+                at("route", ComponentKind::Transport),
+                at("UserService", ComponentKind::Service),
+                at("AuditService", ComponentKind::Service),
+                at("CacheService", ComponentKind::Service),
+                at("redis", ComponentKind::Datastore),
+                endpoint,
+            ],
+            vec![
+                // `svc = UserService(); svc.get()` inside a route handler
+                edge("route", "UserService", "references"),
+                edge("route", "AuditService", "imports"),
+                // Neither a datastore nor an outbound endpoint runs code
+                edge("redis", "CacheService", "references"),
+                edge("billing_api", "CacheService", "imports"),
+            ],
+        );
+
+        let ids: Vec<String> = dead_components(&output).into_iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec!["CacheService"]);
+    }
+}
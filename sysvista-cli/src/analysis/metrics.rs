@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::output::schema::{ComponentMetrics, Grouping, Hotspot, ModuleMetrics, SysVistaOutput};
+
+/// Distinct successors of each component (by index into `output.components`),
+/// ignoring self-edges and edges to unknown components.
+fn successors(output: &SysVistaOutput) -> Vec<Vec<usize>> {
+    let index: HashMap<&str, usize> = output
+        .components
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c.id.as_str(), i))
+        .collect();
+    let mut successors = vec![Vec::new(); output.components.len()];
+    for edge in &output.edges {
+        if let (Some(&from), Some(&to)) = (index.get(edge.from_id.as_str()), index.get(edge.to_id.as_str()))
+            && from != to
+        {
+            successors[from].push(to);
+        }
+    }
+    for next in &mut successors {
+        next.sort_unstable();
+        next.dedup();
+    }
+    successors
+}
+
+/// Metrics of every component, in the order of `output.components`.
+pub fn component_metrics(output: &SysVistaOutput) -> Vec<ComponentMetrics> {
+    let successors = successors(output);
+    let mut fan_in = vec![0; successors.len()];
+    for &to in successors.iter().flatten() {
+        fan_in[to] += 1;
+    }
+    let betweenness = betweenness(&successors);
+
+    let degrees: Vec<f64> = successors
+        .iter()
+        .zip(&fan_in)
+        .map(|(next, &fan_in)| (next.len() + fan_in) as f64)
+        .collect();
+    let count = degrees.len().max(1) as f64;
+    let mean = degrees.iter().sum::<f64>() / count;
+    let stddev = (degrees.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / count).sqrt();
+
+    successors
+        .iter()
+        .enumerate()
+        .map(|(i, next)| ComponentMetrics {
+            fan_in: fan_in[i],
+            fan_out: next.len(),
+            betweenness: round(betweenness[i]),
+            hub_score: if stddev > 0.0 { round((degrees[i] - mean) / stddev) } else { 0.0 },
+        })
+        .collect()
+}
+
+/// Afferent and efferent coupling and instability of each package, with
+/// components outside packages grouped by directory.
+pub fn module_metrics(output: &SysVistaOutput) -> Vec<ModuleMetrics> {
+    let modules: Vec<String> = output.components.iter().map(|c| Grouping::Package.key(c)).collect();
+    let mut sizes: BTreeMap<&str, usize> = BTreeMap::new();
+    for module in &modules {
+        *sizes.entry(module).or_default() += 1;
+    }
+
+    let mut afferent: HashMap<&str, HashSet<usize>> = HashMap::new();
+    let mut efferent: HashMap<&str, HashSet<usize>> = HashMap::new();
+    for (from, next) in successors(output).iter().enumerate() {
+        for &to in next {
+            if modules[from] != modules[to] {
+                efferent.entry(&modules[from]).or_default().insert(from);
+                afferent.entry(&modules[to]).or_default().insert(from);
+            }
+        }
+    }
+
+    sizes
+        .into_iter()
+        .map(|(name, components)| {
+            let ca = afferent.get(name).map_or(0, HashSet::len);
+            let ce = efferent.get(name).map_or(0, HashSet::len);
+            let total = ca + ce;
+            ModuleMetrics {
+                name: name.to_string(),
+                components,
+                afferent: ca,
+                efferent: ce,
+                instability: if total > 0 { round(ce as f64 / total as f64) } else { 0.0 },
+            }
+        })
+        .collect()
+}
+
+/// Components with both fan-in and commits in their history, by fan-in times
+/// commit count relative to the scan's highest of each, highest first. Empty
+/// without `--git`.
+pub fn hotspots(output: &SysVistaOutput) -> Vec<Hotspot> {
+    let measured: Vec<(&str, f64, f64)> = output
+        .components
+        .iter()
+        .filter_map(|c| {
+            let (metrics, history) = (c.metrics.as_ref()?, c.history.as_ref()?);
+            Some((c.id.as_str(), metrics.fan_in as f64, history.commits as f64))
+        })
+        .collect();
+    let max_fan_in = measured.iter().map(|m| m.1).fold(0.0, f64::max);
+    let max_commits = measured.iter().map(|m| m.2).fold(0.0, f64::max);
+
+    let mut hotspots: Vec<Hotspot> = measured
+        .into_iter()
+        .filter(|&(_, fan_in, commits)| fan_in > 0.0 && commits > 0.0)
+        .map(|(id, fan_in, commits)| Hotspot {
+            id: id.to_string(),
+            score: round(fan_in / max_fan_in * commits / max_commits),
+        })
+        .collect();
+    hotspots.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hotspots
+}
+
+/// Normalised betweenness centrality of a directed, unweighted graph
+/// (Brandes' algorithm).
+fn betweenness(successors: &[Vec<usize>]) -> Vec<f64> {
+    let n = successors.len();
+    let mut centrality = vec![0.0; n];
+    let mut distance: Vec<Option<usize>> = vec![None; n];
+    let mut paths = vec![0.0; n];
+    let mut dependency = vec![0.0; n];
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+
+    for source in 0..n {
+        distance.fill(None);
+        paths.fill(0.0);
+        dependency.fill(0.0);
+        predecessors.iter_mut().for_each(Vec::clear);
+        distance[source] = Some(0);
+        paths[source] = 1.0;
+
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            let next_distance = distance[node].map(|d| d + 1);
+            for &next in &successors[node] {
+                if distance[next].is_none() {
+                    distance[next] = next_distance;
+                    queue.push_back(next);
+                }
+                if distance[next] == next_distance {
+                    paths[next] += paths[node];
+                    predecessors[next].push(node);
+                }
+            }
+        }
+
+        while let Some(node) = order.pop() {
+            for &prev in &predecessors[node] {
+                dependency[prev] += paths[prev] / paths[node] * (1.0 + dependency[node]);
+            }
+            if node != source {
+                centrality[node] += dependency[node];
+            }
+        }
+    }
+
+    if n > 2 {
+        let pairs = ((n - 1) * (n - 2)) as f64;
+        centrality.iter_mut().for_each(|c| *c /= pairs);
+    }
+    centrality
+}
+
+/// Three decimals keep outputs stable enough to diff between scans.
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::{component, edge, output};
+    use crate::output::schema::ComponentKind;
+
+    /// `web` calls into `core`, whose hub reaches `db`
+    fn layered() -> SysVistaOutput {
+        let at = |id: &str, file: &str| component(id, ComponentKind::Service, file);
+        output(
+            vec![
+                at("signup", "web/signup.py"),
+                at("login", "web/login.py"),
+                at("hub", "core/hub.py"),
+                at("jobs", "core/jobs.py"),
+                at("store", "db/store.py"),
+            ],
+            vec![
+                edge("signup", "hub", "calls"),
+                edge("signup", "hub", "imports"),
+                edge("login", "hub", "calls"),
+                edge("jobs", "hub", "calls"),
+                edge("hub", "store", "persists"),
+                // Self-edges and edges to unknown components are ignored
+                edge("hub", "hub", "calls"),
+                edge("hub", "missing", "calls"),
+            ],
+        )
+    }
+
+    #[test]
+    fn scores_the_middle_of_a_chain() {
+        // 0 → 1 → 2, plus 3 → 1: every path between others goes through 1
+        let successors = vec![vec![1], vec![2], vec![], vec![1]];
+        let centrality = betweenness(&successors);
+        // Paths 0→2 and 3→2 out of (4-1)(4-2) ordered pairs
+        assert_eq!(round(centrality[1]), round(2.0 / 6.0));
+        assert_eq!(centrality[0], 0.0);
+        assert_eq!(centrality[2], 0.0);
+    }
+
+    #[test]
+    fn measures_fan_in_fan_out_and_hubs() {
+        let metrics = component_metrics(&layered());
+        let hub = &metrics[2];
+        assert_eq!((hub.fan_in, hub.fan_out), (3, 1));
+        // Degrees 1, 1, 4, 1, 1: mean 1.6, standard deviation 1.2
+        assert_eq!(hub.hub_score, 2.0);
+        assert_eq!(metrics[0].hub_score, -0.5);
+        // signup, login and jobs each reach store only through the hub
+        assert_eq!(hub.betweenness, 0.25);
+        assert_eq!((metrics[4].fan_in, metrics[4].fan_out, metrics[4].betweenness), (1, 0, 0.0));
+    }
+
+    #[test]
+    fn measures_coupling_between_modules() {
+        let modules: Vec<(String, usize, usize, usize, f64)> = module_metrics(&layered())
+            .into_iter()
+            .map(|m| (m.name, m.components, m.afferent, m.efferent, m.instability))
+            .collect();
+        assert_eq!(
+            modules,
+            vec![
+                ("core".to_string(), 2, 2, 1, 0.333),
+                ("db".to_string(), 1, 1, 0, 0.0),
+                ("web".to_string(), 2, 0, 2, 1.0),
+            ]
+        );
+    }
+}
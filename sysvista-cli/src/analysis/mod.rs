@@ -0,0 +1,20 @@
+pub mod cycles;
+pub mod dead;
+pub mod metrics;
+
+use crate::output::schema::{Analysis, SysVistaOutput};
+
+/// Recompute `output.analysis` and the metrics of every component from its
+/// components and edges.
+pub fn analyze(output: &mut SysVistaOutput) {
+    let component_metrics = metrics::component_metrics(output);
+    for (comp, metrics) in output.components.iter_mut().zip(component_metrics) {
+        comp.metrics = Some(metrics);
+    }
+    output.analysis = Analysis {
+        cycles: cycles::dependency_cycles(output),
+        modules: metrics::module_metrics(output),
+        dead: dead::dead_components(output),
+        hotspots: metrics::hotspots(output),
+    };
+}
@@ -1,8 +1,9 @@
+mod analysis;
 mod output;
 mod scanner;
 
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "sysvista", version, about = "System architecture visualizer")]
@@ -21,22 +22,163 @@ enum Commands {
         /// Output JSON file path
         #[arg(short, long, default_value = "sysvista-output.json")]
         output: PathBuf,
+
+        /// Print the edges of a component (ID or name) with the evidence for each
+        #[arg(long, value_name = "COMPONENT")]
+        explain: Option<String>,
+
+        /// Drop components and edges with a lower confidence (0 to 1)
+        #[arg(long, value_name = "SCORE")]
+        min_confidence: Option<f64>,
+
+        /// Record commits, authors and churn per component from the local git history
+        #[arg(long)]
+        git: bool,
+
+        /// Start of the `--git` window, as `git log --since` accepts it
+        #[arg(long, value_name = "DATE", default_value = "1 year ago", requires = "git")]
+        git_since: String,
+    },
+    /// Scan several repositories into one map, linking calls between them
+    ScanWorkspace {
+        /// Repository roots, each named after its directory
+        roots: Vec<PathBuf>,
+
+        /// TOML manifest with `[[repos]]` entries of `name` and `path`
+        #[arg(short, long)]
+        manifest: Option<PathBuf>,
+
+        /// Output JSON file path
+        #[arg(short, long, default_value = "sysvista-output.json")]
+        output: PathBuf,
+    },
+    /// Combine existing scan outputs into one JSON architecture map
+    Merge {
+        /// Scan output JSON files to combine
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Project name of the combined output
+        #[arg(long, default_value = "merged")]
+        name: String,
+
+        /// Output JSON file path
+        #[arg(short, long, default_value = "sysvista-output.json")]
+        output: PathBuf,
+    },
+    /// Query a scan output with a graph pattern, e.g. `(t:transport)-[*]->(:model {name: User})`
+    Query {
+        /// Pattern of nodes `(var:kind {key: value})` and hops `-[:label*min..max]->`, optionally ending in `return var, ...`
+        pattern: String,
+
+        /// Scan output JSON file
+        #[arg(short, long, default_value = "sysvista-output.json")]
+        input: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+
+        /// Stop after this many matches
+        #[arg(long, default_value_t = 100)]
+        limit: usize,
+    },
+    /// Check a scan output against architecture rules, exiting non-zero on errors
+    Check {
+        /// TOML rules file with `[[rules]]` entries
+        #[arg(short, long, default_value = "sysvista-rules.toml")]
+        rules: PathBuf,
+
+        /// Scan output JSON file
+        #[arg(short, long, default_value = "sysvista-output.json")]
+        input: PathBuf,
+
+        /// Report format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Print a report from the analysis of a scan output
+    Report {
+        /// Report to print
+        #[arg(value_enum)]
+        report: Report,
+
+        /// Scan output JSON file
+        #[arg(short, long, default_value = "sysvista-output.json")]
+        input: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
     },
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Report {
+    /// Dependency cycles over imports and calls, largest first
+    Cycles,
+    /// Fan-in, fan-out, betweenness and hub score per component
+    Metrics,
+    /// Afferent and efferent coupling and instability per package or directory
+    Modules,
+    /// Models, services, transforms and prompts nothing uses, with the reason
+    Dead,
+    /// Components both depended on and changing often (scans with `--git`)
+    Hotspots,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Scan { path, output } => {
+        Commands::Scan {
+            path,
+            output,
+            explain,
+            min_confidence,
+            git,
+            git_since,
+        } => {
             let root = path.canonicalize().unwrap_or_else(|e| {
                 eprintln!("Error: cannot resolve path '{}': {e}", path.display());
                 std::process::exit(1);
             });
 
+            if let Err(e) = scanner::config::load(&root) {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            }
+
             eprintln!("Scanning {}...", root.display());
 
-            let result = scanner::scan(&root);
+            let mut result = scanner::scan(&root);
+            if git {
+                scanner::history::annotate(&root, &mut result.components, &git_since).unwrap_or_else(|e| {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                });
+                analysis::analyze(&mut result);
+            }
+            if let Some(min) = min_confidence {
+                scanner::confidence::filter_by_confidence(&mut result, min);
+            }
 
             eprintln!(
                 "Found {} components, {} edges across {} languages ({} files scanned in {}ms)",
@@ -53,6 +195,209 @@ fn main() {
             });
 
             eprintln!("Output written to {}", output.display());
+
+            if let Some(query) = explain {
+                let lines = output::explain::explain(&result, &query);
+                if lines.is_empty() {
+                    eprintln!("No edges for '{query}'");
+                }
+                for line in lines {
+                    println!("{line}");
+                }
+            }
+        }
+        Commands::ScanWorkspace {
+            roots,
+            manifest,
+            output,
+        } => {
+            let (name, root_dir, mut repos) = match &manifest {
+                Some(path) => {
+                    let loaded = scanner::workspace::load_manifest(path).unwrap_or_else(|e| {
+                        eprintln!("Error: {e}");
+                        std::process::exit(1);
+                    });
+                    let dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+                    (loaded.name.unwrap_or_else(|| "workspace".to_string()), dir, loaded.repos)
+                }
+                None => ("workspace".to_string(), PathBuf::from("."), Vec::new()),
+            };
+            let from_roots = scanner::workspace::repos_from_roots(&roots, &repos);
+            repos.extend(from_roots);
+            if repos.is_empty() {
+                eprintln!("Error: pass repository roots or --manifest");
+                std::process::exit(1);
+            }
+            for repo in &mut repos {
+                repo.path = repo.path.canonicalize().unwrap_or_else(|e| {
+                    eprintln!("Error: cannot resolve path '{}': {e}", repo.path.display());
+                    std::process::exit(1);
+                });
+                if let Err(e) = scanner::config::load(&repo.path) {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
+            let root_dir = root_dir.canonicalize().unwrap_or(root_dir);
+
+            eprintln!("Scanning {} repositories...", repos.len());
+
+            let result = scanner::workspace::scan_workspace(&name, &root_dir, &repos);
+
+            eprintln!(
+                "Found {} components, {} edges across {} repositories ({} files scanned in {}ms)",
+                result.components.len(),
+                result.edges.len(),
+                result.repositories.len(),
+                result.scan_stats.files_scanned,
+                result.scan_stats.scan_duration_ms,
+            );
+
+            output::writer::write_json(&result, &output).unwrap_or_else(|e| {
+                eprintln!("Error writing output: {e}");
+                std::process::exit(1);
+            });
+
+            eprintln!("Output written to {}", output.display());
+        }
+        Commands::Merge { inputs, name, output } => {
+            let outputs: Vec<_> = inputs
+                .iter()
+                .map(|path| {
+                    let parsed = output::reader::read_json(path).unwrap_or_else(|e| {
+                        eprintln!("Error reading '{}': {e}", path.display());
+                        std::process::exit(1);
+                    });
+                    (path.display().to_string(), parsed)
+                })
+                .collect();
+
+            let root_dir = std::env::current_dir().unwrap_or_default();
+            let result = output::merge::merge_outputs(&name, &root_dir.to_string_lossy(), outputs);
+
+            eprintln!(
+                "Merged {} files: {} components, {} edges across {} languages",
+                inputs.len(),
+                result.components.len(),
+                result.edges.len(),
+                result.detected_languages.len(),
+            );
+
+            output::writer::write_json(&result, &output).unwrap_or_else(|e| {
+                eprintln!("Error writing output: {e}");
+                std::process::exit(1);
+            });
+
+            eprintln!("Output written to {}", output.display());
+        }
+        Commands::Query {
+            pattern,
+            input,
+            format,
+            limit,
+        } => {
+            let query = output::query::parse(&pattern).unwrap_or_else(|e| {
+                eprintln!("Error: invalid query: {e}");
+                std::process::exit(1);
+            });
+            let scan = output::reader::read_json(&input).unwrap_or_else(|e| {
+                eprintln!("Error reading '{}': {e}", input.display());
+                std::process::exit(1);
+            });
+
+            let matches = output::query::run(&scan, &query, limit);
+            match format {
+                Format::Table => print!("{}", output::query::render_table(&query, &matches)),
+                Format::Json => {
+                    let json = output::query::render_json(&query, &matches);
+                    println!("{}", serde_json::to_string_pretty(&json).unwrap_or_default());
+                }
+            }
+
+            if matches.len() >= limit {
+                eprintln!("{} matches (stopped at --limit {limit})", matches.len());
+            } else {
+                eprintln!("{} matches", matches.len());
+            }
+        }
+        Commands::Check {
+            rules,
+            input,
+            format,
+            output,
+        } => {
+            let rule_set = output::check::load_rules(&rules).unwrap_or_else(|e| {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            });
+            let scan = output::reader::read_json(&input).unwrap_or_else(|e| {
+                eprintln!("Error reading '{}': {e}", input.display());
+                std::process::exit(1);
+            });
+            let violations = output::check::check(&scan, &rule_set).unwrap_or_else(|e| {
+                eprintln!("Error: {e}");
+                std::process::exit(1);
+            });
+
+            let report = match format {
+                ReportFormat::Text => output::check::render_text(&violations),
+                ReportFormat::Json => serde_json::to_string_pretty(&violations).unwrap_or_default() + "\n",
+                ReportFormat::Sarif => {
+                    let sarif = output::check::render_sarif(&rule_set, &violations);
+                    serde_json::to_string_pretty(&sarif).unwrap_or_default() + "\n"
+                }
+            };
+            match &output {
+                Some(path) => std::fs::write(path, report).unwrap_or_else(|e| {
+                    eprintln!("Error writing '{}': {e}", path.display());
+                    std::process::exit(1);
+                }),
+                None => print!("{report}"),
+            }
+
+            let errors = output::check::error_count(&violations);
+            eprintln!(
+                "{} rules checked: {errors} errors, {} warnings",
+                rule_set.rules.len(),
+                violations.len() - errors
+            );
+            if errors > 0 {
+                std::process::exit(1);
+            }
+        }
+        Commands::Report { report, input, format } => {
+            let mut scan = output::reader::read_json(&input).unwrap_or_else(|e| {
+                eprintln!("Error reading '{}': {e}", input.display());
+                std::process::exit(1);
+            });
+            // Outputs written before an analysis existed still get a report
+            analysis::analyze(&mut scan);
+
+            match (report, format) {
+                (Report::Cycles, Format::Table) => print!("{}", output::report::render_cycles(&scan)),
+                (Report::Cycles, Format::Json) => {
+                    println!("{}", serde_json::to_string_pretty(&scan.analysis.cycles).unwrap_or_default());
+                }
+                (Report::Metrics, Format::Table) => print!("{}", output::report::render_metrics(&scan)),
+                (Report::Metrics, Format::Json) => {
+                    let json = output::report::metrics_json(&scan);
+                    println!("{}", serde_json::to_string_pretty(&json).unwrap_or_default());
+                }
+                (Report::Modules, Format::Table) => print!("{}", output::report::render_modules(&scan)),
+                (Report::Modules, Format::Json) => {
+                    println!("{}", serde_json::to_string_pretty(&scan.analysis.modules).unwrap_or_default());
+                }
+                (Report::Dead, Format::Table) => print!("{}", output::report::render_dead(&scan)),
+                (Report::Dead, Format::Json) => {
+                    let json = output::report::dead_json(&scan);
+                    println!("{}", serde_json::to_string_pretty(&json).unwrap_or_default());
+                }
+                (Report::Hotspots, Format::Table) => print!("{}", output::report::render_hotspots(&scan)),
+                (Report::Hotspots, Format::Json) => {
+                    let json = output::report::hotspots_json(&scan);
+                    println!("{}", serde_json::to_string_pretty(&json).unwrap_or_default());
+                }
+            }
         }
     }
 }
@@ -0,0 +1,546 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::query::{self, Query};
+use super::schema::{DetectedComponent, EdgeEvidence, Grouping, SourceLocation, SysVistaOutput};
+use crate::analysis::cycles::find_cycles;
+
+/// Matches reported per `forbid` rule at most
+const MAX_MATCHES: usize = 1000;
+
+/// Architecture rules, read from a TOML file of `[[rules]]` tables.
+#[derive(Debug, Deserialize)]
+pub struct RuleSet {
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+/// One rule: a pattern that must not match, a workflow requirement or a ban
+/// on dependency cycles. Exactly one of `forbid`, `workflow` and `no_cycles`
+/// is set.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub name: String,
+    pub message: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+    /// Graph pattern (as for `query`) that must match nothing
+    pub forbid: Option<String>,
+    pub workflow: Option<WorkflowRule>,
+    pub no_cycles: Option<CycleRule>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
+/// Workflows with a step matching `contains` (and an entry matching `entry`)
+/// must also have a step matching `requires`. Each is a single-node pattern.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowRule {
+    pub entry: Option<String>,
+    pub contains: String,
+    pub requires: String,
+}
+
+/// No cycles among components, files, directories or packages along edges
+/// with one of `labels` (any edge when empty).
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CycleRule {
+    #[serde(default)]
+    pub group_by: Grouping,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// A broken rule, with the components involved (the first is where it is
+/// reported) and the evidence of the edges involved.
+#[derive(Debug, Serialize)]
+pub struct Violation {
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    pub components: Vec<ComponentRef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<EdgeEvidence>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComponentRef {
+    pub id: String,
+    pub name: String,
+    pub source: SourceLocation,
+}
+
+impl ComponentRef {
+    fn of(comp: &DetectedComponent) -> Self {
+        ComponentRef {
+            id: comp.id.clone(),
+            name: comp.name.clone(),
+            source: comp.source.clone(),
+        }
+    }
+}
+
+/// Evidence without repeats: many edges can share one import line
+fn distinct(evidence: impl IntoIterator<Item = EdgeEvidence>) -> Vec<EdgeEvidence> {
+    let mut seen = HashSet::new();
+    evidence
+        .into_iter()
+        .filter(|e| seen.insert((e.heuristic.clone(), e.source.file.clone(), e.source.line_start)))
+        .collect()
+}
+
+impl Rule {
+    fn message(&self) -> &str {
+        self.message.as_deref().unwrap_or(&self.name)
+    }
+}
+
+pub fn load_rules(path: &Path) -> Result<RuleSet, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("cannot read '{}': {e}", path.display()))?;
+    toml::from_str(&text).map_err(|e| format!("invalid rules '{}': {e}", path.display()))
+}
+
+/// Evaluate every rule against `output`. Fails on a rule that is malformed
+/// rather than broken.
+pub fn check(output: &SysVistaOutput, rules: &RuleSet) -> Result<Vec<Violation>, String> {
+    let mut violations = Vec::new();
+    for rule in &rules.rules {
+        let found = match (&rule.forbid, &rule.workflow, &rule.no_cycles) {
+            (Some(pattern), None, None) => check_forbid(output, rule, pattern),
+            (None, Some(workflow), None) => check_workflow(output, rule, workflow),
+            (None, None, Some(cycles)) => Ok(check_cycles(output, rule, cycles)),
+            _ => Err("set exactly one of `forbid`, `workflow` and `no_cycles`".to_string()),
+        };
+        violations.extend(found.map_err(|e| format!("rule '{}': {e}", rule.name))?);
+    }
+    Ok(violations)
+}
+
+fn check_forbid(output: &SysVistaOutput, rule: &Rule, pattern: &str) -> Result<Vec<Violation>, String> {
+    let query = query::parse(pattern)?;
+    Ok(query::run(output, &query, MAX_MATCHES)
+        .iter()
+        .map(|m| {
+            let mut seen = HashSet::new();
+            Violation {
+                rule: rule.name.clone(),
+                severity: rule.severity,
+                message: format!("{}: {}", rule.message(), query::describe_path(m)),
+                components: m
+                    .path
+                    .iter()
+                    .filter(|c| seen.insert(c.id.as_str()))
+                    .map(|c| ComponentRef::of(c))
+                    .collect(),
+                evidence: distinct(m.steps.iter().flat_map(|s| s.evidence.iter().cloned())),
+            }
+        })
+        .collect())
+}
+
+fn node_pattern(pattern: &str) -> Result<Query, String> {
+    let query = query::parse(pattern)?;
+    if !query.is_node() {
+        return Err(format!("'{pattern}' must be a single node pattern"));
+    }
+    Ok(query)
+}
+
+fn check_workflow(output: &SysVistaOutput, rule: &Rule, workflow: &WorkflowRule) -> Result<Vec<Violation>, String> {
+    let entries = match &workflow.entry {
+        Some(pattern) => Some(query::matching_ids(output, &node_pattern(pattern)?)),
+        None => None,
+    };
+    let contains = query::matching_ids(output, &node_pattern(&workflow.contains)?);
+    let requires = query::matching_ids(output, &node_pattern(&workflow.requires)?);
+    let component = |id: &str| output.components.iter().find(|c| c.id == id);
+
+    let mut violations = Vec::new();
+    for wf in &output.workflows {
+        if entries.as_ref().is_some_and(|e| !e.contains(wf.entry_point_id.as_str())) {
+            continue;
+        }
+        let steps: HashSet<&str> = wf.steps.iter().map(|s| s.component_id.as_str()).collect();
+        if steps.iter().any(|id| requires.contains(id)) {
+            continue;
+        }
+        let Some(found) = wf.steps.iter().find(|s| contains.contains(s.component_id.as_str())) else {
+            continue;
+        };
+        let Some(reached) = component(&found.component_id) else {
+            continue;
+        };
+        // The edges by which the workflow reaches the offending step
+        let evidence = distinct(
+            output
+                .edges
+                .iter()
+                .filter(|e| e.to_id == reached.id && steps.contains(e.from_id.as_str()))
+                .flat_map(|e| e.relations().into_iter().filter_map(|r| r.evidence)),
+        );
+        let mut components = vec![ComponentRef::of(reached)];
+        if let Some(entry) = component(&wf.entry_point_id).filter(|e| e.id != reached.id) {
+            components.push(ComponentRef::of(entry));
+        }
+        violations.push(Violation {
+            rule: rule.name.clone(),
+            severity: rule.severity,
+            message: format!(
+                "{}: workflow '{}' reaches {} without {}",
+                rule.message(),
+                wf.name,
+                reached.name,
+                workflow.requires
+            ),
+            components,
+            evidence,
+        });
+    }
+    Ok(violations)
+}
+
+fn check_cycles(output: &SysVistaOutput, rule: &Rule, cycles: &CycleRule) -> Vec<Violation> {
+    let component = |id: &str| output.components.iter().find(|c| c.id == id);
+    find_cycles(output, cycles.group_by, &cycles.labels)
+        .into_iter()
+        .map(|cycle| {
+            let members: Vec<String> = match cycles.group_by {
+                Grouping::Component => cycle
+                    .members
+                    .iter()
+                    .map(|id| component(id).map_or(id.clone(), |c| c.name.clone()))
+                    .collect(),
+                _ => cycle.members.clone(),
+            };
+            // One component per member of the cycle, the source of its first edge
+            let mut seen = HashSet::new();
+            let components = cycle
+                .edges
+                .iter()
+                .filter_map(|e| component(&e.from_id))
+                .filter(|c| seen.insert(cycles.group_by.key(c)))
+                .map(ComponentRef::of)
+                .collect();
+            let evidence = distinct(
+                cycle
+                    .edges
+                    .iter()
+                    .flat_map(|e| e.relations())
+                    .filter(|r| cycles.labels.is_empty() || cycles.labels.contains(&r.label))
+                    .filter_map(|r| r.evidence),
+            );
+            Violation {
+                rule: rule.name.clone(),
+                severity: rule.severity,
+                message: format!("{}: cycle between {}", rule.message(), members.join(", ")),
+                components,
+                evidence,
+            }
+        })
+        .collect()
+}
+
+/// Violations of `error` rules; any makes `check` exit non-zero
+pub fn error_count(violations: &[Violation]) -> usize {
+    violations.iter().filter(|v| v.severity == Severity::Error).count()
+}
+
+// ---------------------------------------------------------------------------
+// Reports
+// ---------------------------------------------------------------------------
+
+fn location(source: &SourceLocation) -> String {
+    match source.line_start {
+        Some(line) => format!("{}:{line}", source.file),
+        None => source.file.clone(),
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+/// One block per violation: the rule and message, where it is reported and
+/// the evidence behind it.
+pub fn render_text(violations: &[Violation]) -> String {
+    let mut out = String::new();
+    for violation in violations {
+        out.push_str(&format!(
+            "{}[{}] {}\n",
+            severity_name(violation.severity),
+            violation.rule,
+            violation.message
+        ));
+        for comp in &violation.components {
+            out.push_str(&format!("  --> {} ({})\n", location(&comp.source), comp.name));
+        }
+        for evidence in &violation.evidence {
+            out.push_str(&format!("  {} at {}", evidence.heuristic, location(&evidence.source)));
+            if let Some(snippet) = &evidence.snippet {
+                out.push_str(&format!(": {snippet}"));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn sarif_location(source: &SourceLocation) -> Value {
+    let mut physical = json!({ "artifactLocation": { "uri": source.file } });
+    if let Some(line) = source.line_start {
+        physical["region"] = json!({ "startLine": line });
+    }
+    json!({ "physicalLocation": physical })
+}
+
+/// SARIF 2.1.0 log, for code scanning. Results are located at the first
+/// component of each violation; the other components and the edge evidence
+/// are related locations.
+pub fn render_sarif(rules: &RuleSet, violations: &[Violation]) -> Value {
+    let descriptors: Vec<Value> = rules
+        .rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.name,
+                "shortDescription": { "text": rule.message() },
+                "defaultConfiguration": { "level": severity_name(rule.severity) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = violations
+        .iter()
+        .map(|violation| {
+            let locations: Vec<Value> = violation.components.iter().take(1).map(|c| sarif_location(&c.source)).collect();
+            let related = violation
+                .components
+                .iter()
+                .skip(1)
+                .map(|c| (sarif_location(&c.source), c.name.clone()))
+                .chain(violation.evidence.iter().map(|e| {
+                    let text = match &e.snippet {
+                        Some(snippet) => format!("{}: {snippet}", e.heuristic),
+                        None => e.heuristic.clone(),
+                    };
+                    (sarif_location(&e.source), text)
+                }))
+                .enumerate()
+                .map(|(i, (mut location, text))| {
+                    location["id"] = json!(i);
+                    location["message"] = json!({ "text": text });
+                    location
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "ruleId": violation.rule,
+                "level": severity_name(violation.severity),
+                "message": { "text": violation.message },
+                "locations": locations,
+                "relatedLocations": related,
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "sysvista",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": descriptors,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::{component, edge, output};
+    use crate::output::schema::{ComponentKind, DetectedEdge, StepType, Workflow, WorkflowStep};
+
+    /// An edge whose evidence points into the frontend
+    fn call(from: &str, to: &str, label: &str) -> DetectedEdge {
+        DetectedEdge {
+            evidence: Some(EdgeEvidence {
+                heuristic: "module_call".to_string(),
+                source: SourceLocation {
+                    file: "frontend/app.py".to_string(),
+                    line_start: Some(7),
+                    line_end: None,
+                },
+                snippet: Some(format!("{to}(")),
+            }),
+            ..edge(from, to, label)
+        }
+    }
+
+    fn scan() -> SysVistaOutput {
+        let step = |id: &str, step_type, order| WorkflowStep {
+            component_id: id.to_string(),
+            step_type,
+            order,
+        };
+        SysVistaOutput {
+            workflows: vec![Workflow {
+                id: "wf".to_string(),
+                name: "POST /checkout".to_string(),
+                entry_point_id: "checkout".to_string(),
+                steps: vec![
+                    step("checkout", StepType::Entry, 0),
+                    step("classify", StepType::Prompt, 1),
+                    step("charge", StepType::Call, 2),
+                ],
+            }],
+            ..output(
+                vec![
+                    component("checkout", ComponentKind::Transport, "frontend/app.py"),
+                    component("charge", ComponentKind::Service, "billing/charge.py"),
+                    component("refund", ComponentKind::Service, "billing/refund.py"),
+                    component("classify", ComponentKind::Prompt, "frontend/prompts.py"),
+                ],
+                vec![
+                    call("checkout", "charge", "calls"),
+                    call("checkout", "classify", "invokes_prompt"),
+                    call("charge", "refund", "imports"),
+                    call("refund", "charge", "imports"),
+                ],
+            )
+        }
+    }
+
+    const RULES: &str = r#"
+[[rules]]
+name = "billing-is-private"
+message = "Nothing in billing/ may be called from frontend/"
+forbid = "(a {file: frontend/*})-[:calls]->(b {file: billing/*})"
+
+[[rules]]
+name = "validated-prompts"
+severity = "warning"
+[rules.workflow]
+entry = "(:transport)"
+contains = "(:prompt)"
+requires = "(:prompt {subtype: validator})"
+
+[[rules]]
+name = "no-directory-cycles"
+[rules.no_cycles]
+group_by = "directory"
+labels = ["imports"]
+
+[[rules]]
+name = "no-component-cycles"
+[rules.no_cycles]
+labels = ["imports"]
+"#;
+
+    #[test]
+    fn reports_violations_of_each_rule_kind() {
+        let rules: RuleSet = toml::from_str(RULES).unwrap();
+        let violations = check(&scan(), &rules).unwrap();
+        let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Nothing in billing/ may be called from frontend/: checkout -[calls]-> charge",
+                "validated-prompts: workflow 'POST /checkout' reaches classify without (:prompt {subtype: validator})",
+                "no-component-cycles: cycle between charge, refund",
+            ]
+        );
+        assert_eq!(violations[0].evidence[0].snippet.as_deref(), Some("charge("));
+        assert_eq!(violations[1].severity, Severity::Warning);
+        assert_eq!(violations[2].components.len(), 2);
+    }
+
+    #[test]
+    fn fails_only_on_error_severity() {
+        let rules: RuleSet = toml::from_str(RULES).unwrap();
+        let violations = check(&scan(), &rules).unwrap();
+        assert_eq!(error_count(&violations), 2);
+
+        let mut warnings = rules;
+        warnings.rules.iter_mut().for_each(|r| r.severity = Severity::Warning);
+        let violations = check(&scan(), &warnings).unwrap();
+        assert_eq!(violations.len(), 3);
+        assert_eq!(error_count(&violations), 0);
+    }
+
+    #[test]
+    fn renders_a_sarif_2_1_0_log() {
+        let rules: RuleSet = toml::from_str(RULES).unwrap();
+        let sarif = render_sarif(&rules, &check(&scan(), &rules).unwrap());
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif["$schema"], "https://json.schemastore.org/sarif-2.1.0.json");
+
+        let driver = &sarif["runs"][0]["tool"]["driver"];
+        assert_eq!(driver["name"], "sysvista");
+        let descriptors: Vec<(&str, &str, &str)> = driver["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                let text = r["shortDescription"]["text"].as_str().unwrap();
+                (r["id"].as_str().unwrap(), r["defaultConfiguration"]["level"].as_str().unwrap(), text)
+            })
+            .collect();
+        assert_eq!(
+            descriptors,
+            vec![
+                ("billing-is-private", "error", "Nothing in billing/ may be called from frontend/"),
+                ("validated-prompts", "warning", "validated-prompts"),
+                ("no-directory-cycles", "error", "no-directory-cycles"),
+                ("no-component-cycles", "error", "no-component-cycles"),
+            ]
+        );
+
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[1]["level"], "warning");
+        let forbid = &results[0];
+        assert_eq!(forbid["ruleId"], "billing-is-private");
+        assert_eq!(forbid["level"], "error");
+        assert_eq!(forbid["message"]["text"], "Nothing in billing/ may be called from frontend/: checkout -[calls]-> charge");
+        assert_eq!(forbid["locations"].as_array().unwrap().len(), 1);
+        assert_eq!(forbid["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "frontend/app.py");
+        assert_eq!(forbid["locations"][0]["physicalLocation"]["region"]["startLine"], 1);
+        let related = &forbid["relatedLocations"];
+        assert_eq!(related.as_array().unwrap().len(), 2);
+        assert_eq!((&related[0]["id"], &related[0]["message"]["text"]), (&json!(0), &json!("charge")));
+        assert_eq!(related[0]["physicalLocation"]["artifactLocation"]["uri"], "billing/charge.py");
+        assert_eq!((&related[1]["id"], &related[1]["message"]["text"]), (&json!(1), &json!("module_call: charge(")));
+        assert_eq!(related[1]["physicalLocation"]["region"]["startLine"], 7);
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        let rules: RuleSet = toml::from_str("[[rules]]\nname = \"empty\"\n").unwrap();
+        assert_eq!(
+            check(&scan(), &rules).unwrap_err(),
+            "rule 'empty': set exactly one of `forbid`, `workflow` and `no_cycles`"
+        );
+        let rules: RuleSet =
+            toml::from_str("[[rules]]\nname = \"w\"\n[rules.workflow]\ncontains = \"(a)-->(b)\"\nrequires = \"(x)\"\n").unwrap();
+        assert_eq!(check(&scan(), &rules).unwrap_err(), "rule 'w': '(a)-->(b)' must be a single node pattern");
+    }
+}
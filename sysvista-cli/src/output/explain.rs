@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use super::schema::{DetectedComponent, SysVistaOutput};
+
+/// Describe every relation into or out of the components whose ID or name is
+/// `query`: the relation, the heuristic that inferred it, where it matched and
+/// the matched text.
+///
+/// ```text
+/// create_msg_route --calls--> create_message
+///     module_call at src/routes/messages.py:5
+///     crud.create_message(
+/// ```
+pub fn explain(output: &SysVistaOutput, query: &str) -> Vec<String> {
+    let by_id: HashMap<&str, &DetectedComponent> = output.components.iter().map(|c| (c.id.as_str(), c)).collect();
+    let matches = |id: &str| id == query || by_id.get(id).is_some_and(|c| c.name == query);
+    let name = |id: &str| by_id.get(id).map_or(id.to_string(), |c| c.name.clone());
+
+    let mut lines = Vec::new();
+    for edge in output.edges.iter().filter(|e| matches(&e.from_id) || matches(&e.to_id)) {
+        for relation in edge.relations() {
+            lines.push(format!("{} --{}--> {}", name(&edge.from_id), relation.label, name(&edge.to_id)));
+            match &relation.evidence {
+                Some(evidence) => {
+                    let line = evidence.source.line_start.map(|l| format!(":{l}")).unwrap_or_default();
+                    lines.push(format!("    {} at {}{line}", evidence.heuristic, evidence.source.file));
+                    if let Some(snippet) = &evidence.snippet {
+                        lines.push(format!("    {snippet}"));
+                    }
+                }
+                None => lines.push("    (no evidence recorded)".to_string()),
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::{component, edge, output};
+    use crate::output::schema::{ComponentKind, DetectedEdge, EdgeEvidence, SourceLocation};
+
+    #[test]
+    fn explains_edges_touching_a_component() {
+        let named = |id: &str, name: &str| DetectedComponent {
+            name: name.to_string(),
+            ..component(id, ComponentKind::Service, "app.py")
+        };
+        let output = output(
+            vec![named("r", "create_msg_route"), named("c", "create_message"), named("x", "other")],
+            vec![
+                DetectedEdge {
+                    evidence: Some(EdgeEvidence {
+                        heuristic: "module_call".to_string(),
+                        source: SourceLocation {
+                            file: "src/routes/messages.py".to_string(),
+                            line_start: Some(5),
+                            line_end: None,
+                        },
+                        snippet: Some("crud.create_message(".to_string()),
+                    }),
+                    ..edge("r", "c", "calls")
+                },
+                edge("x", "c", "calls"),
+                edge("x", "r", "calls"),
+            ],
+        );
+
+        assert_eq!(
+            explain(&output, "create_message"),
+            vec![
+                "create_msg_route --calls--> create_message",
+                "    module_call at src/routes/messages.py:5",
+                "    crud.create_message(",
+                "other --calls--> create_message",
+                "    (no evidence recorded)",
+            ]
+        );
+        assert_eq!(explain(&output, "r").len(), 5);
+    }
+}
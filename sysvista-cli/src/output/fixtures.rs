@@ -0,0 +1,58 @@
+use super::schema::{
+    Analysis, ComponentKind, DetectedComponent, DetectedEdge, ScanStats, SourceLocation, SysVistaOutput,
+};
+
+// ---------------------------------------------------------------------------
+// Test builders — set anything else with `..component(...)` or `..output(...)`
+// ---------------------------------------------------------------------------
+
+/// A Python component at line 1 of `file`, named after its ID
+pub fn component(id: &str, kind: ComponentKind, file: &str) -> DetectedComponent {
+    DetectedComponent {
+        id: id.to_string(),
+        name: id.to_string(),
+        kind,
+        language: "python".to_string(),
+        source: SourceLocation {
+            file: file.to_string(),
+            line_start: Some(1),
+            line_end: None,
+        },
+        ..Default::default()
+    }
+}
+
+/// A labelled edge without evidence
+pub fn edge(from: &str, to: &str, label: &str) -> DetectedEdge {
+    DetectedEdge {
+        from_id: from.to_string(),
+        to_id: to.to_string(),
+        label: Some(label.to_string()),
+        payload_type: None,
+        evidence: None,
+        confidence: None,
+        relations: Vec::new(),
+    }
+}
+
+/// A scan holding just `components` and `edges`
+pub fn output(components: Vec<DetectedComponent>, edges: Vec<DetectedEdge>) -> SysVistaOutput {
+    SysVistaOutput {
+        version: "1".to_string(),
+        scanned_at: String::new(),
+        root_dir: String::new(),
+        project_name: String::new(),
+        detected_languages: Vec::new(),
+        components,
+        edges,
+        workflows: Vec::new(),
+        scan_stats: ScanStats {
+            files_scanned: 0,
+            files_skipped: 0,
+            scan_duration_ms: 0,
+        },
+        packages: Vec::new(),
+        repositories: Vec::new(),
+        analysis: Analysis::default(),
+    }
+}
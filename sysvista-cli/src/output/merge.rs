@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use super::schema::{Analysis, ScanStats, SysVistaOutput};
+use crate::analysis;
+use crate::scanner::relationships::merge_relations;
+
+/// Combine scan outputs into one. Components are de-duplicated by ID (the
+/// first occurrence wins and is tagged with the file it came from), edges are
+/// merged per pair of components, workflows are unioned (packages and
+/// repositories too), languages are merged, scan stats summed and the
+/// analysis recomputed.
+pub fn merge_outputs(project_name: &str, root_dir: &str, inputs: Vec<(String, SysVistaOutput)>) -> SysVistaOutput {
+    let mut merged = SysVistaOutput {
+        version: "1".to_string(),
+        scanned_at: chrono::Utc::now().to_rfc3339(),
+        root_dir: root_dir.to_string(),
+        project_name: project_name.to_string(),
+        detected_languages: Vec::new(),
+        components: Vec::new(),
+        edges: Vec::new(),
+        workflows: Vec::new(),
+        scan_stats: ScanStats {
+            files_scanned: 0,
+            files_skipped: 0,
+            scan_duration_ms: 0,
+        },
+        packages: Vec::new(),
+        repositories: Vec::new(),
+        analysis: Analysis::default(),
+    };
+
+    let mut component_ids = HashSet::new();
+    let mut workflow_ids = HashSet::new();
+    let mut package_keys = HashSet::new();
+    let mut repository_names = HashSet::new();
+    for (origin, output) in inputs {
+        for mut component in output.components {
+            if component_ids.insert(component.id.clone()) {
+                component.origin.get_or_insert_with(|| origin.clone());
+                merged.components.push(component);
+            }
+        }
+        merged.edges.extend(output.edges);
+        for workflow in output.workflows {
+            if workflow_ids.insert(workflow.id.clone()) {
+                merged.workflows.push(workflow);
+            }
+        }
+        for package in output.packages {
+            if package_keys.insert((package.name.clone(), package.path.clone())) {
+                merged.packages.push(package);
+            }
+        }
+        for repository in output.repositories {
+            if repository_names.insert(repository.name.clone()) {
+                merged.repositories.push(repository);
+            }
+        }
+        merged.detected_languages.extend(output.detected_languages);
+        merged.scan_stats.files_scanned += output.scan_stats.files_scanned;
+        merged.scan_stats.files_skipped += output.scan_stats.files_skipped;
+        merged.scan_stats.scan_duration_ms += output.scan_stats.scan_duration_ms;
+    }
+
+    merged.edges = merge_relations(std::mem::take(&mut merged.edges));
+    merged.detected_languages.sort();
+    merged.detected_languages.dedup();
+    analysis::analyze(&mut merged);
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::{self, component, edge};
+    use crate::output::schema::{ComponentKind, DetectedComponent, DetectedEdge};
+
+    fn output(languages: &[&str], components: Vec<DetectedComponent>, edges: Vec<DetectedEdge>, files: u64) -> SysVistaOutput {
+        SysVistaOutput {
+            detected_languages: languages.iter().map(|l| l.to_string()).collect(),
+            scan_stats: ScanStats {
+                files_scanned: files,
+                files_skipped: 1,
+                scan_duration_ms: 10,
+            },
+            ..fixtures::output(components, edges)
+        }
+    }
+
+    fn service(id: &str, file: &str) -> DetectedComponent {
+        component(id, ComponentKind::Service, file)
+    }
+
+    fn call(from: &str, to: &str) -> DetectedEdge {
+        edge(from, to, "calls")
+    }
+
+    #[test]
+    fn merges_components_edges_languages_and_stats() {
+        let a = output(&["python"], vec![service("a", "a.py"), service("shared", "s.py")], vec![call("a", "shared")], 3);
+        let b = output(
+            &["go", "python"],
+            vec![service("shared", "s.py"), service("b", "b.go")],
+            vec![call("a", "shared"), call("b", "shared")],
+            4,
+        );
+
+        let merged = merge_outputs("platform", "/ci", vec![("a.json".to_string(), a), ("b.json".to_string(), b)]);
+
+        let origins: Vec<(&str, Option<&str>)> =
+            merged.components.iter().map(|c| (c.id.as_str(), c.origin.as_deref())).collect();
+        assert_eq!(origins, vec![("a", Some("a.json")), ("shared", Some("a.json")), ("b", Some("b.json"))]);
+        assert_eq!(merged.edges.len(), 2);
+        assert_eq!(merged.detected_languages, vec!["go", "python"]);
+        assert_eq!(merged.scan_stats.files_scanned, 7);
+        assert_eq!(merged.scan_stats.files_skipped, 2);
+        assert_eq!(merged.scan_stats.scan_duration_ms, 20);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let original = output(&["python"], vec![service("a", "a.py")], vec![call("a", "a")], 1);
+        let json = serde_json::to_string(&original).unwrap();
+        let parsed: SysVistaOutput = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.components[0].id, "a");
+        assert!(parsed.repositories.is_empty());
+    }
+}
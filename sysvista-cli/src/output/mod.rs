@@ -1,2 +1,11 @@
+pub mod check;
+pub mod explain;
+#[cfg(test)]
+pub mod fixtures;
+pub mod merge;
+pub mod query;
+pub mod reader;
+pub mod report;
 pub mod schema;
+pub mod table;
 pub mod writer;
@@ -0,0 +1,803 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde_json::{Value, json};
+
+use super::schema::{DetectedComponent, DetectedEdge, EdgeEvidence, SysVistaOutput};
+use super::table;
+
+// ---------------------------------------------------------------------------
+// Graph patterns over a scan output
+//
+//   (t:transport)-[*]->()-[:persists]->(:model {name: User}) return t
+//   (caller)-[:calls]->({name: send_email})
+//   ({name: "POST /orders"})-[*..6]->(:prompt)
+//   (m:model {in: 0})
+//
+// A node is `(var:kind {key: value, ...})`, every part optional. Text values
+// match exactly or as a `*` glob; `in`, `out`, `confidence` and the metrics
+// `fan_in`, `fan_out`, `betweenness` and `hub` take a number, optionally after
+// `<`, `<=`, `>` or `>=`. A hop is `-[:label|label*min..max]->`,
+// `<-[...]-` against the edge direction, or `-->` / `<--` for any single edge.
+// `return` lists the variables to report, once per distinct combination;
+// without it every matched path is reported.
+// ---------------------------------------------------------------------------
+
+/// Upper bound on the hops of an open-ended `*` segment
+const MAX_HOPS: usize = 8;
+
+const TEXT_KEYS: &[&str] = &[
+    "id", "name", "kind", "language", "file", "package", "repo", "origin", "protocol", "method", "path", "subtype",
+];
+const NUMERIC_KEYS: &[&str] = &["in", "out", "confidence", "fan_in", "fan_out", "betweenness", "hub"];
+
+#[derive(Debug)]
+pub struct Query {
+    nodes: Vec<NodePattern>,
+    hops: Vec<HopPattern>,
+    returns: Vec<String>,
+}
+
+#[derive(Debug)]
+struct NodePattern {
+    var: Option<String>,
+    filters: Vec<Filter>,
+}
+
+#[derive(Debug)]
+enum Filter {
+    Text { key: String, pattern: String },
+    Number { key: String, op: Op, value: f64 },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug)]
+struct HopPattern {
+    labels: Vec<String>,
+    reverse: bool,
+    min: usize,
+    max: usize,
+}
+
+/// One edge crossed by a match, with the labels that satisfied the hop and
+/// their evidence.
+#[derive(Clone)]
+pub struct Step {
+    pub labels: Vec<String>,
+    pub reverse: bool,
+    pub evidence: Vec<EdgeEvidence>,
+}
+
+/// A matched path: its components, the edges between them and the path
+/// position bound to each node of the pattern.
+pub struct Match<'a> {
+    pub path: Vec<&'a DetectedComponent>,
+    pub steps: Vec<Step>,
+    bound: Vec<usize>,
+}
+
+impl Filter {
+    fn parse(key: String, value: String) -> Result<Filter, String> {
+        if NUMERIC_KEYS.contains(&key.as_str()) {
+            let (op, number) = [(">=", Op::Ge), ("<=", Op::Le), (">", Op::Gt), ("<", Op::Lt), ("=", Op::Eq)]
+                .into_iter()
+                .find_map(|(prefix, op)| value.strip_prefix(prefix).map(|rest| (op, rest)))
+                .unwrap_or((Op::Eq, value.as_str()));
+            let number = number
+                .trim()
+                .parse()
+                .map_err(|_| format!("'{key}' takes a number, got '{value}'"))?;
+            Ok(Filter::Number { key, op, value: number })
+        } else if TEXT_KEYS.contains(&key.as_str()) || key.starts_with("metadata.") {
+            Ok(Filter::Text { key, pattern: value })
+        } else {
+            Err(format!("unknown property '{key}'"))
+        }
+    }
+}
+
+impl Op {
+    fn holds(self, actual: f64, expected: f64) -> bool {
+        match self {
+            Op::Eq => actual == expected,
+            Op::Lt => actual < expected,
+            Op::Le => actual <= expected,
+            Op::Gt => actual > expected,
+            Op::Ge => actual >= expected,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+pub fn parse(text: &str) -> Result<Query, String> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+    };
+    let mut nodes = vec![parser.node()?];
+    let mut hops = Vec::new();
+    while matches!(parser.peek(), Some('-' | '<')) {
+        hops.push(parser.hop()?);
+        nodes.push(parser.node()?);
+    }
+
+    let mut returns = Vec::new();
+    if parser.keyword("return") {
+        loop {
+            returns.push(parser.ident().ok_or_else(|| parser.error("expected a variable"))?);
+            if !parser.eat(',') {
+                break;
+            }
+        }
+    }
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected input"));
+    }
+
+    let mut vars = HashSet::new();
+    for var in nodes.iter().filter_map(|n| n.var.as_deref()) {
+        if !vars.insert(var) {
+            return Err(format!("variable '{var}' is bound twice"));
+        }
+    }
+    if let Some(var) = returns.iter().find(|v| !vars.contains(v.as_str())) {
+        return Err(format!("unknown variable '{var}' in return"));
+    }
+    Ok(Query { nodes, hops, returns })
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> String {
+        format!("{message} at column {}", self.pos + 1)
+    }
+
+    /// Next character after whitespace
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        self.peek();
+        let found = self.chars[self.pos..].iter().take(s.len()).copied().eq(s.chars());
+        if found {
+            self.pos += s.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{c}'")))
+        }
+    }
+
+    fn take_while(&mut self, keep: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|&c| keep(c)) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        self.peek();
+        let ident = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        (!ident.is_empty()).then_some(ident)
+    }
+
+    fn keyword(&mut self, word: &str) -> bool {
+        let start = self.pos;
+        match self.ident() {
+            Some(ident) if ident.eq_ignore_ascii_case(word) => true,
+            _ => {
+                self.pos = start;
+                false
+            }
+        }
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        self.peek();
+        self.take_while(|c| c.is_ascii_digit()).parse().ok()
+    }
+
+    /// A quoted string, or everything up to the next `,` or `}`
+    fn value(&mut self) -> Result<String, String> {
+        let value = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let value = self.take_while(|c| c != quote);
+                self.expect(quote)?;
+                value
+            }
+            _ => self.take_while(|c| c != ',' && c != '}').trim().to_string(),
+        };
+        if value.is_empty() {
+            return Err(self.error("expected a value"));
+        }
+        Ok(value)
+    }
+
+    fn node(&mut self) -> Result<NodePattern, String> {
+        self.expect('(')?;
+        let var = self.ident();
+        let mut filters = Vec::new();
+        if self.eat(':') {
+            let kind = self.ident().ok_or_else(|| self.error("expected a kind"))?;
+            filters.push(Filter::Text {
+                key: "kind".to_string(),
+                pattern: kind,
+            });
+        }
+        if self.eat('{') {
+            loop {
+                self.peek();
+                let key = self.take_while(|c| c != ':' && c != '}').trim().to_string();
+                self.expect(':')?;
+                filters.push(Filter::parse(key, self.value()?)?);
+                if !self.eat(',') {
+                    break;
+                }
+            }
+            self.expect('}')?;
+        }
+        self.expect(')')?;
+        Ok(NodePattern { var, filters })
+    }
+
+    fn hop(&mut self) -> Result<HopPattern, String> {
+        let reverse = self.eat_str("<-");
+        if !reverse {
+            self.expect('-')?;
+        }
+        let mut hop = HopPattern {
+            labels: Vec::new(),
+            reverse,
+            min: 1,
+            max: 1,
+        };
+        if self.eat('[') {
+            if self.eat(':') {
+                loop {
+                    hop.labels.push(self.ident().ok_or_else(|| self.error("expected a label"))?);
+                    if !self.eat('|') {
+                        break;
+                    }
+                }
+            }
+            if self.eat('*') {
+                let min = self.number();
+                if self.eat_str("..") {
+                    hop.min = min.unwrap_or(1);
+                    hop.max = self.number().unwrap_or(MAX_HOPS);
+                } else {
+                    hop.min = min.unwrap_or(1);
+                    hop.max = min.unwrap_or(MAX_HOPS);
+                }
+                if hop.min > hop.max {
+                    return Err(self.error("path length range is empty"));
+                }
+            }
+            self.expect(']')?;
+        }
+        self.expect('-')?;
+        if !reverse {
+            self.expect('>')?;
+        }
+        Ok(hop)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Matching
+// ---------------------------------------------------------------------------
+
+struct Graph<'a> {
+    components: &'a [DetectedComponent],
+    outgoing: HashMap<usize, Vec<(usize, &'a DetectedEdge)>>,
+    incoming: HashMap<usize, Vec<(usize, &'a DetectedEdge)>>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(output: &'a SysVistaOutput) -> Self {
+        let index: HashMap<&str, usize> =
+            output.components.iter().enumerate().map(|(i, c)| (c.id.as_str(), i)).collect();
+        let mut graph = Graph {
+            components: &output.components,
+            outgoing: HashMap::new(),
+            incoming: HashMap::new(),
+        };
+        for edge in &output.edges {
+            if let (Some(&from), Some(&to)) = (index.get(edge.from_id.as_str()), index.get(edge.to_id.as_str())) {
+                graph.outgoing.entry(from).or_default().push((to, edge));
+                graph.incoming.entry(to).or_default().push((from, edge));
+            }
+        }
+        graph
+    }
+
+    fn degree(map: &HashMap<usize, Vec<(usize, &DetectedEdge)>>, i: usize) -> f64 {
+        map.get(&i).map_or(0, Vec::len) as f64
+    }
+
+    fn matches(&self, node: &NodePattern, i: usize) -> bool {
+        let comp = &self.components[i];
+        node.filters.iter().all(|filter| match filter {
+            Filter::Text { key, pattern } => property(comp, key).is_some_and(|value| glob(pattern, &value)),
+            Filter::Number { key, op, value } => {
+                let actual = match key.as_str() {
+                    "in" => Some(Self::degree(&self.incoming, i)),
+                    "out" => Some(Self::degree(&self.outgoing, i)),
+                    "confidence" => comp.confidence,
+                    key => comp.metrics.as_ref().map(|m| match key {
+                        "fan_in" => m.fan_in as f64,
+                        "fan_out" => m.fan_out as f64,
+                        "betweenness" => m.betweenness,
+                        _ => m.hub_score,
+                    }),
+                };
+                actual.is_some_and(|actual| op.holds(actual, *value))
+            }
+        })
+    }
+
+    /// Walks from `from` that satisfy `hop`: the shortest one of at least
+    /// `min` edges to each reachable component, as (components after `from`,
+    /// steps).
+    fn walks(&self, from: usize, hop: &HopPattern) -> Vec<(Vec<usize>, Vec<Step>)> {
+        let edges = if hop.reverse { &self.incoming } else { &self.outgoing };
+        let mut parents: HashMap<(usize, usize), (usize, &DetectedEdge)> = HashMap::new();
+        let mut reached: HashSet<usize> = HashSet::new();
+        let mut ends = Vec::new();
+        let mut queue = VecDeque::from([(from, 0)]);
+        while let Some((node, depth)) = queue.pop_front() {
+            if depth >= hop.min && reached.insert(node) {
+                ends.push((node, depth));
+            }
+            if depth == hop.max {
+                continue;
+            }
+            for &(next, edge) in edges.get(&node).into_iter().flatten() {
+                let allowed = hop.labels.is_empty() || hop.labels.iter().any(|l| edge.has_label(l));
+                if allowed && !parents.contains_key(&(next, depth + 1)) {
+                    parents.insert((next, depth + 1), (node, edge));
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+
+        ends.into_iter()
+            .map(|(end, depth)| {
+                let mut nodes = Vec::new();
+                let mut steps = Vec::new();
+                let (mut node, mut depth) = (end, depth);
+                while depth > 0 {
+                    let (prev, edge) = parents[&(node, depth)];
+                    nodes.push(node);
+                    let relations: Vec<_> = edge
+                        .relations()
+                        .into_iter()
+                        .filter(|r| hop.labels.is_empty() || hop.labels.contains(&r.label))
+                        .collect();
+                    steps.push(Step {
+                        labels: relations.iter().map(|r| r.label.clone()).collect(),
+                        reverse: hop.reverse,
+                        evidence: relations.into_iter().filter_map(|r| r.evidence).collect(),
+                    });
+                    node = prev;
+                    depth -= 1;
+                }
+                nodes.reverse();
+                steps.reverse();
+                (nodes, steps)
+            })
+            .collect()
+    }
+}
+
+struct Search<'q, 'a> {
+    query: &'q Query,
+    graph: Graph<'a>,
+    limit: usize,
+    matches: Vec<Match<'a>>,
+    seen: HashSet<Vec<usize>>,
+}
+
+impl<'a> Search<'_, 'a> {
+    fn extend(&mut self, path: Vec<usize>, steps: Vec<Step>, bound: Vec<usize>) {
+        let k = bound.len();
+        if k == self.query.nodes.len() {
+            self.record(path, steps, bound);
+            return;
+        }
+        let from = path[path.len() - 1];
+        for (nodes, more) in self.graph.walks(from, &self.query.hops[k - 1]) {
+            let end = nodes.last().copied().unwrap_or(from);
+            if !self.graph.matches(&self.query.nodes[k], end) {
+                continue;
+            }
+            let mut path = path.clone();
+            path.extend(nodes);
+            let mut bound = bound.clone();
+            bound.push(path.len() - 1);
+            let mut steps = steps.clone();
+            steps.extend(more);
+            self.extend(path, steps, bound);
+            if self.matches.len() >= self.limit {
+                return;
+            }
+        }
+    }
+
+    fn record(&mut self, path: Vec<usize>, steps: Vec<Step>, bound: Vec<usize>) {
+        // With `return`, each combination of the returned components once
+        if !self.query.returns.is_empty() {
+            let key = self
+                .query
+                .returns
+                .iter()
+                .map(|var| path[bound[self.query.position(var)]])
+                .collect();
+            if !self.seen.insert(key) {
+                return;
+            }
+        }
+        self.matches.push(Match {
+            path: path.iter().map(|&i| &self.graph.components[i]).collect(),
+            steps,
+            bound,
+        });
+    }
+}
+
+impl Query {
+    /// Whether the pattern is a single node, with no hops
+    pub fn is_node(&self) -> bool {
+        self.hops.is_empty()
+    }
+
+    fn position(&self, var: &str) -> usize {
+        self.nodes
+            .iter()
+            .position(|n| n.var.as_deref() == Some(var))
+            .unwrap_or_default()
+    }
+}
+
+/// Match `query` against `output`, stopping after `limit` matches.
+pub fn run<'a>(output: &'a SysVistaOutput, query: &Query, limit: usize) -> Vec<Match<'a>> {
+    let mut search = Search {
+        query,
+        graph: Graph::new(output),
+        limit,
+        matches: Vec::new(),
+        seen: HashSet::new(),
+    };
+    for i in 0..output.components.len() {
+        if search.matches.len() >= limit {
+            break;
+        }
+        if search.graph.matches(&query.nodes[0], i) {
+            search.extend(vec![i], Vec::new(), vec![0]);
+        }
+    }
+    search.matches
+}
+
+/// IDs of the components matching a single-node pattern
+pub fn matching_ids<'a>(output: &'a SysVistaOutput, query: &Query) -> HashSet<&'a str> {
+    let graph = Graph::new(output);
+    (0..output.components.len())
+        .filter(|&i| graph.matches(&query.nodes[0], i))
+        .map(|i| output.components[i].id.as_str())
+        .collect()
+}
+
+fn property(comp: &DetectedComponent, key: &str) -> Option<String> {
+    if let Some(key) = key.strip_prefix("metadata.") {
+        return comp.metadata.get(key).cloned();
+    }
+    match key {
+        "id" => Some(comp.id.clone()),
+        "name" => Some(comp.name.clone()),
+        "kind" => Some(comp.kind.as_str().to_string()),
+        "language" => Some(comp.language.clone()),
+        "file" => Some(comp.source.file.clone()),
+        "package" => comp.package.clone(),
+        "repo" => comp.repo.clone(),
+        "origin" => comp.origin.clone(),
+        "protocol" => comp.transport_protocol.as_ref().map(|p| p.as_str().to_string()),
+        "method" => comp.http_method.clone(),
+        "path" => comp.http_path.clone(),
+        "subtype" => comp.prompt_subtype.clone(),
+        _ => None,
+    }
+}
+
+/// Whole-string match where `*` stands for any run of characters
+pub fn glob(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+// ---------------------------------------------------------------------------
+// Output
+// ---------------------------------------------------------------------------
+
+pub fn location(comp: &DetectedComponent) -> String {
+    match comp.source.line_start {
+        Some(line) => format!("{}:{line}", comp.source.file),
+        None => comp.source.file.clone(),
+    }
+}
+
+fn summary(comp: &DetectedComponent) -> Value {
+    json!({
+        "id": comp.id,
+        "name": comp.name,
+        "kind": comp.kind,
+        "language": comp.language,
+        "source": comp.source,
+    })
+}
+
+/// `a -[calls]-> b <-[persists]- c`
+pub fn describe_path(m: &Match) -> String {
+    let mut text = m.path[0].name.clone();
+    for (step, comp) in m.steps.iter().zip(&m.path[1..]) {
+        let labels = step.labels.join("|");
+        if step.reverse {
+            text.push_str(&format!(" <-[{labels}]- {}", comp.name));
+        } else {
+            text.push_str(&format!(" -[{labels}]-> {}", comp.name));
+        }
+    }
+    text
+}
+
+/// Matches as a table: the returned variables, the matched components when
+/// the pattern is a single node, or the paths.
+pub fn render_table(query: &Query, matches: &[Match]) -> String {
+    if !query.returns.is_empty() {
+        let headers: Vec<&str> = query.returns.iter().map(String::as_str).collect();
+        let rows: Vec<Vec<String>> = matches
+            .iter()
+            .map(|m| {
+                query
+                    .returns
+                    .iter()
+                    .map(|var| {
+                        let comp = m.path[m.bound[query.position(var)]];
+                        format!("{} ({})", comp.name, location(comp))
+                    })
+                    .collect()
+            })
+            .collect();
+        return table::render(&headers, &rows);
+    }
+    if query.hops.is_empty() {
+        let rows: Vec<Vec<String>> = matches
+            .iter()
+            .map(|m| {
+                let comp = m.path[0];
+                vec![comp.name.clone(), comp.kind.as_str().to_string(), comp.language.clone(), location(comp)]
+            })
+            .collect();
+        return table::render(&["NAME", "KIND", "LANGUAGE", "SOURCE"], &rows);
+    }
+    let rows: Vec<Vec<String>> = matches
+        .iter()
+        .map(|m| {
+            vec![
+                m.path[0].name.clone(),
+                m.path[m.path.len() - 1].name.clone(),
+                m.steps.len().to_string(),
+                describe_path(m),
+            ]
+        })
+        .collect();
+    table::render(&["FROM", "TO", "HOPS", "PATH"], &rows)
+}
+
+/// Matches as JSON, shaped like the table
+pub fn render_json(query: &Query, matches: &[Match]) -> Value {
+    let rows = matches.iter().map(|m| {
+        if !query.returns.is_empty() {
+            let bound = query
+                .returns
+                .iter()
+                .map(|var| (var.clone(), summary(m.path[m.bound[query.position(var)]])));
+            return Value::Object(bound.collect());
+        }
+        if query.hops.is_empty() {
+            return summary(m.path[0]);
+        }
+        let hops: Vec<Value> = m
+            .steps
+            .iter()
+            .zip(m.path.windows(2))
+            .map(|(step, pair)| {
+                let (from, to) = if step.reverse { (pair[1], pair[0]) } else { (pair[0], pair[1]) };
+                json!({ "from_id": from.id, "to_id": to.id, "labels": step.labels })
+            })
+            .collect();
+        json!({
+            "nodes": m.path.iter().copied().map(summary).collect::<Vec<_>>(),
+            "hops": hops,
+        })
+    });
+    Value::Array(rows.collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::{component, edge, output};
+    use crate::output::schema::ComponentKind;
+
+    fn named(id: &str, name: &str, kind: ComponentKind) -> DetectedComponent {
+        DetectedComponent {
+            name: name.to_string(),
+            ..component(id, kind, &format!("app/{id}.py"))
+        }
+    }
+
+    fn orders() -> SysVistaOutput {
+        output(
+            vec![
+                named("route", "POST /orders", ComponentKind::Transport),
+                named("create", "create_order", ComponentKind::Service),
+                named("save", "save_order", ComponentKind::Service),
+                named("email", "send_email", ComponentKind::Service),
+                named("summary", "summarize_order", ComponentKind::Prompt),
+                named("order", "Order", ComponentKind::Model),
+                named("audit", "AuditLog", ComponentKind::Model),
+            ],
+            vec![
+                edge("route", "create", "calls"),
+                edge("create", "save", "calls"),
+                edge("create", "email", "calls"),
+                edge("create", "summary", "invokes_prompt"),
+                edge("save", "order", "persists"),
+                edge("email", "order", "references"),
+            ],
+        )
+    }
+
+    fn names(output: &SysVistaOutput, query: &str) -> Vec<String> {
+        let query = parse(query).unwrap();
+        run(output, &query, 100).iter().map(describe_path).collect()
+    }
+
+    #[test]
+    fn matches_patterns_over_the_graph() {
+        let output = orders();
+        let query = parse("(t:transport)-[*]->()-[:persists]->(:model {name: Order}) return t").unwrap();
+        let matches = run(&output, &query, 100);
+        assert_eq!(render_table(&query, &matches), "t\nPOST /orders (app/route.py:1)\n");
+
+        assert_eq!(names(&output, "(x)-[:calls]->({name: send_email})"), vec!["create_order -[calls]-> send_email"]);
+        assert_eq!(
+            names(&output, "({name: \"POST /orders\"})-[*..6]->(:prompt)"),
+            vec!["POST /orders -[calls]-> create_order -[invokes_prompt]-> summarize_order"]
+        );
+        assert_eq!(names(&output, "(:model)<-[:persists]-(s {name: save_*})"), vec!["Order <-[persists]- save_order"]);
+        assert_eq!(names(&output, "(m:model {in: 0})"), vec!["AuditLog"]);
+        assert_eq!(names(&output, "(s:service {out: >=2})"), vec!["create_order"]);
+    }
+
+    #[test]
+    fn reports_query_errors() {
+        assert_eq!(parse("(a)-->(a)").unwrap_err(), "variable 'a' is bound twice");
+        assert_eq!(parse("(x {colour: red})").unwrap_err(), "unknown property 'colour'");
+        assert_eq!(parse("(x:model").unwrap_err(), "expected ')' at column 9");
+        assert_eq!(parse("(x)-[*3..1]->()").unwrap_err(), "path length range is empty at column 11");
+        assert_eq!(parse("(x) return y").unwrap_err(), "unknown variable 'y' in return");
+        assert_eq!(parse("(x {in: many})").unwrap_err(), "'in' takes a number, got 'many'");
+        assert_eq!(parse("(x {name: })").unwrap_err(), "expected a value at column 11");
+        assert_eq!(parse("(x)-[:]->()").unwrap_err(), "expected a label at column 7");
+        assert_eq!(parse("(x)->(y)").unwrap_err(), "expected '-' at column 5");
+        assert_eq!(parse("(x) (y)").unwrap_err(), "unexpected input at column 5");
+        assert_eq!(parse("(x) return").unwrap_err(), "expected a variable at column 11");
+    }
+
+    #[test]
+    fn bounds_hops_by_length_range() {
+        let output = orders();
+        let route = "({name: \"POST /orders\"})";
+        let ends = |hops: &str| -> Vec<String> {
+            let query = parse(&format!("{route}{hops}(x) return x")).unwrap();
+            run(&output, &query, 100).iter().map(|m| m.path[m.path.len() - 1].name.clone()).collect()
+        };
+        assert_eq!(ends("-[*..1]->"), vec!["create_order"]);
+        assert_eq!(ends("-[*2]->"), vec!["save_order", "send_email", "summarize_order"]);
+        assert_eq!(ends("-[*3..]->"), vec!["Order"]);
+        assert_eq!(ends("-[*]->").len(), 5);
+        assert_eq!(ends("-[:calls*]->"), vec!["create_order", "save_order", "send_email"]);
+        assert!(ends("-[*4..]->").is_empty());
+    }
+
+    #[test]
+    fn follows_reverse_hops_against_the_edge_direction() {
+        let output = orders();
+        assert_eq!(
+            names(&output, "(:model {name: Order})<-[*2]-(x)"),
+            vec!["Order <-[persists]- save_order <-[calls]- create_order"]
+        );
+        assert_eq!(
+            names(&output, "({name: send_email})<--(x)-->()-[:persists]->(m)"),
+            vec!["send_email <-[calls]- create_order -[calls]-> save_order -[persists]-> Order"]
+        );
+        assert!(names(&output, "(:model)-->()").is_empty());
+    }
+
+    #[test]
+    fn returns_each_combination_once() {
+        let output = orders();
+        let rows = |text: &str| run(&output, &parse(text).unwrap(), 100).len();
+        assert_eq!(rows("(t:transport)-[*]->(s:service)"), 3);
+        assert_eq!(rows("(t:transport)-[*]->(s:service) return t"), 1);
+        assert_eq!(rows("(t:transport)-[*]->(s:service) return t, s"), 3);
+        assert_eq!(rows("(s:service)-[*]->(m:model) return m"), 1);
+        assert_eq!(run(&output, &parse("(s:service)").unwrap(), 2).len(), 2);
+    }
+
+    #[test]
+    fn globs_match_whole_strings() {
+        assert!(glob("save_*", "save_order"));
+        assert!(glob("*order", "create_order"));
+        assert!(glob("a*b*c", "a-b-b-c"));
+        assert!(glob("*", ""));
+        assert!(glob("Order", "Order"));
+        assert!(!glob("Order", "Orders"));
+        assert!(!glob("save_*", "resave_order"));
+        assert!(!glob("ab*ba", "aba"));
+        assert!(!glob("a*b*c", "a-c-b"));
+
+        let output = orders();
+        assert_eq!(names(&output, "({name: *_order, kind: service})"), vec!["create_order", "save_order"]);
+        assert_eq!(names(&output, "({file: app/s*.py})"), vec!["save_order", "summarize_order"]);
+    }
+}
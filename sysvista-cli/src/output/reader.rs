@@ -0,0 +1,10 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::schema::SysVistaOutput;
+
+pub fn read_json(path: &Path) -> io::Result<SysVistaOutput> {
+    let json = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+}
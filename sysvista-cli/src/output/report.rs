@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+use super::query::location;
+use super::schema::{DetectedComponent, Grouping, SysVistaOutput};
+use super::table;
+
+/// Table of `analysis.cycles`, largest first. Component members are shown by
+/// name, files and packages by path.
+pub fn render_cycles(output: &SysVistaOutput) -> String {
+    let names: HashMap<&str, &str> = output
+        .components
+        .iter()
+        .map(|c| (c.id.as_str(), c.name.as_str()))
+        .collect();
+    let rows: Vec<Vec<String>> = output
+        .analysis
+        .cycles
+        .iter()
+        .map(|cycle| {
+            let members: Vec<&str> = cycle
+                .members
+                .iter()
+                .map(|m| match cycle.level {
+                    Grouping::Component => names.get(m.as_str()).copied().unwrap_or(m),
+                    _ => m,
+                })
+                .collect();
+            vec![
+                cycle.level.as_str().to_string(),
+                cycle.members.len().to_string(),
+                cycle.edges.len().to_string(),
+                members.join(", "),
+            ]
+        })
+        .collect();
+    table::render(&["LEVEL", "SIZE", "EDGES", "MEMBERS"], &rows)
+}
+
+/// Components with metrics, highest hub score first.
+fn by_hub_score(output: &SysVistaOutput) -> Vec<&DetectedComponent> {
+    let mut components: Vec<&DetectedComponent> = output.components.iter().filter(|c| c.metrics.is_some()).collect();
+    components.sort_by(|a, b| {
+        let score = |c: &DetectedComponent| c.metrics.as_ref().map_or(0.0, |m| m.hub_score);
+        score(b).total_cmp(&score(a)).then_with(|| a.name.cmp(&b.name))
+    });
+    components
+}
+
+/// Table of component metrics, highest hub score first.
+pub fn render_metrics(output: &SysVistaOutput) -> String {
+    let rows: Vec<Vec<String>> = by_hub_score(output)
+        .into_iter()
+        .filter_map(|c| {
+            let m = c.metrics.as_ref()?;
+            Some(vec![
+                c.name.clone(),
+                c.kind.as_str().to_string(),
+                m.fan_in.to_string(),
+                m.fan_out.to_string(),
+                format!("{:.3}", m.betweenness),
+                format!("{:.2}", m.hub_score),
+                c.source.file.clone(),
+            ])
+        })
+        .collect();
+    table::render(&["NAME", "KIND", "FAN-IN", "FAN-OUT", "BETWEENNESS", "HUB", "FILE"], &rows)
+}
+
+/// Component metrics as JSON, highest hub score first.
+pub fn metrics_json(output: &SysVistaOutput) -> Value {
+    let rows = by_hub_score(output).into_iter().map(|c| {
+        json!({
+            "id": c.id,
+            "name": c.name,
+            "kind": c.kind.as_str(),
+            "file": c.source.file,
+            "metrics": c.metrics,
+        })
+    });
+    Value::Array(rows.collect())
+}
+
+/// `analysis.dead` with the name, kind and location of each component.
+fn dead(output: &SysVistaOutput) -> Vec<(&DetectedComponent, &str)> {
+    let by_id: HashMap<&str, &DetectedComponent> = output.components.iter().map(|c| (c.id.as_str(), c)).collect();
+    output
+        .analysis
+        .dead
+        .iter()
+        .filter_map(|d| Some((*by_id.get(d.id.as_str())?, d.reason.as_str())))
+        .collect()
+}
+
+/// Table of `analysis.dead`, in scan order.
+pub fn render_dead(output: &SysVistaOutput) -> String {
+    let rows: Vec<Vec<String>> = dead(output)
+        .into_iter()
+        .map(|(c, reason)| {
+            vec![
+                c.name.clone(),
+                c.kind.as_str().to_string(),
+                location(c),
+                reason.to_string(),
+            ]
+        })
+        .collect();
+    table::render(&["NAME", "KIND", "SOURCE", "REASON"], &rows)
+}
+
+/// `analysis.dead` as JSON, with each component's name, kind and file.
+pub fn dead_json(output: &SysVistaOutput) -> Value {
+    let rows = dead(output).into_iter().map(|(c, reason)| {
+        json!({
+            "id": c.id,
+            "name": c.name,
+            "kind": c.kind.as_str(),
+            "file": c.source.file,
+            "line": c.source.line_start,
+            "reason": reason,
+        })
+    });
+    Value::Array(rows.collect())
+}
+
+/// `analysis.hotspots` with each component's fan-in and history.
+fn hotspots(output: &SysVistaOutput) -> Vec<(&DetectedComponent, f64)> {
+    let by_id: HashMap<&str, &DetectedComponent> = output.components.iter().map(|c| (c.id.as_str(), c)).collect();
+    output
+        .analysis
+        .hotspots
+        .iter()
+        .filter_map(|h| Some((*by_id.get(h.id.as_str())?, h.score)))
+        .collect()
+}
+
+/// Table of `analysis.hotspots`, highest score first.
+pub fn render_hotspots(output: &SysVistaOutput) -> String {
+    let rows: Vec<Vec<String>> = hotspots(output)
+        .into_iter()
+        .filter_map(|(c, score)| {
+            let (metrics, history) = (c.metrics.as_ref()?, c.history.as_ref()?);
+            Some(vec![
+                c.name.clone(),
+                c.kind.as_str().to_string(),
+                format!("{score:.3}"),
+                metrics.fan_in.to_string(),
+                history.commits.to_string(),
+                history.authors.to_string(),
+                history.lines_changed.to_string(),
+                history.last_modified.as_deref().map_or("", |d| d.get(..10).unwrap_or(d)).to_string(),
+                location(c),
+            ])
+        })
+        .collect();
+    table::render(
+        &["NAME", "KIND", "SCORE", "FAN-IN", "COMMITS", "AUTHORS", "LINES", "LAST-MODIFIED", "SOURCE"],
+        &rows,
+    )
+}
+
+/// `analysis.hotspots` as JSON, with each component's fan-in and history.
+pub fn hotspots_json(output: &SysVistaOutput) -> Value {
+    let rows = hotspots(output).into_iter().map(|(c, score)| {
+        json!({
+            "id": c.id,
+            "name": c.name,
+            "kind": c.kind.as_str(),
+            "file": c.source.file,
+            "score": score,
+            "fan_in": c.metrics.as_ref().map(|m| m.fan_in),
+            "history": c.history,
+        })
+    });
+    Value::Array(rows.collect())
+}
+
+/// Table of `analysis.modules`, most unstable first.
+pub fn render_modules(output: &SysVistaOutput) -> String {
+    let mut modules: Vec<_> = output.analysis.modules.iter().collect();
+    modules.sort_by(|a, b| b.instability.total_cmp(&a.instability).then_with(|| a.name.cmp(&b.name)));
+    let rows: Vec<Vec<String>> = modules
+        .into_iter()
+        .map(|m| {
+            vec![
+                m.name.clone(),
+                m.components.to_string(),
+                m.afferent.to_string(),
+                m.efferent.to_string(),
+                format!("{:.2}", m.instability),
+            ]
+        })
+        .collect();
+    table::render(&["MODULE", "COMPONENTS", "CA", "CE", "INSTABILITY"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis;
+    use crate::output::fixtures::{component, edge, output};
+    use crate::output::schema::ComponentKind;
+
+    #[test]
+    fn reports_cycles_metrics_and_modules() {
+        let service = |id: &str, file: &str| component(id, ComponentKind::Service, file);
+        let mut output = output(
+            vec![
+                service("place_order", "orders/api.py"),
+                service("charge", "billing/charge.py"),
+                service("refund", "billing/refund.py"),
+                service("notify", "orders/notify.py"),
+            ],
+            vec![
+                edge("place_order", "charge", "calls"),
+                edge("charge", "refund", "calls"),
+                edge("refund", "place_order", "calls"),
+                edge("refund", "notify", "imports"),
+                // Only imports and calls close a cycle
+                edge("notify", "refund", "references"),
+            ],
+        );
+        analysis::analyze(&mut output);
+
+        assert_eq!(
+            render_cycles(&output),
+            "LEVEL      SIZE  EDGES  MEMBERS\n\
+             component  3     3      charge, place_order, refund\n\
+             file       3     3      billing/charge.py, billing/refund.py, orders/api.py\n\
+             package    2     3      billing, orders\n"
+        );
+        assert_eq!(output.analysis.cycles[0].edges[0].labels, vec!["calls"]);
+
+        assert_eq!(
+            render_modules(&output),
+            "MODULE   COMPONENTS  CA  CE  INSTABILITY\n\
+             orders   2           1   2   0.67\n\
+             billing  2           2   1   0.33\n"
+        );
+        let refund = output.components.iter().find(|c| c.id == "refund").unwrap();
+        let metrics = refund.metrics.as_ref().unwrap();
+        assert_eq!((metrics.fan_in, metrics.fan_out), (2, 2));
+        assert!(render_metrics(&output).lines().nth(1).unwrap().starts_with("refund"));
+    }
+}
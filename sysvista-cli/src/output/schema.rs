@@ -1,25 +1,52 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ComponentKind {
+    #[default]
     Model,
     Service,
     Transport,
     Transform,
     Prompt,
+    Datastore,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+impl ComponentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComponentKind::Model => "model",
+            ComponentKind::Service => "service",
+            ComponentKind::Transport => "transport",
+            ComponentKind::Transform => "transform",
+            ComponentKind::Prompt => "prompt",
+            ComponentKind::Datastore => "datastore",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum TransportProtocol {
     Http,
     Grpc,
     Websocket,
+    Mq,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl TransportProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransportProtocol::Http => "http",
+            TransportProtocol::Grpc => "grpc",
+            TransportProtocol::Websocket => "websocket",
+            TransportProtocol::Mq => "mq",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SourceLocation {
     pub file: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -28,13 +55,71 @@ pub struct SourceLocation {
     pub line_end: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// A model field (or enum variant) with its declared type, optionality and default.
+/// Outputs written before fields carried types list bare names; those still load.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "ModelFieldRepr")]
+pub struct ModelField {
+    pub name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    pub optional: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Struct tags, annotations and attributes (`json` → `name,omitempty`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, String>,
+}
+
+/// Either form of a model field in an output file: a bare name or the full object.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ModelFieldRepr {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(rename = "type", default)]
+        type_name: Option<String>,
+        #[serde(default)]
+        optional: bool,
+        #[serde(default)]
+        default: Option<String>,
+        #[serde(default)]
+        tags: HashMap<String, String>,
+    },
+}
+
+impl From<ModelFieldRepr> for ModelField {
+    fn from(repr: ModelFieldRepr) -> Self {
+        match repr {
+            ModelFieldRepr::Name(name) => ModelField {
+                name,
+                type_name: None,
+                optional: false,
+                default: None,
+                tags: HashMap::new(),
+            },
+            ModelFieldRepr::Full { name, type_name, optional, default, tags } => ModelField {
+                name,
+                type_name,
+                optional,
+                default,
+                tags,
+            },
+        }
+    }
+}
+
+/// A detected component. Only the identity and location are always set; build
+/// one with `..Default::default()` for the rest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DetectedComponent {
     pub id: String,
     pub name: String,
     pub kind: ComponentKind,
     pub language: String,
     pub source: SourceLocation,
+    #[serde(default)]
     pub metadata: HashMap<String, String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transport_protocol: Option<TransportProtocol>,
@@ -43,16 +128,68 @@ pub struct DetectedComponent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub http_path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub model_fields: Option<Vec<String>>,
+    pub model_fields: Option<Vec<ModelField>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt_subtype: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub consumes: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub produces: Option<Vec<String>>,
+    /// Repository the component was found in, set by workspace scans
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Output file the component came from, set by `merge`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// Workspace package (npm, Cargo, Go or Python) containing the source file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// How reliable the detection path is, from 0 to 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<ComponentMetrics>,
+    /// Changes to the component's lines in the `--git` window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<ComponentHistory>,
+}
+
+/// Commits touching a component's line span, from `git log`. Hunks are
+/// matched against the current span, so old commits to a moved definition
+/// are approximate.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComponentHistory {
+    pub commits: usize,
+    /// Distinct author names
+    pub authors: usize,
+    /// Lines added plus lines removed
+    pub lines_changed: usize,
+    /// Author date of the latest commit (RFC 3339, UTC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Coupling of a component in the directed graph of the output's `edges`, as
+/// written after `--min-confidence` filtering. Each ordered pair of components
+/// counts once whatever its relations; self-edges and edges to components not
+/// in the output are ignored. Scores are rounded to three decimals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentMetrics {
+    /// Number of distinct components with an edge into this one
+    pub fan_in: usize,
+    /// Number of distinct components this one has an edge to
+    pub fan_out: usize,
+    /// Normalised betweenness centrality: the share of shortest directed paths
+    /// between every ordered pair of other components that pass through this
+    /// one, from 0 to 1. Paths are counted in edges, unweighted.
+    pub betweenness: f64,
+    /// Standard deviations of this component's degree (`fan_in + fan_out`)
+    /// above the mean degree of all components; negative below it, 0 when
+    /// every degree is equal
+    pub hub_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectedEdge {
     pub from_id: String,
     pub to_id: String,
@@ -60,9 +197,69 @@ pub struct DetectedEdge {
     pub label: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<EdgeEvidence>,
+    /// How reliable the inferring heuristic is, from 0 to 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Every relationship between the pair, primary first, when there is more
+    /// than one. `label` then names the primary relation, and `payload_type`,
+    /// `evidence` and `confidence` are only set on the relations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relations: Vec<EdgeRelation>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+impl DetectedEdge {
+    /// The relations of the edge; an edge that was never merged is its own
+    /// single relation.
+    pub fn relations(&self) -> Vec<EdgeRelation> {
+        if !self.relations.is_empty() {
+            return self.relations.clone();
+        }
+        vec![EdgeRelation {
+            label: self.label.clone().unwrap_or_else(|| "related".to_string()),
+            payload_type: self.payload_type.clone(),
+            evidence: self.evidence.clone(),
+            confidence: self.confidence,
+        }]
+    }
+
+    pub fn labels(&self) -> Vec<&str> {
+        if self.relations.is_empty() {
+            return self.label.as_deref().into_iter().collect();
+        }
+        self.relations.iter().map(|r| r.label.as_str()).collect()
+    }
+
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels().contains(&label)
+    }
+}
+
+/// One relationship of an edge (`imports`, `calls`, ...) with its own payload
+/// type, evidence and confidence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeRelation {
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<EdgeEvidence>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+}
+
+/// Why an edge was inferred: the heuristic that produced it, where it matched
+/// and the matched text (`crud.create_message(`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeEvidence {
+    pub heuristic: String,
+    pub source: SourceLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum StepType {
     Entry,
@@ -73,14 +270,14 @@ pub enum StepType {
     Prompt,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowStep {
     pub component_id: String,
     pub step_type: StepType,
     pub order: u32,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Workflow {
     pub id: String,
     pub name: String,
@@ -88,14 +285,34 @@ pub struct Workflow {
     pub steps: Vec<WorkflowStep>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanStats {
     pub files_scanned: u64,
     pub files_skipped: u64,
     pub scan_duration_ms: u64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// A package of a monorepo workspace; `path` is its directory relative to the
+/// scan root (`""` for the root package). Workspace scans prefix both with the
+/// repository name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Package {
+    pub name: String,
+    pub path: String,
+    pub manager: String,
+}
+
+/// One repository of a workspace scan. Component IDs and `repo` fields are
+/// prefixed with `name`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Repository {
+    pub name: String,
+    pub root_dir: String,
+    pub detected_languages: Vec<String>,
+    pub scan_stats: ScanStats,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SysVistaOutput {
     pub version: String,
     pub scanned_at: String,
@@ -104,6 +321,119 @@ pub struct SysVistaOutput {
     pub detected_languages: Vec<String>,
     pub components: Vec<DetectedComponent>,
     pub edges: Vec<DetectedEdge>,
+    #[serde(default)]
     pub workflows: Vec<Workflow>,
     pub scan_stats: ScanStats,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<Package>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repositories: Vec<Repository>,
+    #[serde(default, skip_serializing_if = "Analysis::is_empty")]
+    pub analysis: Analysis,
+}
+
+/// Analyses of the edge graph, recomputed whenever the graph changes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Analysis {
+    /// Dependency cycles over `imports` and `calls`, largest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cycles: Vec<DependencyCycle>,
+    /// Coupling of each package (or directory outside packages), by name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<ModuleMetrics>,
+    /// Models, services, transforms and prompts nothing uses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dead: Vec<DeadComponent>,
+    /// Components both depended on and changing often, with `--git`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hotspots: Vec<Hotspot>,
+}
+
+impl Analysis {
+    pub fn is_empty(&self) -> bool {
+        self.cycles.is_empty() && self.modules.is_empty() && self.dead.is_empty() && self.hotspots.is_empty()
+    }
+}
+
+/// A component ranked by fan-in times commit count, each relative to the
+/// highest in the scan, from 0 to 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hotspot {
+    pub id: String,
+    pub score: f64,
+}
+
+/// A component no edge reaches the way its kind is used, and why.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadComponent {
+    pub id: String,
+    pub reason: String,
+}
+
+/// Robert Martin's package coupling metrics for one module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleMetrics {
+    pub name: String,
+    pub components: usize,
+    /// Components outside the module with an edge into it (Ca)
+    pub afferent: usize,
+    /// Components inside the module with an edge out of it (Ce)
+    pub efferent: usize,
+    /// `efferent / (afferent + efferent)`, 0 for an isolated module
+    pub instability: f64,
+}
+
+/// The level at which dependencies are compared: components themselves, or
+/// the file, directory or package that holds them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Grouping {
+    #[default]
+    Component,
+    File,
+    Directory,
+    Package,
+}
+
+impl Grouping {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Grouping::Component => "component",
+            Grouping::File => "file",
+            Grouping::Directory => "directory",
+            Grouping::Package => "package",
+        }
+    }
+}
+
+/// Components, files or packages that all depend on each other. `members` are
+/// component IDs at the component level; `edges` are the component edges
+/// that close the cycle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyCycle {
+    pub level: Grouping,
+    pub members: Vec<String>,
+    pub edges: Vec<CycleEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleEdge {
+    pub from_id: String,
+    pub to_id: String,
+    pub labels: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_model_fields_as_names_or_objects() {
+        let fields: Vec<ModelField> =
+            serde_json::from_str(r#"["id", {"name": "email", "type": "str", "optional": true}]"#).unwrap();
+        assert_eq!(fields[0].name, "id");
+        assert_eq!((fields[0].type_name.as_deref(), fields[0].optional), (None, false));
+        assert_eq!(fields[1].type_name.as_deref(), Some("str"));
+        assert!(fields[1].optional);
+    }
 }
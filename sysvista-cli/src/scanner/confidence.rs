@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+
+use crate::analysis;
+use crate::output::schema::{ComponentKind, DetectedComponent, DetectedEdge, SysVistaOutput, TransportProtocol};
+
+use super::relationships::{is_outbound, merge_relations};
+use super::workflows;
+
+// ---------------------------------------------------------------------------
+// Confidence — how far to trust each detection path
+// ---------------------------------------------------------------------------
+
+/// Edge heuristics (`EdgeEvidence::heuristic`) and their confidence.
+const EDGE_HEURISTICS: &[(&str, f64)] = &[
+    // Imports resolved to a file, or an item in it
+    ("named_import", 0.95),
+    ("module_import", 0.9),
+    ("package_import", 0.85),
+    ("import_stem", 0.5),
+    ("name_reference", 0.5),
+    // Model structure read from declarations
+    ("field_type", 0.9),
+    ("base_type", 0.9),
+    ("foreign_key", 0.95),
+    ("table_name", 0.7),
+    ("signature_type", 0.9),
+    // Flow inferred from handler and service bodies
+    ("same_file_route", 0.7),
+    ("prompt_in_body", 0.7),
+    ("model_in_body", 0.45),
+    ("datastore_client", 0.8),
+    // Calls
+    ("package_call", 0.9),
+    ("module_call", 0.8),
+    ("name_only_call", 0.35),
+    ("background_dispatch", 0.75),
+    ("awaited_call", 0.5),
+    // Outbound calls
+    ("http_client", 0.75),
+    ("queue_publish", 0.85),
+    ("grpc_stub", 0.85),
+];
+
+/// Confidence of an edge whose heuristic is unknown or unrecorded
+const UNKNOWN_EDGE: f64 = 0.5;
+
+/// Set `confidence` on every component and edge from the path that detected it.
+pub fn score(components: &mut [DetectedComponent], edges: &mut [DetectedEdge]) {
+    for comp in components.iter_mut() {
+        comp.confidence = Some(component_confidence(comp));
+    }
+    for edge in edges.iter_mut() {
+        edge.confidence = Some(edge_confidence(edge));
+    }
+}
+
+/// A decorator or framework registration is near-certain; a naming or
+/// directory convention is a guess.
+pub fn component_confidence(comp: &DetectedComponent) -> f64 {
+    let detection = comp.metadata.get("detection").map(String::as_str);
+    match comp.kind {
+        ComponentKind::Service => match detection {
+            Some("decorator") => 0.95,
+            Some("class_heuristic") => 0.35,
+            _ => 0.6,
+        },
+        ComponentKind::Prompt => match detection {
+            Some("sdk_structural") => 0.9,
+            Some("framework") => 0.85,
+            _ => 0.6,
+        },
+        ComponentKind::Transport if is_outbound(comp) => 0.7,
+        ComponentKind::Transport => match comp.transport_protocol {
+            Some(TransportProtocol::Grpc) => 0.95,
+            Some(TransportProtocol::Http) if comp.http_path.is_some() => 0.9,
+            Some(TransportProtocol::Mq) => 0.85,
+            _ => 0.75,
+        },
+        ComponentKind::Model => {
+            let schema = ["table", "orm", "migrations"].iter().any(|k| comp.metadata.contains_key(*k));
+            if schema || comp.language == "protobuf" {
+                0.95
+            } else if comp.language == "python" {
+                // dataclasses and BaseModel/TypedDict subclasses
+                0.9
+            } else {
+                // every struct, interface or type alias
+                0.7
+            }
+        }
+        ComponentKind::Datastore => 0.85,
+        ComponentKind::Transform => match detection {
+            Some("trait_impl") => 0.95,
+            Some("typed_signature") => 0.7,
+            _ => 0.45,
+        },
+    }
+}
+
+pub fn edge_confidence(edge: &DetectedEdge) -> f64 {
+    edge.evidence
+        .as_ref()
+        .and_then(|e| EDGE_HEURISTICS.iter().find(|(h, _)| *h == e.heuristic))
+        .map_or(UNKNOWN_EDGE, |&(_, c)| c)
+}
+
+/// Drop components and edge relations scored below `min`, edges left without
+/// relations and the edges of dropped components, and re-infer workflows and
+/// the analysis from what remains.
+pub fn filter_by_confidence(output: &mut SysVistaOutput, min: f64) {
+    let passes = |c: Option<f64>| c.is_none_or(|c| c >= min);
+    output.components.retain(|c| passes(c.confidence));
+    let kept: HashSet<&str> = output.components.iter().map(|c| c.id.as_str()).collect();
+    let edges = std::mem::take(&mut output.edges)
+        .into_iter()
+        .filter(|e| kept.contains(e.from_id.as_str()) && kept.contains(e.to_id.as_str()))
+        .filter_map(|mut e| {
+            e.relations = e.relations().into_iter().filter(|r| passes(r.confidence)).collect();
+            (!e.relations.is_empty()).then_some(e)
+        })
+        .collect();
+    output.edges = merge_relations(edges);
+    output.workflows = workflows::infer_workflows(&output.components, &output.edges);
+    analysis::analyze(output);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::{component, edge, output};
+    use crate::output::schema::{EdgeEvidence, SourceLocation};
+
+    fn service(id: &str, detection: &str) -> DetectedComponent {
+        let mut service = component(id, ComponentKind::Service, &format!("{id}.py"));
+        service.metadata.insert("detection".to_string(), detection.to_string());
+        service
+    }
+
+    fn call(from: &str, to: &str, heuristic: &str) -> DetectedEdge {
+        DetectedEdge {
+            evidence: Some(EdgeEvidence {
+                heuristic: heuristic.to_string(),
+                source: SourceLocation {
+                    file: format!("{from}.py"),
+                    line_start: Some(2),
+                    line_end: None,
+                },
+                snippet: None,
+            }),
+            ..edge(from, to, "calls")
+        }
+    }
+
+    #[test]
+    fn scores_by_detection_path_and_filters() {
+        let mut output = output(
+            vec![
+                service("controller", "decorator"),
+                service("helper", "class_heuristic"),
+                service("crud", "directory_convention"),
+            ],
+            vec![
+                call("controller", "crud", "module_call"),
+                call("controller", "helper", "module_call"),
+                call("crud", "controller", "name_only_call"),
+            ],
+        );
+        score(&mut output.components, &mut output.edges);
+        let scores: Vec<f64> = output.components.iter().filter_map(|c| c.confidence).collect();
+        assert_eq!(scores, vec![0.95, 0.35, 0.6]);
+        assert_eq!(output.edges[2].confidence, Some(0.35));
+
+        filter_by_confidence(&mut output, 0.5);
+        let ids: Vec<&str> = output.components.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["controller", "crud"]);
+        let edges: Vec<(&str, &str)> = output.edges.iter().map(|e| (e.from_id.as_str(), e.to_id.as_str())).collect();
+        assert_eq!(edges, vec![("controller", "crud")]);
+    }
+
+    #[test]
+    fn transforms_score_by_signature() {
+        let transform = |detection: &str| {
+            let mut comp = component("to_dto", ComponentKind::Transform, "convert.py");
+            comp.metadata.insert("detection".to_string(), detection.to_string());
+            component_confidence(&comp)
+        };
+        assert_eq!(transform("trait_impl"), 0.95);
+        assert_eq!(transform("typed_signature"), 0.7);
+        assert_eq!(transform("name_only"), 0.45);
+    }
+}
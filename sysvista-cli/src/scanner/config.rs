@@ -0,0 +1,102 @@
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::output::query::glob;
+use crate::output::schema::DetectedComponent;
+
+// ---------------------------------------------------------------------------
+// Project config — `sysvista.toml` at the scan root
+// ---------------------------------------------------------------------------
+
+pub const FILE_NAME: &str = "sysvista.toml";
+
+/// Project settings:
+///
+/// ```toml
+/// # Components used from outside the scanned code (CLI commands, cron jobs,
+/// # framework hooks), matched by name or file as `*` globs
+/// entry_points = ["main", "*_job", "scripts/*"]
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub entry_points: Vec<String>,
+}
+
+/// The config of a project root; defaults when the root has none.
+pub fn load(root: &Path) -> Result<Config, String> {
+    let path = root.join(FILE_NAME);
+    if !path.is_file() {
+        return Ok(Config::default());
+    }
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("cannot read '{}': {e}", path.display()))?;
+    toml::from_str(&text).map_err(|e| format!("invalid config '{}': {e}", path.display()))
+}
+
+/// Tag components the config names as entry points with
+/// `metadata.entry_point`, so later analyses never report them as dead.
+pub fn mark_entry_points(components: &mut [DetectedComponent], config: &Config) {
+    for comp in components {
+        let pattern = config
+            .entry_points
+            .iter()
+            .find(|p| glob(p, &comp.name) || glob(p, &comp.source.file));
+        if let Some(pattern) = pattern {
+            comp.metadata.insert("entry_point".to_string(), pattern.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::component;
+    use crate::output::schema::ComponentKind;
+
+    #[test]
+    fn marks_entry_points_by_name_or_file() {
+        let dir = std::env::temp_dir().join(format!("sysvista-config-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(load(&dir).unwrap().entry_points.is_empty());
+
+        std::fs::write(dir.join(FILE_NAME), "entry_points = [\"*_job\", \"scripts/*\"]\n").unwrap();
+        let config = load(&dir).unwrap();
+        std::fs::write(dir.join(FILE_NAME), "entrypoints = []\n").unwrap();
+        let invalid = load(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(invalid.unwrap_err().starts_with("invalid config"));
+
+        let mut components = vec![
+            component("cleanup_job", ComponentKind::Service, "app/jobs.py"),
+            component("seed", ComponentKind::Service, "scripts/seed.py"),
+            component("create_user", ComponentKind::Service, "app/users.py"),
+        ];
+        mark_entry_points(&mut components, &config);
+        let marked: Vec<Option<&str>> = components.iter().map(|c| c.metadata.get("entry_point").map(String::as_str)).collect();
+        assert_eq!(marked, vec![Some("*_job"), Some("scripts/*"), None]);
+    }
+
+    #[test]
+    fn allowlisted_entry_points_are_never_dead() {
+        let dir = std::env::temp_dir().join(format!("sysvista-entry-points-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("app")).unwrap();
+        std::fs::write(
+            dir.join("app/tasks.py"),
+            "class NightlyExport:\n    def run(self):\n        pass\n\n\nclass LegacySync:\n    def run(self):\n        pass\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join(FILE_NAME), "entry_points = [\"Nightly*\"]\n").unwrap();
+
+        let output = crate::scanner::scan(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let name = |id: &str| output.components.iter().find(|c| c.id == id).map(|c| c.name.as_str());
+        let dead: Vec<Option<&str>> = output.analysis.dead.iter().map(|d| name(&d.id)).collect();
+        assert_eq!(dead, vec![Some("LegacySync")]);
+        let nightly = output.components.iter().find(|c| c.name == "NightlyExport").unwrap();
+        assert_eq!(nightly.metadata.get("entry_point").map(String::as_str), Some("Nightly*"));
+    }
+}
@@ -0,0 +1,900 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::output::schema::ModelField;
+
+// ---------------------------------------------------------------------------
+// Field extraction — structured fields from a model's declaration text
+// ---------------------------------------------------------------------------
+
+static IDENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[A-Za-z_$][\w$]*$").unwrap());
+
+/// Trailing identifier of a `Type name` declaration
+static TRAILING_IDENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([A-Za-z_]\w*)\s*$").unwrap());
+
+static RUST_DECL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(struct|enum)\s+\w+").unwrap());
+
+static GO_STRUCT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bstruct\s*\{").unwrap());
+
+/// `Name, Other Type` — Go field names followed by their type
+static GO_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z_]\w*(?:\s*,\s*[A-Za-z_]\w*)*)\s+(\S.*)$").unwrap()
+});
+
+/// `key:"value"` pairs in a Go struct tag
+static GO_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(\w+):"([^"]*)""#).unwrap());
+
+static PYTHON_CLASS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\s*class\s+\w+").unwrap());
+
+static PROTO_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(optional|repeated|required)\s+)?(map\s*<[^>]+>|[\w.]+)\s+(\w+)\s*=\s*(\d+)").unwrap()
+});
+
+static PROTO_MESSAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bmessage\s+\w+").unwrap());
+
+/// Java/C# `record Name(`, Kotlin `class Name(` (primary constructor)
+static RECORD_HEADER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:record|class)\s+(?:class\s+|struct\s+)?\w+(?:<[^>{(]*>)?\s*\(").unwrap());
+
+static CLASS_HEADER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:class|record|struct|interface)\s+\w+").unwrap());
+
+static PLAIN_CLASS_HEADER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bclass\s+(\w+)").unwrap());
+
+/// Methods a plain data class may declare besides accessors and constructors
+const OBJECT_METHODS: &[&str] = &["equals", "hashCode", "toString", "Equals", "GetHashCode", "ToString"];
+
+/// Declaration modifiers stripped before reading `Type name` / `name: Type`
+const MODIFIERS: &[&str] = &[
+    "public", "private", "protected", "internal", "final", "transient", "volatile", "readonly",
+    "required", "override", "lateinit", "open", "val", "var", "virtual", "new", "const", "pub",
+    "pub(crate)", "pub(super)",
+];
+
+/// Generic collection wrappers: a field typed with one of these is optional
+const OPTIONAL_WRAPPERS: &[&str] = &["Optional[", "Option<", "Optional<", "NotRequired[", "Maybe<"];
+
+/// Extract fields from the full declaration text of a model. `decl` spans the
+/// definition from its first line (decorators included) to its closing line.
+pub fn extract_fields(decl: &str, language: &str) -> Vec<ModelField> {
+    match language {
+        "typescript" | "javascript" => typescript_fields(decl),
+        "rust" => rust_fields(decl),
+        "go" => go_fields(decl),
+        "python" => python_fields(decl),
+        "protobuf" => proto_fields(decl),
+        "java" | "kotlin" | "csharp" => class_fields(decl, language),
+        _ => Vec::new(),
+    }
+}
+
+/// Base classes and implemented interfaces named in a model's declaration
+/// header, without generic arguments (`class Admin(User)` → `User`).
+pub fn extract_bases(decl: &str, language: &str) -> Vec<String> {
+    let list = match language {
+        "python" => PYTHON_CLASS
+            .find(decl)
+            .filter(|m| decl[m.end()..].trim_start().starts_with('('))
+            .and_then(|m| delimited(decl, m.end(), b'(', b')'))
+            .unwrap_or(""),
+        "typescript" | "javascript" | "java" => {
+            let header = &decl[..decl.find('{').unwrap_or(decl.len())];
+            let header = squash(header).replace(" implements ", " extends ");
+            let lists: Vec<&str> = header.split(" extends ").skip(1).collect();
+            return base_names(&lists.join(","), true);
+        }
+        "kotlin" | "csharp" => {
+            let Some(m) = CLASS_HEADER.find(decl) else {
+                return Vec::new();
+            };
+            let mut after = m.end();
+            if decl[after..].starts_with('<') {
+                after += delimited(decl, after, b'<', b'>').map_or(0, |g| g.len() + 2);
+            }
+            if decl[after..].trim_start().starts_with('(') {
+                let open = after + decl[after..].find('(').unwrap_or(0);
+                after = open + delimited(decl, open, b'(', b')').map_or(0, |p| p.len() + 2);
+            }
+            let rest = &decl[after..];
+            let rest = &rest[..rest.find(['{', ';']).unwrap_or(rest.len())];
+            let rest = rest.split(" where ").next().unwrap_or(rest);
+            rest.trim_start().strip_prefix(':').unwrap_or("")
+        }
+        _ => "",
+    };
+    base_names(list, language != "python")
+}
+
+/// Names from a comma-separated base list, skipping keyword arguments and
+/// stripping generic arguments and constructor calls.
+fn base_names(list: &str, angle: bool) -> Vec<String> {
+    split_top_level(list, b",", angle, false)
+        .into_iter()
+        .map(str::trim)
+        .filter(|base| find_top_level(base, b'=', angle).is_none())
+        .map(|base| base[..base.find(['<', '[', '(']).unwrap_or(base.len())].trim().to_string())
+        .filter(|base| !base.is_empty())
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Shared helpers
+// ---------------------------------------------------------------------------
+
+fn squash(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn new_field(name: &str, type_name: Option<&str>, default: Option<&str>) -> ModelField {
+    let type_name = type_name.map(squash).filter(|t| !t.is_empty());
+    let optional = type_name.as_deref().is_some_and(is_optional_type);
+    ModelField {
+        name: name.trim_matches(|c| c == '"' || c == '\'').to_string(),
+        type_name,
+        optional,
+        default: default.map(squash).filter(|d| !d.is_empty()),
+        tags: HashMap::new(),
+    }
+}
+
+fn is_optional_type(ty: &str) -> bool {
+    OPTIONAL_WRAPPERS.iter().any(|w| ty.starts_with(w))
+        || ty.ends_with('?')
+        || ty.split('|').any(|part| matches!(part.trim(), "None" | "null" | "undefined"))
+}
+
+fn is_open(b: u8, angle: bool) -> bool {
+    matches!(b, b'(' | b'[' | b'{') || (angle && b == b'<')
+}
+
+fn is_close(bytes: &[u8], i: usize, angle: bool) -> bool {
+    match bytes[i] {
+        b')' | b']' | b'}' => true,
+        // `=>` and `->` are arrows, not closing generics
+        b'>' => angle && !(i > 0 && matches!(bytes[i - 1], b'=' | b'-')),
+        _ => false,
+    }
+}
+
+/// Split `body` on `seps` at bracket depth 0, skipping quoted strings. With
+/// `split_blocks`, a `{ … }` block closing at depth 0 also ends a member
+/// (method bodies, nested messages) unless an initializer follows it.
+pub(crate) fn split_top_level<'a>(body: &'a str, seps: &[u8], angle: bool, split_blocks: bool) -> Vec<&'a str> {
+    let bytes = body.as_bytes();
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<u8> = None;
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(q) = quote {
+            if b == b'\\' {
+                i += 1;
+            } else if b == q {
+                quote = None;
+            }
+        } else if b == b'"' || b == b'`' {
+            quote = Some(b);
+        } else if is_open(b, angle) {
+            depth += 1;
+        } else if is_close(bytes, i, angle) {
+            depth = (depth - 1).max(0);
+            if depth == 0 && b == b'}' && split_blocks {
+                let next = body[i + 1..].trim_start().bytes().next();
+                if !matches!(next, Some(b'=') | Some(b';') | Some(b',')) {
+                    parts.push(&body[start..=i]);
+                    start = i + 1;
+                }
+            }
+        } else if depth == 0 && seps.contains(&b) {
+            parts.push(&body[start..i]);
+            start = i + 1;
+        }
+        i += 1;
+    }
+    parts.push(&body[start..]);
+    parts.into_iter().filter(|p| !p.trim().is_empty()).collect()
+}
+
+/// Byte index of the first `target` at bracket depth 0. `=` only matches a
+/// plain assignment, never `==`, `=>`, `<=`, `>=` or `!=`.
+pub(crate) fn find_top_level(s: &str, target: u8, angle: bool) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut depth = 0i32;
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(q) = quote {
+            if b == b'\\' {
+                i += 1;
+            } else if b == q {
+                quote = None;
+            }
+        } else if depth == 0 && b == target {
+            let plain_assign = target != b'='
+                || (bytes.get(i + 1).is_none_or(|n| !matches!(n, b'=' | b'>'))
+                    && (i == 0 || !matches!(bytes[i - 1], b'=' | b'!' | b'<' | b'>')));
+            if plain_assign {
+                return Some(i);
+            }
+        } else if b == b'"' || b == b'`' {
+            quote = Some(b);
+        } else if is_open(b, angle) {
+            depth += 1;
+        } else if is_close(bytes, i, angle) {
+            depth = (depth - 1).max(0);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Contents between the first `open` at or after `from` and its matching close.
+pub(crate) fn delimited(text: &str, from: usize, open: u8, close: u8) -> Option<&str> {
+    let start = from + text.get(from..)?.find(open as char)?;
+    let bytes = text.as_bytes();
+    let mut depth = 0;
+    let mut quote: Option<u8> = None;
+    let mut i = start;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(q) = quote {
+            if b == b'\\' {
+                i += 1;
+            } else if b == q {
+                quote = None;
+            }
+        } else if b == b'"' || b == b'`' {
+            quote = Some(b);
+        } else if b == open {
+            depth += 1;
+        } else if b == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(&text[start + 1..i]);
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Split `rest` at its first plain `=` into (declaration, default).
+fn split_default(rest: &str, angle: bool) -> (&str, Option<&str>) {
+    match find_top_level(rest, b'=', angle) {
+        Some(eq) => (rest[..eq].trim(), Some(rest[eq + 1..].trim())),
+        None => (rest.trim(), None),
+    }
+}
+
+/// Parse `name: Type = default` (TypeScript, Rust, Python, Kotlin).
+fn parse_name_colon_type(member: &str, angle: bool) -> Option<ModelField> {
+    let (decl, default) = split_default(member, angle);
+    let colon = find_top_level(decl, b':', angle)?;
+    let raw_name = decl[..colon].trim();
+    let marked_optional = raw_name.ends_with('?');
+    let name = strip_modifiers(raw_name.trim_end_matches('?'));
+    // Quoted keys (`"content-type": string`) may hold any characters
+    let quoted = name.starts_with(['"', '\'']);
+    if !quoted && !IDENT.is_match(name) {
+        return None;
+    }
+    let mut field = new_field(name, Some(&decl[colon + 1..]), default);
+    field.optional |= marked_optional;
+    Some(field)
+}
+
+/// Parse `Type name = default` (Java, C#).
+fn parse_type_then_name(member: &str) -> Option<ModelField> {
+    let (decl, default) = split_default(member, true);
+    let decl = strip_modifiers(decl);
+    let cap = TRAILING_IDENT.captures(decl)?;
+    let name = cap.get(1)?;
+    let ty = decl[..name.start()].trim();
+    if ty.is_empty() {
+        return None;
+    }
+    Some(new_field(name.as_str(), Some(ty), default))
+}
+
+fn strip_modifiers(mut s: &str) -> &str {
+    loop {
+        s = s.trim_start();
+        let Some(m) = MODIFIERS.iter().find(|m| {
+            s.strip_prefix(*m).is_some_and(|rest| rest.starts_with(char::is_whitespace))
+        }) else {
+            return s;
+        };
+        s = &s[m.len()..];
+    }
+}
+
+/// Peel leading `@Annotation(args)`, `#[attr(args)]` or — in C# — `[Attr(args)]`
+/// markers, returning them as tags and the remaining text.
+fn take_annotations<'a>(mut s: &'a str, language: &str) -> (HashMap<String, String>, &'a str) {
+    let mut tags = HashMap::new();
+    loop {
+        s = s.trim_start();
+        let inner = if s.starts_with("#[") {
+            delimited(s, 1, b'[', b']').map(|inner| (inner, inner.len() + 3))
+        } else if s.starts_with('[') && language == "csharp" {
+            delimited(s, 0, b'[', b']').map(|inner| (inner, inner.len() + 2))
+        } else if let Some(rest) = s.strip_prefix('@') {
+            let name_len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            let after = &rest[name_len..];
+            let args_len = if after.starts_with('(') {
+                delimited(after, 0, b'(', b')').map_or(after.len(), |args| args.len() + 2)
+            } else {
+                0
+            };
+            Some((&rest[..name_len + args_len], 1 + name_len + args_len))
+        } else {
+            None
+        };
+        let Some((annotation, consumed)) = inner else {
+            return (tags, s);
+        };
+        // `[Required, MaxLength(50)]` carries several attributes
+        for part in split_top_level(annotation, b",", false, false) {
+            let part = part.trim();
+            let part = part.strip_prefix("property:").map_or(part, str::trim);
+            match part.find('(') {
+                Some(open) => {
+                    let args = delimited(part, open, b'(', b')').unwrap_or("");
+                    tags.insert(part[..open].trim().to_string(), squash(args));
+                }
+                None => {
+                    tags.insert(part.to_string(), String::new());
+                }
+            }
+        }
+        s = &s[consumed..];
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TypeScript / JavaScript
+// ---------------------------------------------------------------------------
+
+fn typescript_fields(decl: &str) -> Vec<ModelField> {
+    let Some(body) = delimited(decl, 0, b'{', b'}') else {
+        return Vec::new();
+    };
+    let header = &decl[..decl.find('{').unwrap_or(0)];
+
+    if header.split_whitespace().any(|w| w == "enum") {
+        return split_top_level(body, b",", false, false)
+            .into_iter()
+            .filter_map(|variant| {
+                let (name, value) = split_default(variant, false);
+                IDENT.is_match(name.trim_matches('"')).then(|| new_field(name, None, value))
+            })
+            .collect();
+    }
+
+    split_top_level(body, b";,\n", true, false)
+        .into_iter()
+        .filter_map(|member| {
+            let member = member.trim();
+            // Index signatures and methods are not data fields
+            if member.starts_with('[') {
+                return None;
+            }
+            if let (Some(paren), Some(colon)) = (member.find('('), find_top_level(member, b':', true))
+                && paren < colon
+            {
+                return None;
+            }
+            parse_name_colon_type(member, true)
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Rust
+// ---------------------------------------------------------------------------
+
+fn rust_fields(decl: &str) -> Vec<ModelField> {
+    let Some(header) = RUST_DECL.captures(decl) else {
+        return Vec::new();
+    };
+    let is_enum = &header[1] == "enum";
+    let after = header.get(0).map_or(0, |m| m.end());
+    let opener = decl[after..].find(['{', '(', ';']).map(|i| decl.as_bytes()[after + i]);
+
+    match opener {
+        Some(b'(') if !is_enum => delimited(decl, after, b'(', b')')
+            .map(|body| {
+                split_top_level(body, b",", true, false)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, member)| {
+                        let (tags, ty) = take_annotations(member, "rust");
+                        let mut field = new_field(&i.to_string(), Some(strip_modifiers(ty)), None);
+                        field.tags = tags;
+                        field
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Some(b'{') => {
+            let Some(body) = delimited(decl, after, b'{', b'}') else {
+                return Vec::new();
+            };
+            split_top_level(body, b",", true, false)
+                .into_iter()
+                .filter_map(|member| {
+                    let (tags, rest) = take_annotations(member, "rust");
+                    let mut field = if is_enum {
+                        rust_variant(rest)?
+                    } else {
+                        parse_name_colon_type(rest, true)?
+                    };
+                    if let Some(serde) = tags.get("serde") {
+                        field.optional |= serde.split(',').any(|a| a.trim() == "default");
+                        if let Some(path) = serde.split(',').find_map(|a| a.trim().strip_prefix("default")) {
+                            let path = path.trim_start_matches([' ', '=']).trim_matches('"');
+                            field.default = Some(if path.is_empty() { "Default::default()".to_string() } else { path.to_string() });
+                        }
+                    }
+                    field.tags = tags;
+                    Some(field)
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// `Name`, `Name(T)`, `Name { a: T }` or `Name = 3`
+fn rust_variant(member: &str) -> Option<ModelField> {
+    let (decl, discriminant) = split_default(member, true);
+    let name_len = decl.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(decl.len());
+    let name = &decl[..name_len];
+    if !IDENT.is_match(name) {
+        return None;
+    }
+    let payload = decl[name_len..].trim();
+    Some(new_field(name, (!payload.is_empty()).then_some(payload), discriminant))
+}
+
+// ---------------------------------------------------------------------------
+// Go
+// ---------------------------------------------------------------------------
+
+fn go_fields(decl: &str) -> Vec<ModelField> {
+    let Some(m) = GO_STRUCT.find(decl) else {
+        return Vec::new();
+    };
+    let Some(body) = delimited(decl, m.start(), b'{', b'}') else {
+        return Vec::new();
+    };
+
+    let mut fields = Vec::new();
+    for member in split_top_level(body, b";\n", false, false) {
+        let member = member.trim();
+        let (decl, tag) = match member.strip_suffix('`').and_then(|m| m.rfind('`').map(|i| (m, i))) {
+            Some((m, i)) => (m[..i].trim(), Some(&m[i + 1..])),
+            None => (member, None),
+        };
+        let tags: HashMap<String, String> = tag
+            .map(|t| GO_TAG.captures_iter(t).map(|c| (c[1].to_string(), c[2].to_string())).collect())
+            .unwrap_or_default();
+        let omitempty = tags.values().any(|v| v.split(',').skip(1).any(|o| o == "omitempty"));
+
+        let names_and_type = match GO_FIELD.captures(decl) {
+            Some(cap) => {
+                let names: Vec<String> = cap[1].split(',').map(|n| n.trim().to_string()).collect();
+                (names, cap[2].to_string())
+            }
+            None => {
+                // Embedded field: the type name doubles as the field name
+                let embedded = decl.trim_start_matches('*');
+                let name = embedded.rsplit('.').next().unwrap_or(embedded);
+                if !IDENT.is_match(name) {
+                    continue;
+                }
+                (vec![name.to_string()], decl.to_string())
+            }
+        };
+        let (names, ty) = names_and_type;
+        for name in names {
+            let mut field = new_field(&name, Some(&ty), None);
+            field.optional |= ty.starts_with('*') || omitempty;
+            field.tags = tags.clone();
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+// ---------------------------------------------------------------------------
+// Python: dataclasses, Pydantic models, TypedDicts
+// ---------------------------------------------------------------------------
+
+fn python_fields(decl: &str) -> Vec<ModelField> {
+    let Some(class) = PYTHON_CLASS.find(decl) else {
+        return Vec::new();
+    };
+    let Some(colon) = find_top_level(&decl[class.end()..], b':', false) else {
+        return Vec::new();
+    };
+    let header = &decl[class.end()..class.end() + colon];
+    let total_false = header.replace(' ', "").contains("total=False");
+    let body = &decl[class.end() + colon + 1..];
+
+    let members = split_top_level(body, b"\n", false, false);
+    let indent_of = |m: &str| m.len() - m.trim_start().len();
+    let Some(base_indent) = members.iter().map(|m| indent_of(m)).find(|&i| i > 0) else {
+        return Vec::new();
+    };
+
+    members
+        .into_iter()
+        .filter(|m| indent_of(m) == base_indent)
+        .filter_map(|member| {
+            let member = member.trim();
+            if ["def ", "async ", "class ", "@", "return "].iter().any(|k| member.starts_with(k)) {
+                return None;
+            }
+            let mut field = parse_name_colon_type(member, false)?;
+            field.optional |= total_false;
+            Some(field)
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Protobuf
+// ---------------------------------------------------------------------------
+
+fn proto_fields(decl: &str) -> Vec<ModelField> {
+    let Some(m) = PROTO_MESSAGE.find(decl) else {
+        return Vec::new();
+    };
+    delimited(decl, m.end(), b'{', b'}').map(|body| proto_members(body, false)).unwrap_or_default()
+}
+
+fn proto_members(body: &str, in_oneof: bool) -> Vec<ModelField> {
+    let mut fields = Vec::new();
+    for member in split_top_level(body, b";\n", true, true) {
+        let member = member.trim();
+        if let Some(rest) = member.strip_prefix("oneof ") {
+            if let Some(inner) = delimited(rest, 0, b'{', b'}') {
+                fields.extend(proto_members(inner, true));
+            }
+            continue;
+        }
+        let Some(cap) = PROTO_FIELD.captures(member) else {
+            continue;
+        };
+        let label = cap.get(1).map(|l| l.as_str());
+        let ty = match label {
+            Some("repeated") => format!("repeated {}", &cap[2]),
+            _ => cap[2].to_string(),
+        };
+        let mut field = new_field(&cap[3], Some(&ty), None);
+        field.optional = in_oneof || label == Some("optional");
+        field.tags.insert("number".to_string(), cap[4].to_string());
+        fields.push(field);
+    }
+    fields
+}
+
+// ---------------------------------------------------------------------------
+// Java / Kotlin / C#
+// ---------------------------------------------------------------------------
+
+fn class_fields(decl: &str, language: &str) -> Vec<ModelField> {
+    let mut fields = Vec::new();
+
+    // Records and Kotlin primary constructors declare fields as parameters
+    if let Some(m) = RECORD_HEADER.find(decl)
+        && let Some(params) = delimited(decl, m.end() - 1, b'(', b')')
+    {
+        for param in split_top_level(params, b",", true, false) {
+            if let Some(field) = class_member(param, language, true) {
+                fields.push(field);
+            }
+        }
+    }
+
+    if let Some(m) = CLASS_HEADER.find(decl) {
+        let after = m.end();
+        // Skip over a primary constructor so its default-value braces aren't taken for the body
+        let body_from = match (decl[after..].find('('), decl[after..].find('{')) {
+            (Some(p), Some(b)) if p < b => delimited(decl, after + p, b'(', b')')
+                .map_or(after, |params| after + p + params.len() + 2),
+            _ => after,
+        };
+        // Kotlin properties end at a newline; Java and C# members need the `;`
+        // so annotations on their own line stay with the member below
+        let seps: &[u8] = if language == "kotlin" { b";\n" } else { b";" };
+        if let Some(body) = delimited(decl, body_from, b'{', b'}') {
+            for member in split_top_level(body, seps, true, true) {
+                if let Some(field) = class_member(member, language, false) {
+                    fields.push(field);
+                }
+            }
+        }
+    }
+
+    fields
+}
+
+fn class_member(member: &str, language: &str, is_param: bool) -> Option<ModelField> {
+    let (tags, rest) = take_annotations(member, language);
+    let rest = rest.trim();
+    if rest.is_empty()
+        || rest.starts_with('{')
+        || rest.split_whitespace().any(|w| matches!(w, "static" | "class" | "interface" | "enum" | "fun" | "init" | "companion" | "object" | "record"))
+    {
+        return None;
+    }
+
+    let mut field = if language == "kotlin" {
+        // Body members must be properties; constructor params without val/var are plain args
+        let is_property = rest.split_whitespace().any(|w| w == "val" || w == "var");
+        if !is_property && !is_param {
+            return None;
+        }
+        parse_name_colon_type(rest, true)?
+    } else {
+        // Methods, constructors and expression-bodied members
+        let paren = find_top_level(rest, b'(', true);
+        let brace = find_top_level(rest, b'{', true);
+        let eq = find_top_level(rest, b'=', true);
+        if rest.contains("=>") || paren.is_some_and(|p| eq.is_none_or(|e| p < e) && brace.is_none_or(|b| p < b)) {
+            return None;
+        }
+        match brace {
+            // C# property: `Type Name { get; set; } = default`
+            Some(b) if eq.is_none_or(|e| b < e) => {
+                let accessors_end = b + delimited(rest, b, b'{', b'}')?.len() + 2;
+                let default = rest[accessors_end..].trim().strip_prefix('=').map(str::trim);
+                let mut field = parse_type_then_name(&rest[..b])?;
+                field.default = default.map(squash).filter(|d| !d.is_empty());
+                field
+            }
+            _ => parse_type_then_name(rest)?,
+        }
+    };
+
+    field.optional |= tags.keys().any(|t| t == "Nullable" || t.ends_with(".Nullable"));
+    field.tags = tags;
+    Some(field)
+}
+
+/// Whether a Java or C# class holds only data: at least one field or property,
+/// and otherwise only constructors, Java bean accessors, computed properties and
+/// the `equals`/`hashCode`/`toString` family.
+pub fn is_data_class(decl: &str, language: &str) -> bool {
+    let Some(header) = PLAIN_CLASS_HEADER.captures(decl) else {
+        return false;
+    };
+    let class_name = &header[1];
+    let Some(body) = delimited(decl, header.get(0).unwrap().end(), b'{', b'}') else {
+        return false;
+    };
+    let is_accessor = |method: &str| {
+        method == class_name
+            || OBJECT_METHODS.contains(&method)
+            || (language == "java"
+                && ["get", "set", "is"].iter().any(|p| {
+                    method.strip_prefix(p).and_then(|rest| rest.chars().next()).is_some_and(char::is_uppercase)
+                }))
+    };
+
+    let mut has_field = false;
+    for member in split_top_level(body, b";", true, true) {
+        if class_member(member, language, false).is_some() {
+            has_field = true;
+            continue;
+        }
+        // Only the signature of an expression-bodied member counts
+        let rest = take_annotations(member, language).1.split("=>").next().unwrap_or("").trim();
+        let allowed = match find_top_level(rest, b'(', true) {
+            Some(paren) => TRAILING_IDENT.captures(&rest[..paren]).is_some_and(|m| is_accessor(&m[1])),
+            // Static constants and computed properties; nested types are not data
+            None => !rest.split_whitespace().any(|w| matches!(w, "class" | "interface" | "enum" | "record" | "struct")),
+        };
+        if !allowed {
+            return false;
+        }
+    }
+    has_field
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bases_across_languages() {
+        assert_eq!(extract_bases("class Admin(models.User, Generic[T], metaclass=Meta):\n    pass", "python"), vec!["models.User", "Generic"]);
+        assert_eq!(extract_bases("export interface Admin extends User, Auditable<Admin> {\n}", "typescript"), vec!["User", "Auditable"]);
+        assert_eq!(extract_bases("public class Admin extends User implements Serializable {\n}", "java"), vec!["User", "Serializable"]);
+        assert_eq!(extract_bases("data class Admin(val level: Int) : User(), Auditable", "kotlin"), vec!["User", "Auditable"]);
+        assert_eq!(extract_bases("public record AdminDto<T>(Guid Id) : UserDto(Id) where T : class;", "csharp"), vec!["UserDto"]);
+        assert!(extract_bases("pub struct Admin { user: User }", "rust").is_empty());
+    }
+
+    fn summary(fields: &[ModelField]) -> Vec<(String, Option<String>, bool, Option<String>)> {
+        fields
+            .iter()
+            .map(|f| (f.name.clone(), f.type_name.clone(), f.optional, f.default.clone()))
+            .collect()
+    }
+
+    fn f(name: &str, ty: Option<&str>, optional: bool, default: Option<&str>) -> (String, Option<String>, bool, Option<String>) {
+        (name.to_string(), ty.map(String::from), optional, default.map(String::from))
+    }
+
+    #[test]
+    fn typescript_interface_and_enum() {
+        let decl = "export interface Order {\n  id: string;\n  readonly items: Array<{ sku: string; qty: number }>;\n  note?: string\n  total: number | null,\n  [key: string]: unknown;\n  toJSON(): string;\n}";
+        assert_eq!(
+            summary(&extract_fields(decl, "typescript")),
+            vec![
+                f("id", Some("string"), false, None),
+                f("items", Some("Array<{ sku: string; qty: number }>"), false, None),
+                f("note", Some("string"), true, None),
+                f("total", Some("number | null"), true, None),
+            ]
+        );
+
+        let decl = "enum Status {\n  Active = \"ACTIVE\",\n  Disabled,\n}";
+        assert_eq!(
+            summary(&extract_fields(decl, "typescript")),
+            vec![f("Active", None, false, Some("\"ACTIVE\"")), f("Disabled", None, false, None)]
+        );
+    }
+
+    #[test]
+    fn rust_structs_enums_and_serde_defaults() {
+        let decl = "#[derive(Serialize)]\npub struct Order<'a> {\n    pub id: u64,\n    #[serde(default)]\n    pub items: Vec<LineItem>,\n    pub note: Option<&'a str>,\n    lookup: HashMap<String, u32>,\n}";
+        let fields = extract_fields(decl, "rust");
+        assert_eq!(
+            summary(&fields),
+            vec![
+                f("id", Some("u64"), false, None),
+                f("items", Some("Vec<LineItem>"), true, Some("Default::default()")),
+                f("note", Some("Option<&'a str>"), true, None),
+                f("lookup", Some("HashMap<String, u32>"), false, None),
+            ]
+        );
+        assert_eq!(fields[1].tags.get("serde").map(String::as_str), Some("default"));
+
+        let decl = "pub enum Shape {\n    Circle(f64),\n    Rect { w: f64, h: f64 },\n    Empty = 0,\n}";
+        assert_eq!(
+            summary(&extract_fields(decl, "rust")),
+            vec![
+                f("Circle", Some("(f64)"), false, None),
+                f("Rect", Some("{ w: f64, h: f64 }"), false, None),
+                f("Empty", None, false, Some("0")),
+            ]
+        );
+
+        let decl = "pub struct Meters(pub f64, u32);";
+        assert_eq!(
+            summary(&extract_fields(decl, "rust")),
+            vec![f("0", Some("f64"), false, None), f("1", Some("u32"), false, None)]
+        );
+    }
+
+    #[test]
+    fn go_struct_tags_and_embedding() {
+        let decl = "type User struct {\n\tBase\n\tID, OrgID int64 `json:\"id\" db:\"id\"`\n\tEmail *string `json:\"email,omitempty\"`\n\tRoles []Role\n}";
+        let fields = extract_fields(decl, "go");
+        assert_eq!(
+            summary(&fields),
+            vec![
+                f("Base", Some("Base"), false, None),
+                f("ID", Some("int64"), false, None),
+                f("OrgID", Some("int64"), false, None),
+                f("Email", Some("*string"), true, None),
+                f("Roles", Some("[]Role"), false, None),
+            ]
+        );
+        assert_eq!(fields[1].tags.get("db").map(String::as_str), Some("id"));
+        assert_eq!(fields[3].tags.get("json").map(String::as_str), Some("email,omitempty"));
+    }
+
+    #[test]
+    fn python_pydantic_dataclass_and_typeddict() {
+        let decl = "class User(BaseModel):\n    \"\"\"   \"\"\"\n    id: int\n    name: str = Field(\n        default=\"anon\", max_length=50\n    )\n    email: Optional[str] = None\n    tags: list[str] | None\n    model_config = ConfigDict(extra=\"forbid\")\n\n    class Config:\n        orm_mode: bool = True\n\n    def display(self) -> str:\n        return self.name\n";
+        assert_eq!(
+            summary(&extract_fields(decl, "python")),
+            vec![
+                f("id", Some("int"), false, None),
+                f("name", Some("str"), false, Some("Field( default=\"anon\", max_length=50 )")),
+                f("email", Some("Optional[str]"), true, Some("None")),
+                f("tags", Some("list[str] | None"), true, None),
+            ]
+        );
+
+        let decl = "class Movie(TypedDict, total=False):\n    title: str\n    year: int\n";
+        assert!(extract_fields(decl, "python").iter().all(|f| f.optional));
+    }
+
+    #[test]
+    fn protobuf_messages_with_oneof() {
+        let decl = "message Order {\n  string id = 1;\n  repeated LineItem items = 2;\n  optional string note = 3;\n  map<string, int32> counts = 4;\n  message Nested { int32 x = 1; }\n  oneof payment {\n    Card card = 5;\n    Cash cash = 6;\n  }\n}";
+        let fields = extract_fields(decl, "protobuf");
+        assert_eq!(
+            summary(&fields),
+            vec![
+                f("id", Some("string"), false, None),
+                f("items", Some("repeated LineItem"), false, None),
+                f("note", Some("string"), true, None),
+                f("counts", Some("map<string, int32>"), false, None),
+                f("card", Some("Card"), true, None),
+                f("cash", Some("Cash"), true, None),
+            ]
+        );
+        assert_eq!(fields[1].tags.get("number").map(String::as_str), Some("2"));
+    }
+
+    #[test]
+    fn java_entity_and_record() {
+        let decl = "@Entity\npublic class Order {\n    @Id\n    private Long id;\n    @Nullable private String note;\n    private List<LineItem> items = new ArrayList<>();\n    private static final long serialVersionUID = 1L;\n    public Long getId() { return id; }\n}";
+        let fields = extract_fields(decl, "java");
+        assert_eq!(
+            summary(&fields),
+            vec![
+                f("id", Some("Long"), false, None),
+                f("note", Some("String"), true, None),
+                f("items", Some("List<LineItem>"), false, Some("new ArrayList<>()")),
+            ]
+        );
+        assert!(fields[0].tags.contains_key("Id"));
+
+        let decl = "public record Point(int x, @NotNull Optional<Integer> y) {}";
+        assert_eq!(
+            summary(&extract_fields(decl, "java")),
+            vec![f("x", Some("int"), false, None), f("y", Some("Optional<Integer>"), true, None)]
+        );
+    }
+
+    #[test]
+    fn kotlin_data_class_and_csharp_properties() {
+        let decl = "data class User(\n    val id: Long,\n    @SerialName(\"mail\") val email: String? = null,\n    val roles: List<Role> = emptyList(),\n) {\n    val display: String get() = email ?: \"\"\n    fun greet() = \"hi\"\n}";
+        let fields = extract_fields(decl, "kotlin");
+        assert_eq!(
+            summary(&fields)[..3],
+            vec![
+                f("id", Some("Long"), false, None),
+                f("email", Some("String?"), true, Some("null")),
+                f("roles", Some("List<Role>"), false, Some("emptyList()")),
+            ]
+        );
+        assert_eq!(fields[1].tags.get("SerialName").map(String::as_str), Some("\"mail\""));
+
+        let decl = "public class OrderDto\n{\n    [Required]\n    public string Id { get; set; } = \"\";\n    public int? Quantity { get; init; }\n    public decimal Total => Quantity ?? 0;\n    public void Reset() { Quantity = null; }\n}";
+        let fields = extract_fields(decl, "csharp");
+        assert_eq!(
+            summary(&fields),
+            vec![f("Id", Some("string"), false, Some("\"\"")), f("Quantity", Some("int?"), true, None)]
+        );
+        assert!(fields[0].tags.contains_key("Required"));
+    }
+
+    #[test]
+    fn data_classes_hold_only_fields_and_accessors() {
+        let pojo = "public class Customer {\n    private static final long serialVersionUID = 1L;\n    private String name;\n    public Customer(String name) { this.name = name; }\n    public String getName() { return name; }\n    public void setName(String name) { this.name = name; }\n    @Override public String toString() { return name; }\n}";
+        assert!(is_data_class(pojo, "java"));
+        let service = "public class CustomerService {\n    private Repo repo;\n    public Customer find(String id) { return repo.get(id); }\n}";
+        assert!(!is_data_class(service, "java"));
+        assert!(!is_data_class("public class Empty {\n    public Empty() {}\n}", "java"));
+
+        let poco = "public class Customer\n{\n    public string Name { get; set; }\n    public string Display => Name.ToUpper();\n    public override string ToString() => Name;\n}";
+        assert!(is_data_class(poco, "csharp"));
+        assert!(!is_data_class("public class Mailer\n{\n    public string Host { get; set; }\n    public void Send() { }\n}", "csharp"));
+    }
+}
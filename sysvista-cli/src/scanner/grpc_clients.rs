@@ -0,0 +1,113 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::output::schema::{ComponentKind, DetectedComponent, SourceLocation, TransportProtocol};
+
+use super::lexer::is_code;
+use super::make_id;
+use super::orm::line_of;
+
+// ---------------------------------------------------------------------------
+// gRPC client stubs — generated clients named after the proto `service`
+// ---------------------------------------------------------------------------
+
+/// Stub constructors; the first group is the proto service name.
+static STUB_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        // Go: pb.NewUserServiceClient(conn)
+        Regex::new(r"\bNew(\w+)Client\s*\(\s*\w+\s*\)").unwrap(),
+        // Python: user_pb2_grpc.UserServiceStub(channel)
+        Regex::new(r"\b([A-Z]\w*)Stub\s*\(\s*\w+\s*\)").unwrap(),
+        // Java/Kotlin: UserServiceGrpc.newBlockingStub(channel)
+        Regex::new(r"\b(\w+)Grpc\.new(?:Blocking|Future)?Stub\s*\(").unwrap(),
+        // Rust tonic: UserServiceClient::connect(addr)
+        Regex::new(r"\b([A-Z]\w*)Client::(?:connect|new|with_interceptor|with_origin)\s*\(").unwrap(),
+        // C#: new UserService.UserServiceClient(channel)
+        Regex::new(r"\bnew\s+(\w+)\.\w+Client\s*\(").unwrap(),
+        // Node: new proto.UserService("host:50051", grpc.credentials...)
+        Regex::new(r"\bnew\s+(?:\w+\.)+(\w+)\s*\(\s*[^,()]+,\s*grpc\.credentials").unwrap(),
+    ]
+});
+
+/// One generated client constructed for a proto service.
+#[derive(Debug, Clone)]
+pub struct GrpcStub {
+    pub service: String,
+    pub line: u32,
+}
+
+/// gRPC stubs constructed in a file. Only files that mention gRPC (or tonic)
+/// are searched, since the constructor shapes are common elsewhere, and only
+/// constructors that start in `code` count.
+pub fn detect_grpc_stubs(content: &str, code: &str) -> Vec<GrpcStub> {
+    if !(content.contains("grpc") || content.contains("tonic")) {
+        return Vec::new();
+    }
+    let mut stubs: Vec<GrpcStub> = Vec::new();
+    for pattern in STUB_PATTERNS.iter() {
+        for cap in pattern.captures_iter(content) {
+            if !is_code(code, cap.get(0).unwrap().start()) {
+                continue;
+            }
+            let line = line_of(content, cap.get(0).unwrap().start());
+            if stubs.iter().any(|s| s.line == line && s.service == cap[1]) {
+                continue;
+            }
+            stubs.push(GrpcStub {
+                service: cap[1].to_string(),
+                line,
+            });
+        }
+    }
+    stubs.sort_by_key(|s| s.line);
+    stubs
+}
+
+/// A node for a gRPC service that no proto in the scan defines.
+pub fn outbound_service(stub: &GrpcStub, language: &str, file: &str) -> DetectedComponent {
+    DetectedComponent {
+        id: make_id("transport", &format!("outbound:grpc:{}", stub.service), ""),
+        name: stub.service.clone(),
+        kind: ComponentKind::Transport,
+        language: language.to_string(),
+        source: SourceLocation {
+            file: file.to_string(),
+            line_start: Some(stub.line),
+            line_end: Some(stub.line),
+        },
+        metadata: HashMap::from([("direction".to_string(), "outbound".to_string())]),
+        transport_protocol: Some(TransportProtocol::Grpc),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(content: &str, language: &str) -> Vec<GrpcStub> {
+        let masked = crate::scanner::lexer::mask(content, language);
+        detect_grpc_stubs(&masked.text, &masked.code)
+    }
+
+    #[test]
+    fn detects_stubs_by_language() {
+        let go = "import \"google.golang.org/grpc\"\n\nclient := pb.NewUserServiceClient(conn)\n";
+        let py = "import grpc\nstub = users_pb2_grpc.UserServiceStub(channel)\n";
+        let java = "import io.grpc.*;\nvar stub = UserServiceGrpc.newBlockingStub(channel);\n";
+        let rs = "use tonic::transport::Channel;\nlet client = UserServiceClient::connect(\"http://[::1]:50051\").await?;\n";
+        for (src, language) in [(go, "go"), (py, "python"), (java, "java"), (rs, "rust")] {
+            let stubs = detect(src, language);
+            assert_eq!(stubs.len(), 1, "{src}");
+            assert_eq!(stubs[0].service, "UserService");
+        }
+        assert!(detect("client := redis.NewRedisClient(opts)\n", "go").is_empty());
+    }
+
+    #[test]
+    fn skips_stubs_inside_string_literals() {
+        let py = "import grpc\nHELP = \"create one with users_pb2_grpc.UserServiceStub(channel)\"\n";
+        assert!(detect(py, "python").is_empty());
+    }
+}
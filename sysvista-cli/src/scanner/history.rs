@@ -0,0 +1,168 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+use std::sync::LazyLock;
+
+use crate::output::schema::{ComponentHistory, DetectedComponent};
+
+// ---------------------------------------------------------------------------
+// Git history — churn and authorship per component, for `--git`
+// ---------------------------------------------------------------------------
+
+/// Separators in the `git log` format: record before each commit header,
+/// unit between its fields
+const RECORD: char = '\u{1e}';
+const UNIT: char = '\u{1f}';
+
+/// `@@ -old_start,old_count +new_start,new_count @@`; counts default to 1
+static HUNK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^@@ -\d+(?:,(\d+))? \+(\d+)(?:,(\d+))? @@").unwrap());
+
+/// Annotate every component with the commits since `since` (any date `git log
+/// --since` accepts) that changed its lines. Reads the local repository only;
+/// fails when `root` is not inside one or git is not installed.
+pub fn annotate(root: &Path, components: &mut [DetectedComponent], since: &str) -> Result<(), String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["log", "--no-merges", "--no-renames", "--no-color", "--no-ext-diff", "--relative", "-p", "-U0"])
+        .arg(format!("--since={since}"))
+        .arg(format!("--format={RECORD}%H{UNIT}%aN{UNIT}%at"))
+        .output()
+        .map_err(|e| format!("cannot run git: {e}"))?;
+    if !output.status.success() {
+        return Err(format!("git log failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let log = String::from_utf8_lossy(&output.stdout);
+    let totals = tally(&log, components);
+    for (comp, history) in components.iter_mut().zip(totals) {
+        comp.history = Some(history);
+    }
+    Ok(())
+}
+
+/// Per-component totals from `git log -p -U0` output, in the order of
+/// `components`.
+fn tally(log: &str, components: &[DetectedComponent]) -> Vec<ComponentHistory> {
+    let mut by_file: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, comp) in components.iter().enumerate() {
+        by_file.entry(comp.source.file.as_str()).or_default().push(i);
+    }
+
+    let mut totals = vec![ComponentHistory::default(); components.len()];
+    let mut authors: Vec<HashSet<&str>> = vec![HashSet::new(); components.len()];
+    let mut latest: Vec<Option<i64>> = vec![None; components.len()];
+
+    for commit in log.split(RECORD).filter(|c| !c.is_empty()) {
+        let (header, diff) = commit.split_once('\n').unwrap_or((commit, ""));
+        let mut fields = header.split(UNIT);
+        let (Some(_hash), Some(author), Some(time)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let time: Option<i64> = time.trim().parse().ok();
+
+        let mut touched: HashSet<usize> = HashSet::new();
+        let mut file: Option<&str> = None;
+        let mut lines = diff.lines();
+        while let Some(line) = lines.next() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                file = path.strip_prefix("b/");
+                continue;
+            }
+            let Some(hunk) = HUNK_RE.captures(line) else {
+                continue;
+            };
+            let count = |i: usize| hunk.get(i).map_or(1, |m| m.as_str().parse().unwrap_or(1));
+            let (removed, start, added) = (count(1), count(2), count(3));
+            // With no context every line of the hunk is a change; skip them so
+            // a changed line that looks like a header is never read as one
+            for _ in 0..removed + added {
+                lines.next();
+            }
+
+            let end = start + added.max(1) - 1;
+            let Some(candidates) = file.and_then(|f| by_file.get(f)) else {
+                continue;
+            };
+            for &i in candidates {
+                let span = &components[i].source;
+                let first = span.line_start.unwrap_or(1) as usize;
+                let last = span.line_end.map_or(first, |l| l as usize).max(first);
+                // A component without a line covers its whole file
+                let (first, last) = if span.line_start.is_some() { (first, last) } else { (1, usize::MAX) };
+                if start > last || end < first {
+                    continue;
+                }
+                // Only the part of the hunk inside the span counts; removed
+                // lines have no new position, so they are shared in proportion
+                let added_inside = if added == 0 { 0 } else { end.min(last) + 1 - start.max(first) };
+                let removed_inside = (removed * added_inside).checked_div(added).unwrap_or(removed);
+                totals[i].lines_changed += added_inside + removed_inside;
+                touched.insert(i);
+            }
+        }
+
+        for i in touched {
+            totals[i].commits += 1;
+            authors[i].insert(author);
+            latest[i] = latest[i].max(time);
+        }
+    }
+
+    for (i, history) in totals.iter_mut().enumerate() {
+        history.authors = authors[i].len();
+        history.last_modified = latest[i]
+            .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+            .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::fixtures::component;
+    use crate::output::schema::{ComponentKind, SourceLocation};
+
+    fn spanning(name: &str, file: &str, lines: (u32, u32)) -> DetectedComponent {
+        DetectedComponent {
+            source: SourceLocation {
+                file: file.to_string(),
+                line_start: Some(lines.0),
+                line_end: Some(lines.1),
+            },
+            ..component(name, ComponentKind::Service, file)
+        }
+    }
+
+    #[test]
+    fn tallies_hunks_inside_each_span() {
+        let log = [
+            "\u{1e}c2\u{1f}Bea\u{1f}1767225600\n",
+            "diff --git a/app/users.py b/app/users.py\n",
+            "--- a/app/users.py\n+++ b/app/users.py\n",
+            "@@ -12 +12,2 @@ def create_user(\n-    save(user)\n+    validate(user)\n+    save(user)\n",
+            // A removed line that looks like a hunk header is skipped as content
+            "@@ -40,1 +41,0 @@\n-@@ -1 +1 @@\n",
+            "\u{1e}c1\u{1f}Ada\u{1f}1704067200\n",
+            "diff --git a/app/users.py b/app/users.py\n",
+            "--- /dev/null\n+++ b/app/users.py\n",
+            "@@ -0,0 +1,3 @@\n+def create_user(user):\n+    save(user)\n+\n",
+        ]
+        .concat();
+        let components = [
+            spanning("create_user", "app/users.py", (10, 20)),
+            spanning("delete_user", "app/users.py", (38, 45)),
+            spanning("send_email", "app/email.py", (1, 9)),
+        ];
+
+        let totals = tally(&log, &components);
+        assert_eq!((totals[0].commits, totals[0].authors, totals[0].lines_changed), (1, 1, 3));
+        assert_eq!(totals[0].last_modified.as_deref(), Some("2026-01-01T00:00:00Z"));
+        assert_eq!((totals[1].commits, totals[1].lines_changed), (1, 1));
+        assert_eq!(totals[2].commits, 0);
+        assert_eq!(totals[2].last_modified, None);
+    }
+}
@@ -0,0 +1,442 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::output::schema::{ComponentKind, DetectedComponent, SourceLocation, TransportProtocol};
+
+use super::fields::{delimited, split_top_level};
+use super::lexer::is_code;
+use super::make_id;
+use super::orm::line_of;
+
+// ---------------------------------------------------------------------------
+// Outbound HTTP calls — fetch, axios, requests/httpx, reqwest, net/http
+// ---------------------------------------------------------------------------
+
+/// One outbound request found in a source file.
+#[derive(Debug, Clone)]
+pub struct HttpCall {
+    pub method: String,
+    /// The URL as written, with interpolations turned into `{placeholders}`
+    pub url: String,
+    pub host: Option<String>,
+    pub path: String,
+    /// The receiver or function the request was made through (`axios`, `requests`, `fetch`)
+    pub client: String,
+    pub line: u32,
+}
+
+/// `client.get(url)`, `requests.post(url)`, `this.http.put<T>(url)`, `reqwest::Client::new().get(url)`
+static VERB_CALL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?P<recv>[A-Za-z_][\w.]*(?:::\w+)*(?:\(\))?)\s*\.\s*(?P<verb>get|post|put|patch|delete|head)\s*(?:<[^<>()]*>)?\s*\(").unwrap()
+});
+
+/// `fetch(url, { method: "POST" })`
+static FETCH_CALL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?:^|[^\w.])(?P<recv>fetch)\s*\(").unwrap());
+
+/// `requests.request("POST", url)`, `client.request(Method::POST, url)`, `axios.request({...})`, `axios({...})`
+static REQUEST_CALL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?P<recv>[A-Za-z_][\w.]*)\s*\.\s*request\s*\(|\b(?P<axios>axios)\s*\(").unwrap());
+
+/// `reqwest::get(url)` and `reqwest::blocking::get(url)`
+static REQWEST_GET: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?P<recv>reqwest(?:::blocking)?)::get\s*\(").unwrap());
+
+/// Go `http.Get(url)`, `http.Post(url, ...)`, `http.NewRequest(method, url, body)`
+static GO_CALL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?P<recv>http)\.(?P<verb>Get|Post|Head|PostForm|NewRequest|NewRequestWithContext)\s*\(").unwrap()
+});
+
+/// `method: "POST"` inside fetch options or an axios config object
+static METHOD_OPTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bmethod\s*:\s*["'`](\w+)["'`]"#).unwrap());
+
+/// `url: "/users"` inside an axios config object
+static URL_OPTION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\burl\s*:\s*").unwrap());
+
+/// `format!(...)`, `fmt.Sprintf(...)`, `String.format(...)` wrapping the URL
+static FORMAT_CALL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:format!|fmt\.Sprintf|String\.format)\s*\(").unwrap());
+
+/// `${expr}`, `{expr}`, `%s`/`%d`/`%v` interpolations
+static INTERPOLATION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$\{([^}]*)\}|%[sdv]").unwrap());
+
+/// Receivers that register server routes rather than make requests
+const ROUTE_RECEIVERS: &[&str] = &[
+    "app", "router", "server", "bp", "blueprint", "fastify", "mux", "e", "r", "g", "engine", "group",
+];
+
+/// HTTP methods accepted as the method argument of `request(...)`-style calls
+const METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Find outbound HTTP calls whose URL is a literal or a simple format string.
+/// Calls through a bare variable (`requests.get(url)`) are skipped: without
+/// the path there is nothing to link them to. Calls must start in `code`.
+pub fn detect_http_calls(content: &str, code: &str, language: &str) -> Vec<HttpCall> {
+    if !matches!(
+        language,
+        "typescript" | "javascript" | "python" | "rust" | "go" | "java" | "kotlin" | "csharp" | "ruby"
+    ) {
+        return Vec::new();
+    }
+
+    let mut calls = Vec::new();
+    let mut push = |offset: usize, method: &str, client: &str, url: Option<String>| {
+        let Some(url) = url.filter(|_| is_code(code, offset)) else {
+            return;
+        };
+        let (host, path) = split_url(&url);
+        calls.push(HttpCall {
+            method: method.to_uppercase(),
+            url,
+            host,
+            path,
+            client: client.to_string(),
+            line: line_of(content, offset),
+        });
+    };
+
+    if language == "go" {
+        for caps in GO_CALL.captures_iter(content) {
+            let whole = caps.get(0).unwrap();
+            let args = call_args(content, whole.end() - 1);
+            let verb = &caps["verb"];
+            let (method, url) = if verb.starts_with("NewRequest") {
+                let skip = usize::from(verb == "NewRequestWithContext");
+                let method = args.get(skip).and_then(|m| method_arg(m));
+                (method.unwrap_or_else(|| "GET".to_string()), args.get(skip + 1))
+            } else {
+                let method = if verb == "PostForm" { "POST" } else { verb };
+                (method.to_string(), args.first())
+            };
+            push(whole.start(), &method, "net/http", url.and_then(|u| url_template(u)));
+        }
+        return calls;
+    }
+
+    for caps in VERB_CALL.captures_iter(content) {
+        let whole = caps.get(0).unwrap();
+        let recv = &caps["recv"];
+        let root = recv.rsplit('.').next().unwrap_or(recv);
+        if ROUTE_RECEIVERS.contains(&root) || content[..whole.start()].trim_end().ends_with('@') {
+            continue;
+        }
+        let args = call_args(content, whole.end() - 1);
+        // Express-style `api.get("/users", (req, res) => ...)` registers a route
+        if args.iter().skip(1).any(|a| is_handler(a)) {
+            continue;
+        }
+        push(whole.start(), &caps["verb"], recv, args.first().and_then(|u| url_template(u)));
+    }
+
+    for caps in FETCH_CALL.captures_iter(content) {
+        let start = caps.name("recv").unwrap().start();
+        let whole = caps.get(0).unwrap();
+        let args = call_args(content, whole.end() - 1);
+        let method = args
+            .get(1)
+            .and_then(|opts| METHOD_OPTION.captures(opts))
+            .map_or_else(|| "GET".to_string(), |m| m[1].to_string());
+        push(start, &method, "fetch", args.first().and_then(|u| url_template(u)));
+    }
+
+    for caps in REQUEST_CALL.captures_iter(content) {
+        let whole = caps.get(0).unwrap();
+        let client = caps.name("recv").or(caps.name("axios")).unwrap();
+        let args = call_args(content, whole.end() - 1);
+        let Some(first) = args.first() else {
+            continue;
+        };
+        let (method, url) = if first.trim_start().starts_with('{') {
+            // axios config object: `{ method: "post", url: "/users" }`
+            let method = METHOD_OPTION.captures(first).map(|m| m[1].to_string());
+            let url = URL_OPTION.find(first).and_then(|m| {
+                let rest = &first[m.end()..];
+                let value = split_top_level(rest, b",", false, false).into_iter().next()?;
+                url_template(value.trim().trim_end_matches('}'))
+            });
+            (method.unwrap_or_else(|| "GET".to_string()), url)
+        } else {
+            let Some(method) = method_arg(first) else {
+                continue;
+            };
+            (method, args.get(1).and_then(|u| url_template(u)))
+        };
+        push(client.start(), &method, client.as_str(), url);
+    }
+
+    for caps in REQWEST_GET.captures_iter(content) {
+        let whole = caps.get(0).unwrap();
+        let args = call_args(content, whole.end() - 1);
+        push(whole.start(), "GET", &caps["recv"], args.first().and_then(|u| url_template(u)));
+    }
+
+    calls.sort_by_key(|c| c.line);
+    calls
+}
+
+/// Top-level arguments of the call whose `(` is at `open`.
+fn call_args(content: &str, open: usize) -> Vec<&str> {
+    delimited(content, open, b'(', b')')
+        .map(|inner| split_top_level(inner, b",", false, false))
+        .unwrap_or_default()
+        .into_iter()
+        .map(str::trim)
+        .collect()
+}
+
+/// Whether an argument is an inline request handler (`(req, res) => ...`, `function (...)`).
+fn is_handler(arg: &str) -> bool {
+    arg.contains("=>") || arg.starts_with("function") || arg.starts_with("async ")
+}
+
+/// The HTTP method named by a `"POST"`, `Method::POST` or `http.MethodPost` argument.
+fn method_arg(arg: &str) -> Option<String> {
+    let arg = arg.trim();
+    let word = arg
+        .trim_matches(|c| matches!(c, '"' | '\'' | '`'))
+        .rsplit([':', '.'])
+        .next()?;
+    let word = word.strip_prefix("Method").filter(|w| !w.is_empty()).unwrap_or(word);
+    let upper = word.to_uppercase();
+    METHODS.contains(&upper.as_str()).then_some(upper)
+}
+
+/// The URL template an argument expression builds, when it contains a literal
+/// path or absolute URL. Interpolated values become `{name}` (or `{}` when
+/// unnamed); a leading base-URL variable is dropped.
+pub(crate) fn url_template(expr: &str) -> Option<String> {
+    let mut expr = expr.trim();
+    if let Some(rest) = expr.strip_prefix("url")
+        && let Some(value) = rest.trim_start().strip_prefix('=')
+        && !value.starts_with('=')
+    {
+        expr = value.trim_start();
+    }
+    if let Some(m) = FORMAT_CALL.find(expr) {
+        let inner = delimited(expr, m.end() - 1, b'(', b')')?;
+        return url_template(split_top_level(inner, b",", false, false).first()?);
+    }
+
+    let mut url = String::new();
+    let mut literal = false;
+    for (i, part) in split_top_level(expr, b"+", false, false).into_iter().enumerate() {
+        match string_literal(part.trim()) {
+            Some(text) => {
+                url.push_str(&text);
+                literal = true;
+            }
+            // Base URL held in a variable: the host is unknown
+            None if i == 0 => {}
+            None => url.push_str("{}"),
+        }
+    }
+    if !literal {
+        return None;
+    }
+
+    let url = INTERPOLATION.replace_all(&url, |c: &regex::Captures| {
+        format!("{{{}}}", c.get(1).map_or("", |m| m.as_str().trim()))
+    });
+    // `${API_BASE}/users` or `{base_url}/users`: drop the leading base
+    let url = match url.strip_prefix('{').and_then(|rest| rest.split_once('}')) {
+        Some((_, rest)) if rest.starts_with('/') => rest.to_string(),
+        _ => url.into_owned(),
+    };
+    (url.starts_with('/') || url.starts_with("http://") || url.starts_with("https://")).then_some(url)
+}
+
+/// The text of a quoted string literal, ignoring `f`/`r`/`b` prefixes and a
+/// trailing `.format(...)`, `% args` or conversion call.
+fn string_literal(expr: &str) -> Option<String> {
+    let body = expr.trim_start_matches(['f', 'r', 'b', 'F', 'R', 'B', '@', '$']);
+    if expr.len() - body.len() > 2 {
+        return None;
+    }
+    let quote = body.chars().next().filter(|c| matches!(c, '"' | '\'' | '`'))?;
+    let bytes = body.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b if b == quote as u8 => break,
+            _ => {}
+        }
+        i += 1;
+    }
+    if i >= bytes.len() {
+        return None;
+    }
+    let rest = body[i + 1..].trim_start();
+    if !(rest.is_empty() || rest.starts_with('.') || rest.starts_with('%')) {
+        return None;
+    }
+    Some(body[1..i].to_string())
+}
+
+/// Split a URL template into host (when absolute and literal) and path, without
+/// query string or fragment.
+fn split_url(url: &str) -> (Option<String>, String) {
+    let (host, path) = match url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+        Some(rest) => match rest.find('/') {
+            Some(i) => (Some(&rest[..i]), &rest[i..]),
+            None => (Some(rest), "/"),
+        },
+        None => (None, url),
+    };
+    let path = path.split(['?', '#']).next().unwrap_or("/");
+    let host = host.filter(|h| !h.is_empty() && !h.contains('{')).map(str::to_string);
+    (host, if path.is_empty() { "/".to_string() } else { path.to_string() })
+}
+
+/// Path segments with every parameter (`{id}`, `:id`, `<id>`, `<int:id>`, `*`)
+/// collapsed to `{}`, so client templates and route patterns compare equal.
+pub(crate) fn path_segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let param = (s.starts_with('{') && s.ends_with('}'))
+                || (s.starts_with('<') && s.ends_with('>'))
+                || s.starts_with(':')
+                || s == "*";
+            if param { "{}".to_string() } else { s.to_string() }
+        })
+        .collect()
+}
+
+/// A node for an endpoint that no transport in the scan serves. One node per
+/// method, host and path, wherever it is called from.
+pub fn outbound_endpoint(call: &HttpCall, language: &str, file: &str) -> DetectedComponent {
+    let name = format!("{} {}{}", call.method, call.host.as_deref().unwrap_or(""), call.path);
+    let mut metadata = HashMap::from([
+        ("direction".to_string(), "outbound".to_string()),
+        ("client".to_string(), call.client.clone()),
+        ("url".to_string(), call.url.clone()),
+    ]);
+    if let Some(host) = &call.host {
+        metadata.insert("host".to_string(), host.clone());
+    }
+    DetectedComponent {
+        id: make_id("transport", &format!("outbound:{name}"), ""),
+        name,
+        kind: ComponentKind::Transport,
+        language: language.to_string(),
+        source: SourceLocation {
+            file: file.to_string(),
+            line_start: Some(call.line),
+            line_end: Some(call.line),
+        },
+        metadata,
+        transport_protocol: Some(TransportProtocol::Http),
+        http_method: Some(call.method.clone()),
+        http_path: Some(call.path.clone()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(content: &str, language: &str) -> Vec<HttpCall> {
+        let masked = crate::scanner::lexer::mask(content, language);
+        detect_http_calls(&masked.text, &masked.code, language)
+    }
+
+    fn summary(calls: &[HttpCall]) -> Vec<(String, Option<String>, String)> {
+        calls.iter().map(|c| (c.method.clone(), c.host.clone(), c.path.clone())).collect()
+    }
+
+    #[test]
+    fn detects_javascript_and_python_clients() {
+        let ts = r#"
+export async function loadUser(id: string) {
+  const res = await fetch(`${API_BASE}/api/users/${id}`);
+  await fetch("/api/users", { method: "POST", body: JSON.stringify(user) });
+  await axios.delete("/api/users/" + id);
+  await api.get<User[]>("/api/teams?active=1");
+  await axios({ method: "put", url: "/api/teams/1" });
+  router.get("/health", (req, res) => res.send("ok"));
+  cache.get(key);
+}
+"#;
+        assert_eq!(
+            summary(&detect(ts, "typescript")),
+            vec![
+                ("GET".to_string(), None, "/api/users/{id}".to_string()),
+                ("POST".to_string(), None, "/api/users".to_string()),
+                ("DELETE".to_string(), None, "/api/users/{}".to_string()),
+                ("GET".to_string(), None, "/api/teams".to_string()),
+                ("PUT".to_string(), None, "/api/teams/1".to_string()),
+            ]
+        );
+
+        let py = r#"
+def sync(user_id):
+    requests.post(f"{BASE_URL}/users/{user_id}/sync", json=payload)
+    httpx.get("https://billing.example.com/v1/invoices", params=q)
+    requests.request("PATCH", "/users/%s" % user_id)
+    requests.get(url)
+
+@app.get("/users")
+def list_users():
+    pass
+"#;
+        assert_eq!(
+            summary(&detect(py, "python")),
+            vec![
+                ("POST".to_string(), None, "/users/{user_id}/sync".to_string()),
+                ("GET".to_string(), Some("billing.example.com".to_string()), "/v1/invoices".to_string()),
+                ("PATCH".to_string(), None, "/users/{}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_rust_and_go_clients() {
+        let rs = r#"
+async fn fetch_orders(client: &reqwest::Client, id: u64) {
+    let a = reqwest::get("https://orders.internal/orders").await;
+    let b = client.post(format!("{}/orders/{}/cancel", base, id)).send().await;
+    let c = client.request(Method::DELETE, "http://orders.internal/orders/1").send().await;
+}
+"#;
+        assert_eq!(
+            summary(&detect(rs, "rust")),
+            vec![
+                ("GET".to_string(), Some("orders.internal".to_string()), "/orders".to_string()),
+                ("POST".to_string(), None, "/orders/{}/cancel".to_string()),
+                ("DELETE".to_string(), Some("orders.internal".to_string()), "/orders/1".to_string()),
+            ]
+        );
+
+        let go = r#"
+func notify(ctx context.Context, id string) {
+	resp, err := http.Get("http://users:8080/users")
+	req, _ := http.NewRequestWithContext(ctx, http.MethodPut, fmt.Sprintf("%s/users/%s", base, id), body)
+	req2, _ := http.NewRequest("DELETE", baseURL+"/users/"+id, nil)
+}
+"#;
+        assert_eq!(
+            summary(&detect(go, "go")),
+            vec![
+                ("GET".to_string(), Some("users:8080".to_string()), "/users".to_string()),
+                ("PUT".to_string(), None, "/users/{}".to_string()),
+                ("DELETE".to_string(), None, "/users/{}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_calls_inside_string_literals() {
+        let py = "def usage():\n    print(\"try requests.post('/users', json=body)\")\n";
+        assert!(detect(py, "python").is_empty());
+    }
+
+    #[test]
+    fn path_segments_normalize_parameters() {
+        assert_eq!(path_segments("/users/{id}/"), path_segments("/users/:id"));
+        assert_eq!(path_segments("/users/<int:id>"), path_segments("/users/{}"));
+        assert_ne!(path_segments("/users/me"), path_segments("/users/{id}"));
+    }
+}
@@ -14,6 +14,8 @@ pub fn detect_language(path: &Path) -> Option<&'static str> {
         "rb" => Some("ruby"),
         "proto" => Some("protobuf"),
         "graphql" | "gql" => Some("graphql"),
+        "prisma" => Some("prisma"),
+        "sql" => Some("sql"),
         _ => None,
     }
 }
@@ -0,0 +1,335 @@
+use std::ops::Range;
+
+// ---------------------------------------------------------------------------
+// Lexer pre-pass — locate comments and string literals so detectors can skip them
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    Comment,
+    String,
+}
+
+/// A comment, or the contents of a string literal (delimiters excluded).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub kind: RegionKind,
+    pub range: Range<usize>,
+    /// Triple-quoted or spanning several lines: docstrings, templates, embedded documents
+    pub block: bool,
+}
+
+/// Two masked views of a file. Both keep every byte offset and line number of
+/// the original, so matches map straight back to source locations.
+#[derive(Debug, Clone)]
+pub struct MaskedSource {
+    /// Comments and block strings blanked; single-line string contents kept
+    /// because detectors read route paths, decorator arguments and struct tags.
+    pub text: String,
+    /// Comments and every string literal's contents blanked — only code remains.
+    pub code: String,
+}
+
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    /// `"""` (and `'''` for Python) strings
+    triple_quotes: bool,
+    /// `'x'` is a string rather than a char literal
+    single_quote_strings: bool,
+    /// Backtick template literals (JS/TS) or raw strings (Go)
+    backticks: bool,
+    /// Double-quoted strings may span lines
+    multiline_strings: bool,
+}
+
+fn syntax_for(language: &str) -> Syntax {
+    let c_like = Syntax {
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        triple_quotes: false,
+        single_quote_strings: false,
+        backticks: false,
+        multiline_strings: false,
+    };
+    match language {
+        "python" => Syntax {
+            line_comments: &["#"],
+            block_comment: None,
+            triple_quotes: true,
+            single_quote_strings: true,
+            ..c_like
+        },
+        "ruby" => Syntax {
+            line_comments: &["#"],
+            block_comment: Some(("=begin", "=end")),
+            single_quote_strings: true,
+            multiline_strings: true,
+            ..c_like
+        },
+        "graphql" => Syntax {
+            line_comments: &["#"],
+            block_comment: None,
+            triple_quotes: true,
+            ..c_like
+        },
+        "typescript" | "javascript" => Syntax {
+            single_quote_strings: true,
+            backticks: true,
+            ..c_like
+        },
+        "go" => Syntax { backticks: true, ..c_like },
+        "sql" => Syntax {
+            line_comments: &["--"],
+            single_quote_strings: true,
+            multiline_strings: true,
+            ..c_like
+        },
+        "rust" => Syntax { multiline_strings: true, ..c_like },
+        "java" | "kotlin" | "csharp" => Syntax { triple_quotes: true, ..c_like },
+        _ => c_like,
+    }
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Scan a string body starting at `from` for the closing `quote`, honouring
+/// backslash escapes. Stops at a newline unless `multiline`. Returns the end of
+/// the contents and the index to resume lexing at.
+fn close_quote(bytes: &[u8], from: usize, quote: &[u8], escapes: bool, multiline: bool) -> (usize, usize) {
+    let mut i = from;
+    while i < bytes.len() {
+        if escapes && bytes[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if !multiline && bytes[i] == b'\n' {
+            return (i, i);
+        }
+        if bytes[i..].starts_with(quote) {
+            return (i, i + quote.len());
+        }
+        i += 1;
+    }
+    (bytes.len(), bytes.len())
+}
+
+/// Find comment and string regions in `content`.
+pub fn lex(content: &str, language: &str) -> Vec<Region> {
+    let syntax = syntax_for(language);
+    let bytes = content.as_bytes();
+    let mut regions = Vec::new();
+    let mut i = 0;
+
+    let push_string = |regions: &mut Vec<Region>, open: usize, end: usize, triple: bool| {
+        let body = open..end.min(content.len());
+        let block = triple || content[body.clone()].contains('\n');
+        regions.push(Region { kind: RegionKind::String, range: body, block });
+    };
+
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        let at_line_start = i == 0 || bytes[i - 1] == b'\n';
+
+        if let Some(token) = syntax.line_comments.iter().find(|t| rest.starts_with(t.as_bytes())) {
+            // A leading shebang is not a comment
+            let end = content[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            if !(token.starts_with('#') && rest.starts_with(b"#!") && i == 0) {
+                regions.push(Region { kind: RegionKind::Comment, range: i..end, block: false });
+            }
+            i = end;
+            continue;
+        }
+
+        if let Some((open, close)) = syntax.block_comment
+            && rest.starts_with(open.as_bytes())
+            && (!open.starts_with('=') || at_line_start)
+        {
+            let end = if language == "rust" {
+                // Rust block comments nest
+                let mut depth = 0;
+                let mut j = i;
+                loop {
+                    if j >= bytes.len() {
+                        break bytes.len();
+                    }
+                    if bytes[j..].starts_with(b"/*") {
+                        depth += 1;
+                        j += 2;
+                    } else if bytes[j..].starts_with(b"*/") {
+                        depth -= 1;
+                        j += 2;
+                        if depth == 0 {
+                            break j;
+                        }
+                    } else {
+                        j += 1;
+                    }
+                }
+            } else {
+                content[i + open.len()..]
+                    .find(close)
+                    .map_or(bytes.len(), |n| i + open.len() + n + close.len())
+            };
+            regions.push(Region { kind: RegionKind::Comment, range: i..end, block: true });
+            i = end;
+            continue;
+        }
+
+        // Rust raw strings: r"..", r#".."#, br".."
+        if language == "rust" && (rest.starts_with(b"r\"") || rest.starts_with(b"r#")) && (i == 0 || !is_ident_byte(bytes[i - 1]) || bytes[i - 1] == b'b') {
+            let hashes = rest[1..].iter().take_while(|&&b| b == b'#').count();
+            if rest.get(1 + hashes) == Some(&b'"') {
+                let open = i + 2 + hashes;
+                let mut closing = b"\"".to_vec();
+                closing.extend(std::iter::repeat_n(b'#', hashes));
+                let (end, resume) = close_quote(bytes, open, &closing, false, true);
+                push_string(&mut regions, open, end, false);
+                i = resume;
+                continue;
+            }
+        }
+
+        let b = bytes[i];
+        if syntax.triple_quotes
+            && (rest.starts_with(b"\"\"\"") || (language == "python" && rest.starts_with(b"'''")))
+        {
+            let quote = &rest[..3];
+            let (end, resume) = close_quote(bytes, i + 3, quote, language == "python", true);
+            push_string(&mut regions, i + 3, end, true);
+            i = resume;
+            continue;
+        }
+        match b {
+            b'"' => {
+                let (end, resume) = close_quote(bytes, i + 1, b"\"", true, syntax.multiline_strings);
+                push_string(&mut regions, i + 1, end, false);
+                i = resume;
+            }
+            b'\'' if syntax.single_quote_strings => {
+                let (end, resume) = close_quote(bytes, i + 1, b"'", true, syntax.multiline_strings);
+                push_string(&mut regions, i + 1, end, false);
+                i = resume;
+            }
+            b'\'' => {
+                // Char literals close within a few bytes; Rust lifetimes (`'a`) never do
+                let (end, resume) = close_quote(bytes, i + 1, b"'", true, false);
+                let body = &content[i + 1..end];
+                let is_char = body.chars().count() == 1 || (body.starts_with('\\') && body.len() <= 10);
+                if resume > end && is_char {
+                    push_string(&mut regions, i + 1, end, false);
+                    i = resume;
+                } else {
+                    i += 1;
+                }
+            }
+            b'`' if syntax.backticks => {
+                let (end, resume) = close_quote(bytes, i + 1, b"`", language != "go", true);
+                push_string(&mut regions, i + 1, end, false);
+                i = resume;
+            }
+            _ => i += 1,
+        }
+    }
+
+    regions
+}
+
+fn blank(bytes: &mut [u8], range: Range<usize>) {
+    for b in &mut bytes[range] {
+        if *b != b'\n' {
+            *b = b' ';
+        }
+    }
+}
+
+/// Run the lexer and build both masked views of `content`.
+pub fn mask(content: &str, language: &str) -> MaskedSource {
+    mask_regions(content, &lex(content, language))
+}
+
+/// Build both masked views of `content` from the regions `lex` found in it.
+pub fn mask_regions(content: &str, regions: &[Region]) -> MaskedSource {
+    let mut text = content.as_bytes().to_vec();
+    let mut code = text.clone();
+    for region in regions {
+        if region.kind == RegionKind::Comment || region.block {
+            blank(&mut text, region.range.clone());
+        }
+        blank(&mut code, region.range.clone());
+    }
+    // Only whole regions are replaced, and always by ASCII spaces, so both stay valid UTF-8
+    MaskedSource {
+        text: String::from_utf8(text).unwrap_or_else(|_| content.to_string()),
+        code: String::from_utf8(code).unwrap_or_else(|_| content.to_string()),
+    }
+}
+
+/// Whether the byte at `offset` of a [`MaskedSource::code`] view is code, not
+/// part of a comment or string literal. Only meaningful at a non-space byte of
+/// the original, such as the start of a match that begins with a literal.
+pub fn is_code(code: &str, offset: usize) -> bool {
+    code.as_bytes().get(offset).is_some_and(|b| !b.is_ascii_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_comments_but_keeps_route_paths() {
+        let src = "// router.get(\"/old\", legacy);\nrouter.get(\"/new\", handler); /* note */\n";
+        let masked = mask(src, "javascript");
+        assert_eq!(masked.text.len(), src.len());
+        assert!(!masked.text.contains("/old"));
+        assert!(masked.text.contains("router.get(\"/new\", handler);"));
+        assert!(!masked.text.contains("note"));
+        assert!(masked.code.contains("router.get(\"    \", handler);"));
+    }
+
+    #[test]
+    fn masks_python_docstrings_and_block_strings() {
+        let src = "\
+def load():
+    \"\"\"Returns a class User(BaseModel): payload.\"\"\"
+    doc = '''
+class Invoice(BaseModel):
+    total: int
+'''
+    return get(\"/users\")  # class Other(BaseModel)
+";
+        let masked = mask(src, "python");
+        assert_eq!(masked.text.lines().count(), src.lines().count());
+        assert!(!masked.text.contains("BaseModel"));
+        assert!(masked.text.contains("get(\"/users\")"));
+        assert!(masked.text.contains("def load():"));
+    }
+
+    #[test]
+    fn rust_lifetimes_raw_strings_and_nested_comments() {
+        let src = "fn f<'a>(x: &'a str) -> char { /* a /* b */ c */ let _ = r#\"// not\"#; 'x' }\n";
+        let masked = mask(src, "rust");
+        assert!(masked.code.contains("fn f<'a>(x: &'a str) -> char {"));
+        assert!(!masked.code.contains(" c */"));
+        assert!(!masked.code.contains("not"));
+        assert!(masked.code.contains("r#\""));
+    }
+
+    #[test]
+    fn multiline_template_literals_are_masked_in_text_view() {
+        let src = "const md = `\n## Usage\nclass Fake extends Base {}\n`;\nconst url = `/users/${id}`;\n";
+        let masked = mask(src, "typescript");
+        assert!(!masked.text.contains("class Fake"));
+        assert!(masked.text.contains("`/users/${id}`"));
+    }
+
+    #[test]
+    fn go_struct_tags_survive_text_view() {
+        let src = "type User struct {\n\tName string `json:\"name\"` // display name\n}\n";
+        let masked = mask(src, "go");
+        assert!(masked.text.contains("`json:\"name\"`"));
+        assert!(!masked.text.contains("display"));
+    }
+}
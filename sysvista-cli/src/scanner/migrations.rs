@@ -0,0 +1,767 @@
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::output::schema::{ComponentKind, DetectedComponent, SourceLocation};
+
+use super::fields::{delimited, split_top_level};
+use super::make_id;
+use super::orm::{Column, apply_column_call, column_fields, insert_table_metadata, line_of, parse_call, unquote};
+use super::spans::definition_end;
+
+// ---------------------------------------------------------------------------
+// Schema from migrations — SQL DDL (plain, Flyway, golang-migrate, Diesel) and
+// Alembic revisions, replayed in order into one table per final schema entry
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct Table {
+    name: String,
+    columns: Vec<Column>,
+    file: String,
+    language: &'static str,
+    line: u32,
+    /// Every migration that created or altered the table, in replay order
+    migrations: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct Schema {
+    tables: Vec<Table>,
+}
+
+impl Schema {
+    fn table_mut(&mut self, name: &str) -> Option<&mut Table> {
+        self.tables.iter_mut().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    fn touch(&mut self, name: &str, file: &str) -> Option<&mut Table> {
+        let table = self.table_mut(name)?;
+        if !table.migrations.iter().any(|m| m == file) {
+            table.migrations.push(file.to_string());
+        }
+        Some(table)
+    }
+
+    fn primary_key_of(&self, name: &str) -> String {
+        self.tables
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+            .and_then(|t| t.columns.iter().find(|c| c.primary_key))
+            .map_or_else(|| "id".to_string(), |c| c.name.clone())
+    }
+
+    fn create(&mut self, name: &str, columns: Vec<Column>, file: &str, language: &'static str, line: u32) {
+        self.tables.retain(|t| !t.name.eq_ignore_ascii_case(name));
+        self.tables.push(Table {
+            name: name.to_string(),
+            columns,
+            file: file.to_string(),
+            language,
+            line,
+            migrations: vec![file.to_string()],
+        });
+    }
+
+    fn drop(&mut self, name: &str) {
+        self.tables.retain(|t| !t.name.eq_ignore_ascii_case(name));
+    }
+}
+
+impl Table {
+    fn column_mut(&mut self, name: &str) -> Option<&mut Column> {
+        self.columns.iter_mut().find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ordering
+// ---------------------------------------------------------------------------
+
+/// Compare paths with embedded numbers numerically, so `V2__` sorts before
+/// `V10__` and `1_1` before `1_10`.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let chunks = |s: &str| -> Vec<(bool, String)> {
+        let mut out: Vec<(bool, String)> = Vec::new();
+        for c in s.chars() {
+            let digit = c.is_ascii_digit();
+            match out.last_mut() {
+                Some((d, chunk)) if *d == digit => chunk.push(c),
+                _ => out.push((digit, c.to_string())),
+            }
+        }
+        out
+    };
+    for (x, y) in chunks(a).iter().zip(chunks(b).iter()) {
+        let ord = match (x, y) {
+            ((true, n), (true, m)) => {
+                let (n, m) = (n.trim_start_matches('0'), m.trim_start_matches('0'));
+                n.len().cmp(&m.len()).then_with(|| n.cmp(m))
+            }
+            _ => x.1.cmp(&y.1),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    chunks(a).len().cmp(&chunks(b).len())
+}
+
+static FLYWAY_VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(?:^|/)V(\d+(?:[._]\d+)*)__[^/]*$").unwrap());
+
+/// Replay order of two SQL migrations: Flyway versions (`V1__` < `V1_1__` < `V2__`)
+/// when both files carry one, otherwise natural path order.
+fn migration_cmp(a: &str, b: &str) -> Ordering {
+    let version = |path: &str| -> Option<Vec<u64>> {
+        let cap = FLYWAY_VERSION.captures(path)?;
+        Some(cap[1].split(['.', '_']).filter_map(|n| n.parse().ok()).collect())
+    };
+    match (version(a), version(b)) {
+        (Some(x), Some(y)) => x.cmp(&y).then_with(|| natural_cmp(a, b)),
+        _ => natural_cmp(a, b),
+    }
+}
+
+/// Down and undo scripts reverse a migration; replaying them would drop the schema.
+fn is_down_migration(path: &str) -> bool {
+    let file = path.rsplit('/').next().unwrap_or(path).to_lowercase();
+    file == "down.sql"
+        || file.ends_with(".down.sql")
+        || file.ends_with("_down.sql")
+        || (file.starts_with('u') && file[1..].starts_with(|c: char| c.is_ascii_digit()) && file.contains("__"))
+}
+
+static ALEMBIC_REVISION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^revision\s*(?::[^=\n]+)?=\s*["'](\w+)["']"#).unwrap()
+});
+
+static ALEMBIC_DOWN_REVISION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^down_revision\s*(?::[^=\n]+)?=\s*(.+)$").unwrap());
+
+static QUOTED_WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"["'](\w+)["']"#).unwrap());
+
+fn is_alembic_revision(content: &str) -> bool {
+    ALEMBIC_REVISION.is_match(content) && content.contains("def upgrade(")
+}
+
+/// Order Alembic revisions by their distance from the base revision, following
+/// `down_revision` (merge revisions wait for their deepest parent).
+fn alembic_order<'a>(revisions: &[(&'a str, &'a str)]) -> Vec<&'a str> {
+    let ids: HashMap<&str, (&str, Vec<String>)> = revisions
+        .iter()
+        .filter_map(|&(file, content)| {
+            let id = ALEMBIC_REVISION.captures(content)?.get(1)?.as_str();
+            let parents = ALEMBIC_DOWN_REVISION
+                .captures(content)
+                .map(|c| QUOTED_WORD.captures_iter(&c[1]).map(|p| p[1].to_string()).collect())
+                .unwrap_or_default();
+            Some((id, (file, parents)))
+        })
+        .collect();
+
+    fn depth(id: &str, ids: &HashMap<&str, (&str, Vec<String>)>, seen: &mut Vec<String>) -> usize {
+        if seen.iter().any(|s| s == id) {
+            return 0;
+        }
+        seen.push(id.to_string());
+        let d = ids.get(id).map_or(0, |(_, parents)| {
+            parents.iter().filter(|p| ids.contains_key(p.as_str())).map(|p| depth(p, ids, seen) + 1).max().unwrap_or(0)
+        });
+        seen.pop();
+        d
+    }
+
+    let mut ordered: Vec<(usize, &str)> = ids
+        .iter()
+        .map(|(id, (file, _))| (depth(id, &ids, &mut Vec::new()), *file))
+        .collect();
+    ordered.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| natural_cmp(a.1, b.1)));
+    ordered.into_iter().map(|(_, file)| file).collect()
+}
+
+// ---------------------------------------------------------------------------
+// SQL DDL
+// ---------------------------------------------------------------------------
+
+const IDENT: &str = r#"((?:[\w$]+|"[^"]+"|`[^`]+`|\[[^\]]+\])(?:\.(?:[\w$]+|"[^"]+"|`[^`]+`|\[[^\]]+\]))*)"#;
+
+static CREATE_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"(?is)^create\s+(?:or\s+replace\s+)?(?:unlogged\s+)?table\s+(?:if\s+not\s+exists\s+)?{IDENT}\s*\(")).unwrap()
+});
+
+static ALTER_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"(?is)^alter\s+table\s+(?:if\s+exists\s+)?(?:only\s+)?{IDENT}\s+(.*)$")).unwrap()
+});
+
+static DROP_TABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)^drop\s+table\s+(?:if\s+exists\s+)?(.+?)(?:\s+(?:cascade|restrict))?$").unwrap());
+
+static RENAME_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"(?is)^rename\s+table\s+{IDENT}\s+to\s+{IDENT}$")).unwrap()
+});
+
+static REFERENCES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"(?is)\breferences\s+{IDENT}\s*(?:\(([^)]*)\))?")).unwrap()
+});
+
+/// Unquote an identifier and drop its schema: `public."Users"` → `Users`
+fn sql_ident(raw: &str) -> String {
+    let last = raw.trim().rsplit('.').next().unwrap_or(raw);
+    last.trim_matches(|c| matches!(c, '"' | '`' | '[' | ']')).to_string()
+}
+
+fn ident_list(raw: &str) -> Vec<String> {
+    raw.split(',').map(sql_ident).filter(|s| !s.is_empty()).collect()
+}
+
+/// Split at whitespace outside parentheses: `numeric(10, 2) not null`
+fn sql_tokens(def: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut depth = 0i32;
+    let mut start = None;
+    for (i, c) in def.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(s) = start.take() {
+                    tokens.push(&def[s..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(s) = start {
+        tokens.push(&def[s..]);
+    }
+    tokens
+}
+
+/// Words that end a column's type and begin its constraints
+const COLUMN_CONSTRAINTS: &[&str] = &[
+    "not", "null", "primary", "references", "default", "unique", "check", "constraint", "collate",
+    "generated", "auto_increment", "autoincrement", "identity", "on", "comment",
+];
+
+/// First words of a table-level constraint inside `CREATE TABLE (...)`
+const TABLE_CONSTRAINTS: &[&str] = &[
+    "constraint", "primary", "foreign", "unique", "check", "key", "index", "exclude", "fulltext", "spatial",
+];
+
+fn references_of(def: &str, schema: &Schema) -> Option<String> {
+    let cap = REFERENCES.captures(def)?;
+    let table = sql_ident(&cap[1]);
+    let column = cap
+        .get(2)
+        .and_then(|c| ident_list(c.as_str()).into_iter().next())
+        .unwrap_or_else(|| schema.primary_key_of(&table));
+    Some(format!("{table}.{column}"))
+}
+
+fn sql_column(def: &str, schema: &Schema) -> Option<Column> {
+    let tokens = sql_tokens(def);
+    let (name, rest) = tokens.split_first()?;
+    let type_len = rest
+        .iter()
+        .position(|t| COLUMN_CONSTRAINTS.contains(&t.to_lowercase().as_str()))
+        .unwrap_or(rest.len());
+    let lower: Vec<String> = rest.iter().map(|t| t.to_lowercase()).collect();
+    let has = |a: &str, b: &str| lower.windows(2).any(|w| w[0] == a && w[1] == b);
+    let primary_key = has("primary", "key");
+    let default = lower.iter().position(|t| t == "default").and_then(|i| rest.get(i + 1)).map(|d| d.to_string());
+    Some(Column {
+        name: sql_ident(name),
+        type_name: (type_len > 0).then(|| rest[..type_len].join(" ")),
+        primary_key,
+        foreign_key: references_of(def, schema),
+        optional: !primary_key && !has("not", "null"),
+        default,
+    })
+}
+
+/// Apply a table-level constraint (`PRIMARY KEY (a, b)`, `FOREIGN KEY (a) REFERENCES t (c)`).
+fn apply_table_constraint(columns: &mut [Column], def: &str, schema: &Schema) {
+    let lower = def.to_lowercase();
+    let keys = || delimited(def, 0, b'(', b')').map(ident_list).unwrap_or_default();
+    if lower.contains("primary key") && !lower.contains("references") {
+        for key in keys() {
+            if let Some(c) = columns.iter_mut().find(|c| c.name.eq_ignore_ascii_case(&key)) {
+                c.primary_key = true;
+                c.optional = false;
+            }
+        }
+    } else if lower.contains("foreign key")
+        && let Some(cap) = REFERENCES.captures(def)
+    {
+        let table = sql_ident(&cap[1]);
+        let remote = cap.get(2).map(|c| ident_list(c.as_str())).unwrap_or_default();
+        for (i, key) in keys().iter().enumerate() {
+            let column = remote.get(i).cloned().unwrap_or_else(|| schema.primary_key_of(&table));
+            if let Some(c) = columns.iter_mut().find(|c| c.name.eq_ignore_ascii_case(key)) {
+                c.foreign_key = Some(format!("{table}.{column}"));
+            }
+        }
+    }
+}
+
+fn is_table_constraint(def: &str) -> bool {
+    let first = def.split_whitespace().next().unwrap_or("").to_lowercase();
+    TABLE_CONSTRAINTS.contains(&first.as_str())
+}
+
+/// Strip leading keywords (case-insensitive) from `s`, in order, when present.
+fn strip_words<'a>(mut s: &'a str, words: &[&str]) -> &'a str {
+    for word in words {
+        let trimmed = s.trim_start();
+        if trimmed.len() > word.len()
+            && trimmed[..word.len()].eq_ignore_ascii_case(word)
+            && trimmed[word.len()..].starts_with(char::is_whitespace)
+        {
+            s = &trimmed[word.len()..];
+        }
+    }
+    s.trim()
+}
+
+fn alter_action(schema: &mut Schema, table: &str, action: &str, file: &str) {
+    let tokens = sql_tokens(action);
+    let lower: Vec<String> = tokens.iter().map(|t| t.to_lowercase()).collect();
+    let Some(verb) = lower.first().map(String::as_str) else {
+        return;
+    };
+    let rest = action.trim_start()[tokens[0].len()..].trim();
+
+    match verb {
+        "add" => {
+            let def = strip_words(rest, &["column", "if", "not", "exists"]);
+            if is_table_constraint(def) {
+                let Some(t) = schema.touch(table, file) else {
+                    return;
+                };
+                let mut columns = std::mem::take(&mut t.columns);
+                apply_table_constraint(&mut columns, def, schema);
+                if let Some(t) = schema.table_mut(table) {
+                    t.columns = columns;
+                }
+            } else if let Some(column) = sql_column(def, schema)
+                && let Some(t) = schema.touch(table, file)
+            {
+                t.columns.retain(|c| !c.name.eq_ignore_ascii_case(&column.name));
+                t.columns.push(column);
+            }
+        }
+        "drop" => {
+            let Some(t) = schema.touch(table, file) else {
+                return;
+            };
+            if lower.get(1).is_some_and(|w| w == "primary") {
+                t.columns.iter_mut().for_each(|c| c.primary_key = false);
+            } else if !matches!(lower.get(1).map(String::as_str), Some("constraint" | "index" | "key" | "foreign")) {
+                let name = sql_ident(strip_words(rest, &["column", "if", "exists"]).split_whitespace().next().unwrap_or(""));
+                t.columns.retain(|c| !c.name.eq_ignore_ascii_case(&name));
+            }
+        }
+        "rename" => {
+            let words = strip_words(rest, &["column"]);
+            let parts: Vec<&str> = words.split_whitespace().collect();
+            match parts.as_slice() {
+                [to, new] if to.eq_ignore_ascii_case("to") => {
+                    if let Some(t) = schema.touch(table, file) {
+                        t.name = sql_ident(new);
+                    }
+                }
+                [old, to, new] if to.eq_ignore_ascii_case("to") => {
+                    if let Some(t) = schema.touch(table, file)
+                        && let Some(c) = t.column_mut(&sql_ident(old))
+                    {
+                        c.name = sql_ident(new);
+                    }
+                }
+                _ => {}
+            }
+        }
+        "alter" | "modify" | "change" => {
+            let def = strip_words(rest, &["column"]);
+            let replacement = match verb {
+                "modify" => sql_column(def, schema).map(|c| (c.name.clone(), c)),
+                // CHANGE old_name new_definition
+                "change" => {
+                    let (old, new) = def.split_once(char::is_whitespace).unwrap_or((def, ""));
+                    sql_column(new, schema).map(|c| (sql_ident(old), c))
+                }
+                _ => None,
+            };
+            let Some(t) = schema.touch(table, file) else {
+                return;
+            };
+            if let Some((old, column)) = replacement {
+                if let Some(c) = t.column_mut(&old) {
+                    *c = column;
+                }
+                return;
+            }
+            let parts = sql_tokens(def);
+            let Some(c) = parts.first().and_then(|name| t.column_mut(&sql_ident(name))) else {
+                return;
+            };
+            let op: Vec<String> = parts[1..].iter().map(|p| p.to_lowercase()).collect();
+            match op.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+                ["type", ..] => c.type_name = Some(parts[2..].join(" ")).filter(|t| !t.is_empty()),
+                ["set", "data", "type", ..] => c.type_name = Some(parts[4..].join(" ")),
+                ["set", "not", "null"] => c.optional = false,
+                ["drop", "not", "null"] => c.optional = true,
+                ["set", "default", ..] => c.default = parts.get(3).map(|d| d.to_string()),
+                ["drop", "default"] => c.default = None,
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+fn replay_sql(schema: &mut Schema, content: &str, file: &str) {
+    let base = content.as_ptr() as usize;
+    for statement in split_top_level(content, b";", false, false) {
+        let offset = statement.as_ptr() as usize - base + (statement.len() - statement.trim_start().len());
+        let statement = statement.trim();
+
+        if let Some(cap) = CREATE_TABLE.captures(statement) {
+            let name = sql_ident(&cap[1]);
+            let Some(body) = delimited(statement, cap.get(0).unwrap().end() - 1, b'(', b')') else {
+                continue;
+            };
+            let defs = split_top_level(body, b",", false, false);
+            let mut columns: Vec<Column> = defs
+                .iter()
+                .map(|d| d.trim())
+                .filter(|d| !d.is_empty() && !is_table_constraint(d))
+                .filter_map(|d| sql_column(d, schema))
+                .collect();
+            for def in defs.iter().map(|d| d.trim()).filter(|d| is_table_constraint(d)) {
+                apply_table_constraint(&mut columns, def, schema);
+            }
+            schema.create(&name, columns, file, "sql", line_of(content, offset));
+        } else if let Some(cap) = ALTER_TABLE.captures(statement) {
+            let table = sql_ident(&cap[1]);
+            for action in split_top_level(&cap[2], b",", false, false) {
+                alter_action(schema, &table, action.trim(), file);
+            }
+        } else if let Some(cap) = RENAME_TABLE.captures(statement) {
+            let new = sql_ident(&cap[2]);
+            if let Some(t) = schema.touch(&sql_ident(&cap[1]), file) {
+                t.name = new;
+            }
+        } else if let Some(cap) = DROP_TABLE.captures(statement) {
+            for name in ident_list(&cap[1]) {
+                schema.drop(&name);
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Alembic operations
+// ---------------------------------------------------------------------------
+
+static ALEMBIC_OP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bop\.(\w+)\s*\(").unwrap());
+
+static UPGRADE_FN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^def\s+upgrade\s*\(").unwrap());
+
+fn string_list(arg: &str) -> Vec<String> {
+    arg.trim()
+        .trim_start_matches(['[', '('])
+        .trim_end_matches([']', ')'])
+        .split(',')
+        .map(|s| unquote(s).to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn alembic_column(arg: &str) -> Option<Column> {
+    let call = parse_call(arg).filter(|c| c.callee.ends_with("Column"))?;
+    let mut column = Column::default();
+    apply_column_call(&mut column, &call);
+    if !call.keywords.contains_key("nullable") {
+        column.optional = !column.primary_key;
+    }
+    Some(column)
+}
+
+fn replay_alembic(schema: &mut Schema, content: &str, file: &str) {
+    // Only `upgrade()` moves the schema forward; `downgrade()` undoes it
+    let Some(upgrade) = UPGRADE_FN.find(content) else {
+        return;
+    };
+    let start_line = line_of(content, upgrade.start());
+    let end_line = definition_end(content, "python", start_line).unwrap_or(u32::MAX);
+
+    for op in ALEMBIC_OP.captures_iter(content) {
+        let at = op.get(0).unwrap().start();
+        let line = line_of(content, at);
+        if line < start_line || line > end_line {
+            continue;
+        }
+        let Some(call) = parse_call(&content[at..]) else {
+            continue;
+        };
+        let arg = |i: usize| call.positional.get(i).map(|a| unquote(a).to_string());
+        let keyword = |k: &str| call.keywords.get(k).map(|a| unquote(a).to_string());
+
+        match &op[1] {
+            "create_table" => {
+                let Some(name) = arg(0) else {
+                    continue;
+                };
+                let mut columns: Vec<Column> = call.positional[1..].iter().filter_map(|a| alembic_column(a)).collect();
+                for constraint in call.positional[1..].iter().filter_map(|a| parse_call(a)) {
+                    if constraint.callee.ends_with("PrimaryKeyConstraint") {
+                        for key in constraint.positional.iter().map(|k| unquote(k)) {
+                            if let Some(c) = columns.iter_mut().find(|c| c.name == key) {
+                                c.primary_key = true;
+                                c.optional = false;
+                            }
+                        }
+                    } else if constraint.callee.ends_with("ForeignKeyConstraint")
+                        && let [local, remote, ..] = constraint.positional.as_slice()
+                    {
+                        for (key, target) in string_list(local).iter().zip(string_list(remote)) {
+                            if let Some(c) = columns.iter_mut().find(|c| &c.name == key) {
+                                c.foreign_key = Some(target);
+                            }
+                        }
+                    }
+                }
+                schema.create(&name, columns, file, "python", line);
+            }
+            "add_column" => {
+                if let (Some(table), Some(column)) = (arg(0), call.positional.get(1).and_then(|a| alembic_column(a)))
+                    && let Some(t) = schema.touch(&table, file)
+                {
+                    t.columns.retain(|c| c.name != column.name);
+                    t.columns.push(column);
+                }
+            }
+            "drop_column" => {
+                if let (Some(table), Some(name)) = (arg(0), arg(1))
+                    && let Some(t) = schema.touch(&table, file)
+                {
+                    t.columns.retain(|c| c.name != name);
+                }
+            }
+            "alter_column" => {
+                let (Some(table), Some(name)) = (arg(0), arg(1)) else {
+                    continue;
+                };
+                if let Some(t) = schema.touch(&table, file)
+                    && let Some(c) = t.column_mut(&name)
+                {
+                    if let Some(new_name) = keyword("new_column_name") {
+                        c.name = new_name;
+                    }
+                    if let Some(nullable) = keyword("nullable") {
+                        c.optional = nullable == "True";
+                    }
+                    if let Some(ty) = keyword("type_") {
+                        c.type_name = Some(ty);
+                    }
+                    if let Some(default) = call.keywords.get("server_default") {
+                        c.default = (*default != "None").then(|| default.to_string());
+                    }
+                }
+            }
+            "create_foreign_key" => {
+                if let (Some(source), Some(referent), Some(local), Some(remote)) =
+                    (arg(1), arg(2), call.positional.get(3), call.positional.get(4))
+                    && let Some(t) = schema.touch(&source, file)
+                {
+                    for (key, column) in string_list(local).iter().zip(string_list(remote)) {
+                        if let Some(c) = t.column_mut(key) {
+                            c.foreign_key = Some(format!("{referent}.{column}"));
+                        }
+                    }
+                }
+            }
+            "create_primary_key" => {
+                if let (Some(table), Some(keys)) = (arg(1), call.positional.get(2))
+                    && let Some(t) = schema.touch(&table, file)
+                {
+                    for key in string_list(keys) {
+                        if let Some(c) = t.column_mut(&key) {
+                            c.primary_key = true;
+                            c.optional = false;
+                        }
+                    }
+                }
+            }
+            "rename_table" => {
+                if let (Some(old), Some(new)) = (arg(0), arg(1))
+                    && let Some(t) = schema.touch(&old, file)
+                {
+                    t.name = new;
+                }
+            }
+            "drop_table" => {
+                if let Some(name) = arg(0) {
+                    schema.drop(&name);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Entry point
+// ---------------------------------------------------------------------------
+
+/// Replay every SQL and Alembic migration in order and return one model
+/// component per table in the resulting schema. SQL files replay first, by
+/// natural path order; Alembic revisions follow their `down_revision` chain.
+pub fn replay_migrations(file_contents: &HashMap<String, String>) -> Vec<DetectedComponent> {
+    let mut sql_files: Vec<&str> = file_contents
+        .keys()
+        .map(String::as_str)
+        .filter(|f| f.to_lowercase().ends_with(".sql") && !is_down_migration(f))
+        .collect();
+    sql_files.sort_by(|a, b| migration_cmp(a, b));
+
+    let revisions: Vec<(&str, &str)> = file_contents
+        .iter()
+        .filter(|(f, content)| f.ends_with(".py") && is_alembic_revision(content))
+        .map(|(f, content)| (f.as_str(), content.as_str()))
+        .collect();
+
+    let mut schema = Schema::default();
+    for file in sql_files {
+        replay_sql(&mut schema, &file_contents[file], file);
+    }
+    for file in alembic_order(&revisions) {
+        replay_alembic(&mut schema, &file_contents[file], file);
+    }
+
+    schema
+        .tables
+        .into_iter()
+        .map(|table| {
+            let mut metadata = HashMap::new();
+            insert_table_metadata(&mut metadata, &table.name, &table.columns);
+            metadata.insert("migrations".to_string(), table.migrations.join(","));
+            let line_end = file_contents
+                .get(&table.file)
+                .and_then(|content| definition_end(content, table.language, table.line));
+            DetectedComponent {
+                id: make_id("model", &table.name, &table.file),
+                name: table.name.clone(),
+                kind: ComponentKind::Model,
+                language: table.language.to_string(),
+                source: SourceLocation {
+                    file: table.file.clone(),
+                    line_start: Some(table.line),
+                    line_end,
+                },
+                metadata,
+                model_fields: (!table.columns.is_empty()).then(|| column_fields(&table.columns)),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay(files: &[(&str, &str)]) -> Vec<DetectedComponent> {
+        let contents = files.iter().map(|(f, c)| (f.to_string(), c.to_string())).collect();
+        replay_migrations(&contents)
+    }
+
+    fn meta<'a>(comp: &'a DetectedComponent, key: &str) -> Option<&'a str> {
+        comp.metadata.get(key).map(String::as_str)
+    }
+
+    #[test]
+    fn natural_order_of_versioned_files() {
+        let mut files = vec!["db/V10__c.sql", "db/V2__b.sql", "db/V1_1__a.sql", "db/V1__init.sql"];
+        files.sort_by(|a, b| migration_cmp(a, b));
+        assert_eq!(files, vec!["db/V1__init.sql", "db/V1_1__a.sql", "db/V2__b.sql", "db/V10__c.sql"]);
+        assert!(is_down_migration("migrations/20240101_users/down.sql"));
+        assert!(is_down_migration("db/U2__undo.sql"));
+        assert!(!is_down_migration("db/V2__users.sql"));
+    }
+
+    #[test]
+    fn replays_sql_migrations_in_order() {
+        let comps = replay(&[
+            (
+                "db/V2__orders.sql",
+                "CREATE TABLE orders (\n  id bigserial PRIMARY KEY,\n  user_id bigint NOT NULL REFERENCES users,\n  total numeric(10, 2) DEFAULT 0\n);\nALTER TABLE users ADD COLUMN nickname text, DROP COLUMN legacy;\n",
+            ),
+            (
+                "db/V1__init.sql",
+                "CREATE TABLE IF NOT EXISTS public.\"users\" (\n  uid serial,\n  email varchar(255) NOT NULL,\n  legacy int,\n  CONSTRAINT users_pk PRIMARY KEY (uid)\n);\nCREATE TABLE scratch (x int);\n",
+            ),
+            ("db/V3__cleanup.sql", "DROP TABLE IF EXISTS scratch;\nALTER TABLE orders RENAME COLUMN total TO amount;\n"),
+        ]);
+        let names: Vec<&str> = comps.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["users", "orders"]);
+        assert_eq!(meta(&comps[0], "columns"), Some("uid,email,nickname"));
+        assert_eq!(meta(&comps[0], "primary_key"), Some("uid"));
+        assert_eq!(meta(&comps[0], "migrations"), Some("db/V1__init.sql,db/V2__orders.sql"));
+        assert_eq!(comps[0].source.line_start, Some(1));
+        assert_eq!(comps[0].source.line_end, Some(6));
+        assert_eq!(meta(&comps[1], "columns"), Some("id,user_id,amount"));
+        assert_eq!(meta(&comps[1], "foreign_keys"), Some("user_id->users.uid"));
+        let fields = comps[1].model_fields.as_ref().unwrap();
+        assert_eq!(fields[2].type_name.as_deref(), Some("numeric(10, 2)"));
+        assert_eq!(fields[2].default.as_deref(), Some("0"));
+        assert!(!fields[1].optional);
+    }
+
+    #[test]
+    fn alembic_revisions_follow_down_revision_chain() {
+        let first = r#"
+revision = "a1"
+down_revision = None
+
+def upgrade():
+    op.create_table(
+        "accounts",
+        sa.Column("id", sa.Integer(), nullable=False),
+        sa.Column("name", sa.String(50)),
+        sa.PrimaryKeyConstraint("id"),
+    )
+
+def downgrade():
+    op.drop_table("accounts")
+"#;
+        let second = r#"
+revision = "b2"
+down_revision = "a1"
+
+def upgrade():
+    op.create_table(
+        "invoices",
+        sa.Column("id", sa.Integer(), primary_key=True),
+        sa.Column("account_id", sa.Integer(), sa.ForeignKey("accounts.id")),
+    )
+    op.alter_column("accounts", "name", new_column_name="display_name", nullable=False)
+
+def downgrade():
+    op.drop_table("invoices")
+"#;
+        // File names sort the wrong way round; the revision chain decides
+        let comps = replay(&[("alembic/versions/zz_first.py", first), ("alembic/versions/aa_second.py", second)]);
+        let names: Vec<&str> = comps.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["accounts", "invoices"]);
+        assert_eq!(meta(&comps[0], "columns"), Some("id,display_name"));
+        assert_eq!(meta(&comps[0], "primary_key"), Some("id"));
+        assert!(!comps[0].model_fields.as_ref().unwrap()[1].optional);
+        assert_eq!(meta(&comps[1], "foreign_keys"), Some("account_id->accounts.id"));
+        assert_eq!(comps[1].language, "python");
+    }
+}
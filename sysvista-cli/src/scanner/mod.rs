@@ -1,19 +1,36 @@
+pub mod confidence;
+pub mod config;
+pub mod datastores;
+pub mod fields;
 pub mod file_walker;
+pub mod grpc_clients;
+pub mod history;
+pub mod http_calls;
 pub mod language;
+pub mod lexer;
+pub mod migrations;
 pub mod models;
+pub mod modules;
+pub mod orm;
+pub mod packages;
 pub mod prompts;
+pub mod queues;
 pub mod relationships;
 pub mod services;
+pub mod spans;
+pub mod syntax;
 pub mod transforms;
 pub mod transports;
 pub mod workflows;
+pub mod workspace;
 
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::time::Instant;
 
-use crate::output::schema::{DetectedComponent, ScanStats, SysVistaOutput};
+use crate::analysis;
+use crate::output::schema::{Analysis, DetectedComponent, ScanStats, SysVistaOutput};
 
 /// Create a deterministic ID from kind + name + file
 pub fn make_id(kind: &str, name: &str, file: &str) -> String {
@@ -31,6 +48,7 @@ pub fn scan(root: &Path) -> SysVistaOutput {
     let mut all_components: Vec<DetectedComponent> = Vec::new();
     let mut languages_seen: HashSet<String> = HashSet::new();
     let mut file_contents: HashMap<String, String> = HashMap::new();
+    let mut code_contents: HashMap<String, String> = HashMap::new();
     let mut files_scanned: u64 = 0;
 
     for walked in &files {
@@ -46,49 +64,129 @@ pub fn scan(root: &Path) -> SysVistaOutput {
 
         files_scanned += 1;
         languages_seen.insert(lang.to_string());
+
+        // Parse with tree-sitter where a grammar exists; other languages use the regex detectors
+        let syntax = syntax::parse(&content, lang, &walked.relative_path);
+
+        // Detectors see comments and block strings blanked out, so commented-out code
+        // and docstrings never produce components. Offsets and lines are unchanged.
+        let masked = lexer::mask(&content, lang);
+        let content = masked.text;
+        let code = masked.code;
         file_contents.insert(walked.relative_path.clone(), content.clone());
+        code_contents.insert(walked.relative_path.clone(), code.clone());
 
-        // Detect components
+        // Detect components. Models, services, transforms and Python routes come
+        // from the parse tree where there is one; other languages use the regexes.
         let mut components = Vec::new();
-        components.extend(models::detect_models(&content, lang, &walked.relative_path));
-        components.extend(services::detect_services(
+        match &syntax {
+            Some(index) => {
+                components.extend(models::detect_models_from_syntax(
+                    &content,
+                    lang,
+                    &walked.relative_path,
+                    index,
+                ));
+                components.extend(services::detect_services_from_syntax(lang, &walked.relative_path, index));
+                components.extend(transforms::detect_transforms_from_syntax(&content, lang, &walked.relative_path, index));
+            }
+            None => {
+                components.extend(models::detect_models(&content, lang, &walked.relative_path));
+                components.extend(services::detect_services(&content, &code, lang, &walked.relative_path));
+                components.extend(transforms::detect_transforms(&content, &code, lang, &walked.relative_path));
+            }
+        }
+        components.extend(transports::detect_transports(
             &content,
+            &code,
             lang,
             &walked.relative_path,
+            syntax.as_ref(),
         ));
-        components.extend(transports::detect_transports(
+        components.extend(prompts::detect_prompts(
             &content,
+            &code,
             lang,
             &walked.relative_path,
         ));
-        components.extend(transforms::detect_transforms(
+        components.extend(queues::detect_queue_consumers(
             &content,
+            &code,
             lang,
             &walked.relative_path,
         ));
-        components.extend(prompts::detect_prompts(
+        components.extend(datastores::detect_datastores(
             &content,
+            &code,
             lang,
             &walked.relative_path,
         ));
 
+        // Exact definition spans for the components still detected by regex
+        if let Some(index) = &syntax {
+            syntax::annotate_spans(&mut components, index);
+        }
+
+        // Table, column and key metadata for ORM-mapped models
+        orm::annotate_orm_models(&mut components, &content, lang, &walked.relative_path);
+
         all_components.extend(components);
     }
 
+    // Apply FastAPI include_router prefixes so routes carry their externally visible path
+    transports::resolve_router_mounts(&mut all_components, &file_contents, &code_contents);
+
+    // Tables from SQL and Alembic migrations, replayed in order into the final schema
+    all_components.extend(migrations::replay_migrations(&file_contents));
+
+    // One datastore node per database, cache or bucket, whichever files connect to it
+    datastores::merge_datastores(&mut all_components);
+
+    // Django foreign keys to models that set their own `db_table`
+    orm::resolve_django_tables(&mut all_components);
+
     // Deduplicate components by ID (multiple patterns can match the same definition)
     let mut seen_ids = HashSet::new();
     all_components.retain(|c| seen_ids.insert(c.id.clone()));
 
+    // Workspace packages, so imports can resolve by package name before file stem
+    let packages = packages::detect_packages(&files);
+    packages::assign_packages(&mut all_components, &packages);
+
     // Infer edges
-    let mut edges = relationships::infer_edges(&all_components, &file_contents);
+    let module_index = modules::ModuleIndex::build(&files, &file_contents);
+    let mut edges = relationships::infer_edges(&all_components, &file_contents, &code_contents, &module_index);
+
+    // Model structure edges (contains, contains_many, extends, foreign_key, maps_to)
+    edges.extend(relationships::infer_model_edges(&all_components));
+    edges.extend(relationships::infer_foreign_key_edges(&all_components));
+    edges.extend(relationships::infer_table_mapping_edges(&all_components));
+
+    // Flow edges (handles, persists, transforms, consumes, produces)
+    edges.extend(relationships::infer_flow_edges(&all_components, &file_contents, &code_contents));
 
-    // Merge flow edges (handles, persists, transforms, consumes, produces).
-    // These carry semantic meaning for the flow view even when an import/reference
-    // edge already exists for the same pair.
-    edges.extend(relationships::infer_flow_edges(&all_components, &file_contents));
+    // Call/dispatch edges
+    edges.extend(relationships::infer_call_edges(&all_components, &file_contents, &code_contents, &module_index));
 
-    // Merge call/dispatch edges.
-    edges.extend(relationships::infer_call_edges(&all_components, &file_contents));
+    // Reads and writes from services and handlers to the datastores they use
+    edges.extend(relationships::infer_datastore_edges(&all_components, &file_contents, &code_contents));
+
+    // Outbound HTTP calls, queue publishes and gRPC stubs, linked to the transport
+    // that serves them or to an outbound endpoint node
+    let (endpoints, outbound_edges) =
+        relationships::infer_outbound_edges(&all_components, &file_contents, &code_contents);
+    all_components.extend(endpoints);
+    edges.extend(outbound_edges);
+
+    // Entry points named in `sysvista.toml`; the CLI reports an invalid config
+    // before scanning
+    config::mark_entry_points(&mut all_components, &config::load(root).unwrap_or_default());
+
+    // Confidence of each component and edge, from the path that detected it
+    confidence::score(&mut all_components, &mut edges);
+
+    // One edge per pair of components, carrying every relation between them
+    let edges = relationships::merge_relations(edges);
 
     // Infer workflows from components and edges
     let workflows = workflows::infer_workflows(&all_components, &edges);
@@ -104,7 +202,7 @@ pub fn scan(root: &Path) -> SysVistaOutput {
     let mut detected_languages: Vec<String> = languages_seen.into_iter().collect();
     detected_languages.sort();
 
-    SysVistaOutput {
+    let mut output = SysVistaOutput {
         version: "1".to_string(),
         scanned_at: chrono::Utc::now().to_rfc3339(),
         root_dir: root.to_string_lossy().to_string(),
@@ -118,5 +216,10 @@ pub fn scan(root: &Path) -> SysVistaOutput {
             files_skipped,
             scan_duration_ms: duration.as_millis() as u64,
         },
-    }
+        packages,
+        repositories: Vec::new(),
+        analysis: Analysis::default(),
+    };
+    analysis::analyze(&mut output);
+    output
 }
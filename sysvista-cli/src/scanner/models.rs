@@ -2,9 +2,12 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::sync::LazyLock;
 
-use crate::output::schema::{ComponentKind, DetectedComponent, SourceLocation};
+use crate::output::schema::{ComponentKind, DetectedComponent, ModelField, SourceLocation};
 
+use super::fields::{extract_bases, extract_fields, is_data_class};
 use super::make_id;
+use super::spans::definition_end;
+use super::syntax::{Definition, DefinitionKind, SyntaxIndex};
 
 struct ModelPattern {
     regex: Regex,
@@ -64,6 +67,55 @@ static GO_PATTERNS: LazyLock<Vec<ModelPattern>> = LazyLock::new(|| {
     }]
 });
 
+static JAVA_PATTERNS: LazyLock<Vec<ModelPattern>> = LazyLock::new(|| {
+    vec![
+        ModelPattern {
+            regex: Regex::new(r"(?m)^[ \t]*(?:public\s+)?(?:static\s+)?(?:final\s+)?record\s+(\w+)\s*[(<]").unwrap(),
+            name_group: 1,
+        },
+        // JPA entities and Lombok data classes, possibly under further annotations
+        ModelPattern {
+            regex: Regex::new(r"(?m)^[ \t]*@(?:Entity|Embeddable|Document|Data|Value)\b[^\n]*\n(?:[ \t]*@[^\n]*\n)*[ \t]*(?:public\s+)?(?:abstract\s+|final\s+)?class\s+(\w+)").unwrap(),
+            name_group: 1,
+        },
+        ModelPattern {
+            regex: Regex::new(r"(?m)^[ \t]*(?:public\s+)?(?:final\s+)?class\s+(\w+(?:Dto|DTO|Entity|Model|Request|Response))\b").unwrap(),
+            name_group: 1,
+        },
+    ]
+});
+
+static KOTLIN_PATTERNS: LazyLock<Vec<ModelPattern>> = LazyLock::new(|| {
+    vec![
+        ModelPattern {
+            regex: Regex::new(r"(?m)^[ \t]*(?:@\w+(?:\([^)]*\))?\s+)*(?:(?:public|internal|private)\s+)?data\s+class\s+(\w+)").unwrap(),
+            name_group: 1,
+        },
+        ModelPattern {
+            regex: Regex::new(r"(?m)^[ \t]*@(?:Entity|Document|Serializable)\b[^\n]*\n(?:[ \t]*@[^\n]*\n)*[ \t]*(?:(?:public|internal|open)\s+)*class\s+(\w+)").unwrap(),
+            name_group: 1,
+        },
+    ]
+});
+
+static CSHARP_PATTERNS: LazyLock<Vec<ModelPattern>> = LazyLock::new(|| {
+    vec![
+        ModelPattern {
+            regex: Regex::new(r"(?m)^[ \t]*(?:(?:public|internal|sealed|abstract|partial)\s+)*record\s+(?:class\s+|struct\s+)?(\w+)").unwrap(),
+            name_group: 1,
+        },
+        ModelPattern {
+            regex: Regex::new(r"(?m)^[ \t]*(?:(?:public|internal|sealed|partial)\s+)*class\s+(\w+(?:Dto|DTO|Entity|Model|Request|Response))\b").unwrap(),
+            name_group: 1,
+        },
+    ]
+});
+
+/// Any Java or C# class; kept as a model only when its body holds only data
+static PLAIN_CLASS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:(?:public|internal|sealed|partial|final)\s+)*class\s+(\w+)").unwrap()
+});
+
 static PROTO_PATTERNS: LazyLock<Vec<ModelPattern>> = LazyLock::new(|| {
     vec![ModelPattern {
         regex: Regex::new(r"(?m)^message\s+(\w+)\s*\{").unwrap(),
@@ -71,41 +123,32 @@ static PROTO_PATTERNS: LazyLock<Vec<ModelPattern>> = LazyLock::new(|| {
     }]
 });
 
-fn extract_ts_fields(content: &str, start: usize) -> Vec<String> {
-    let rest = &content[start..];
-    let mut fields = Vec::new();
-    let mut brace_depth = 0;
-    let mut found_open = false;
-
-    for line in rest.lines() {
-        let trimmed = line.trim();
-        if trimmed.contains('{') {
-            found_open = true;
-            brace_depth += trimmed.matches('{').count();
-            brace_depth -= trimmed.matches('}').count();
-            continue;
-        }
-        if !found_open {
-            continue;
-        }
-        brace_depth += trimmed.matches('{').count();
-        brace_depth -= trimmed.matches('}').count();
-        if brace_depth == 0 {
-            break;
-        }
-        // Parse field: "name: type" or "name?: type"
-        if let Some(colon_pos) = trimmed.find(':') {
-            let field_name = trimmed[..colon_pos].trim().trim_end_matches('?');
-            if !field_name.is_empty()
-                && !field_name.starts_with("//")
-                && !field_name.starts_with("/*")
-            {
-                fields.push(field_name.to_string());
-            }
-        }
+/// Structured fields parsed from a model's declaration text, if any were found.
+fn fields_of(decl: &str, language: &str) -> Option<Vec<ModelField>> {
+    let fields = extract_fields(decl, language);
+    if fields.is_empty() {
+        None
+    } else {
+        Some(fields)
+    }
+}
+
+/// Model metadata from the declaration: declared bases go under `extends`.
+fn model_metadata(decl: &str, language: &str) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    let bases = extract_bases(decl, language);
+    if !bases.is_empty() {
+        metadata.insert("extends".to_string(), bases.join(","));
     }
+    metadata
+}
 
-    fields
+/// Byte offset just past the end of 1-based `line`.
+fn line_end_offset(content: &str, line: u32) -> usize {
+    content
+        .match_indices('\n')
+        .nth(line.saturating_sub(1) as usize)
+        .map_or(content.len(), |(i, _)| i)
 }
 
 pub fn detect_models(
@@ -119,53 +162,139 @@ pub fn detect_models(
         "python" => &PYTHON_PATTERNS,
         "go" => &GO_PATTERNS,
         "protobuf" => &PROTO_PATTERNS,
+        "java" => &JAVA_PATTERNS,
+        "kotlin" => &KOTLIN_PATTERNS,
+        "csharp" => &CSHARP_PATTERNS,
         _ => return Vec::new(),
     };
 
-    let mut components = Vec::new();
+    let mut components: Vec<DetectedComponent> = Vec::new();
 
     for pattern in patterns {
         for cap in pattern.regex.captures_iter(content) {
             let name = cap[pattern.name_group].to_string();
             let match_start = cap.get(0).unwrap().start();
-            let line_num = content[..match_start].lines().count() as u32 + 1;
-
-            let model_fields = if language == "typescript" || language == "javascript" {
-                let fields = extract_ts_fields(content, match_start);
-                if fields.is_empty() {
-                    None
-                } else {
-                    Some(fields)
-                }
-            } else {
-                None
-            };
-
-            components.push(DetectedComponent {
-                id: make_id("model", &name, file),
-                name,
-                kind: ComponentKind::Model,
-                language: language.to_string(),
-                source: SourceLocation {
-                    file: file.to_string(),
-                    line_start: Some(line_num),
-                    line_end: None,
-                },
-                metadata: HashMap::new(),
-                transport_protocol: None,
-                http_method: None,
-                http_path: None,
-                model_fields,
-                prompt_subtype: None,
-                consumes: None,
-                produces: None,
-            });
+            components.push(model_at(content, language, file, name, match_start));
+        }
+    }
+
+    // Plain Java and C# classes without an annotation or a DTO-style name are
+    // models when they hold only fields and accessors
+    if matches!(language, "java" | "csharp") {
+        for cap in PLAIN_CLASS.captures_iter(content) {
+            let start = cap.get(0).unwrap().start();
+            if components.iter().any(|c| c.name == cap[1]) || follows_annotation(content, start) {
+                continue;
+            }
+            let model = model_at(content, language, file, cap[1].to_string(), start);
+            let decl_end = model.source.line_end.map_or(content.len(), |l| line_end_offset(content, l));
+            if is_data_class(&content[start..decl_end.max(start)], language) {
+                components.push(model);
+            }
         }
     }
 
     components
 }
 
+/// Whether the nearest non-blank line above `offset` is an annotation or
+/// attribute, which the annotated patterns already judged.
+fn follows_annotation(content: &str, offset: usize) -> bool {
+    content[..offset]
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .is_some_and(|l| l.starts_with('@') || l.starts_with('['))
+}
+
+/// A model named `name` whose declaration starts at byte `match_start`.
+fn model_at(content: &str, language: &str, file: &str, name: String, match_start: usize) -> DetectedComponent {
+    let line_num = content[..match_start].lines().count() as u32 + 1;
+
+    let line_end = definition_end(content, language, line_num);
+    let decl_end = line_end.map_or(content.len(), |l| line_end_offset(content, l));
+    let decl = &content[match_start..decl_end.max(match_start)];
+
+    DetectedComponent {
+        id: make_id("model", &name, file),
+        name,
+        kind: ComponentKind::Model,
+        language: language.to_string(),
+        source: SourceLocation {
+            file: file.to_string(),
+            line_start: Some(line_num),
+            line_end,
+        },
+        metadata: model_metadata(decl, language),
+        model_fields: fields_of(decl, language),
+        ..Default::default()
+    }
+}
+
+/// Base classes that mark a Python class as a model
+const PYTHON_MODEL_BASES: &[&str] = &["BaseModel", "Schema", "TypedDict"];
+
+fn is_syntax_model(def: &Definition, language: &str) -> bool {
+    match language {
+        "typescript" | "javascript" => matches!(
+            def.kind,
+            DefinitionKind::Interface | DefinitionKind::TypeAlias | DefinitionKind::Enum
+        ),
+        "rust" => matches!(def.kind, DefinitionKind::Struct | DefinitionKind::Enum),
+        "go" => def.kind == DefinitionKind::Struct,
+        "python" => {
+            def.kind == DefinitionKind::Class
+                && (def
+                    .decorators
+                    .iter()
+                    .any(|d| d.name.rsplit('.').next() == Some("dataclass"))
+                    || def.bases.iter().any(|b| {
+                        let base = b.rsplit('.').next().unwrap_or(b);
+                        PYTHON_MODEL_BASES.contains(&base)
+                    }))
+        }
+        _ => false,
+    }
+}
+
+/// Detect models from parsed definitions. Unlike the line-anchored regexes this
+/// finds nested and indented definitions and records their exact span.
+pub fn detect_models_from_syntax(
+    content: &str,
+    language: &str,
+    file: &str,
+    syntax: &SyntaxIndex,
+) -> Vec<DetectedComponent> {
+    let mut components = Vec::new();
+
+    for def in &syntax.definitions {
+        if !is_syntax_model(def, language) {
+            continue;
+        }
+
+        let decl = content.get(def.byte_range.clone()).unwrap_or("");
+        let model_fields = fields_of(decl, language);
+
+        components.push(DetectedComponent {
+            id: make_id("model", &def.name, file),
+            name: def.name.clone(),
+            kind: ComponentKind::Model,
+            language: language.to_string(),
+            source: SourceLocation {
+                file: file.to_string(),
+                line_start: Some(def.line_start),
+                line_end: Some(def.line_end),
+            },
+            metadata: model_metadata(decl, language),
+            model_fields,
+            ..Default::default()
+        });
+    }
+
+    components
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,4 +376,94 @@ class Config(TypedDict):
         let comps = detect_models(content, "python", "types.py");
         assert_eq!(names(&comps), vec!["Message", "Result", "Config"]);
     }
+
+    #[test]
+    fn detects_jvm_and_dotnet_models() {
+        let java = "@Entity\n@Table(name = \"orders\")\npublic class Order {\n    private Long id;\n}\n\npublic record Point(int x, int y) {}\n";
+        assert_eq!(names(&detect_models(java, "java", "Order.java")), vec!["Point", "Order"]);
+
+        let kotlin = "@Serializable\ndata class User(val id: Long, val email: String?)\n";
+        let comps = detect_models(kotlin, "kotlin", "User.kt");
+        assert_eq!(names(&comps)[0], "User");
+        assert_eq!(comps[0].model_fields.as_ref().map(Vec::len), Some(2));
+
+        let csharp = "public sealed record OrderPlaced(Guid Id, decimal Total);\npublic class OrderService { }\n";
+        assert_eq!(names(&detect_models(csharp, "csharp", "Orders.cs")), vec!["OrderPlaced"]);
+    }
+
+    #[test]
+    fn detects_plain_java_and_csharp_data_classes() {
+        let java = "public class Customer {\n    private String name;\n    public String getName() { return name; }\n}\n\n\
+@Service\npublic class Billing {\n    private final Repo repo;\n}\n\n\
+public class Mailer {\n    private String host;\n    public void send() { }\n}\n";
+        let comps = detect_models(java, "java", "Customer.java");
+        assert_eq!(names(&comps), vec!["Customer"]);
+        assert_eq!(comps[0].model_fields.as_ref().map(Vec::len), Some(1));
+
+        let csharp = "public class Customer\n{\n    public string Name { get; set; }\n    public int? Age { get; init; }\n}\n\n\
+public class CustomerDto\n{\n    public string Name { get; set; }\n}\n";
+        assert_eq!(names(&detect_models(csharp, "csharp", "Customer.cs")), vec!["CustomerDto", "Customer"]);
+    }
+
+    #[test]
+    fn regex_models_carry_structured_fields() {
+        let content = "message Order {\n  string id = 1;\n  repeated LineItem items = 2;\n}\n\nmessage LineItem {\n  string sku = 1;\n}\n";
+        let comps = detect_models(content, "protobuf", "order.proto");
+        assert_eq!(names(&comps), vec!["Order", "LineItem"]);
+        let fields = comps[0].model_fields.as_ref().unwrap();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[1].type_name.as_deref(), Some("repeated LineItem"));
+        assert_eq!(comps[0].source.line_end, Some(4));
+    }
+
+    fn syntax_models(content: &str, language: &str) -> Vec<DetectedComponent> {
+        let index = crate::scanner::syntax::parse(content, language, "src/types").unwrap();
+        detect_models_from_syntax(content, language, "src/types", &index)
+    }
+
+    #[test]
+    fn syntax_detects_indented_python_models() {
+        let content = r#"
+if TYPE_CHECKING:
+    @dataclasses.dataclass
+    class Hidden:
+        x: int
+
+class User(pydantic.BaseModel):
+    name: str
+
+class Service:
+    pass
+"#;
+        let comps = syntax_models(content, "python");
+        assert_eq!(names(&comps), vec!["Hidden", "User"]);
+        assert_eq!(comps[0].source.line_start, Some(3));
+        assert_eq!(comps[0].source.line_end, Some(5));
+    }
+
+    #[test]
+    fn syntax_detects_rust_models_with_any_visibility() {
+        let content = "pub(crate) struct Config {\n    name: String,\n}\n\nmod inner {\n    pub enum Mode { A, B }\n}\n";
+        let comps = syntax_models(content, "rust");
+        assert_eq!(names(&comps), vec!["Config", "Mode"]);
+        assert_eq!(comps[0].source.line_end, Some(3));
+    }
+
+    #[test]
+    fn syntax_detects_grouped_go_structs() {
+        let content = "package m\n\ntype (\n\tUser struct {\n\t\tID string\n\t}\n\tID string\n)\n";
+        let comps = syntax_models(content, "go");
+        assert_eq!(names(&comps), vec!["User"]);
+    }
+
+    #[test]
+    fn syntax_extracts_typescript_fields() {
+        let content = "export interface User {\n  id: string;\n  email?: string;\n}\n";
+        let comps = syntax_models(content, "typescript");
+        assert_eq!(names(&comps), vec!["User"]);
+        let fields = comps[0].model_fields.as_ref().unwrap();
+        let summary: Vec<(&str, bool)> = fields.iter().map(|f| (f.name.as_str(), f.optional)).collect();
+        assert_eq!(summary, vec![("id", false), ("email", true)]);
+        assert_eq!(comps[0].source.line_end, Some(4));
+    }
 }
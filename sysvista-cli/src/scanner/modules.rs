@@ -0,0 +1,855 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use super::fields::{delimited, split_top_level};
+use super::file_walker::WalkedFile;
+use super::language::detect_language;
+use super::orm::line_of;
+
+// ---------------------------------------------------------------------------
+// Module resolution — import specifiers to the files (and names) they load
+// ---------------------------------------------------------------------------
+
+/// One import of a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Import {
+    /// A file of the scan. `names` lists the bound names for named imports and
+    /// is `None` when the whole module is used (default, namespace, side effect).
+    Module { file: String, names: Option<Vec<String>> },
+    /// A specifier no resolver could map to a file, for the stem fallback
+    Unresolved(String),
+}
+
+/// `export { A, B as C } from "x"` / `pub use x::{A, B as C}` (names exported
+/// as `C`, defined as `B`) or `export * from "x"` / `pub use x::*` (`names` is
+/// `None`). An original name of `"*"` re-exports a whole module.
+#[derive(Debug, Clone)]
+struct ReExport {
+    names: Option<Vec<(String, String)>>,
+    from: String,
+}
+
+/// `compilerOptions.baseUrl` and `paths` of one tsconfig/jsconfig.
+#[derive(Debug, Clone, Default)]
+struct TsConfig {
+    /// Directory of the config file
+    dir: String,
+    base_url: Option<String>,
+    /// Alias pattern → target patterns, relative to `paths_base`
+    paths: Vec<(String, Vec<String>)>,
+    paths_base: String,
+}
+
+/// One path of a Rust `use` tree: `a::b::C as D` or `a::b::*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UsePath {
+    segments: Vec<String>,
+    alias: Option<String>,
+    glob: bool,
+}
+
+/// Resolves imports by language: TypeScript/JavaScript relative paths,
+/// tsconfig aliases and index barrels; Rust paths through the module tree
+/// of `mod` declarations; Go import paths to package directories by go.mod.
+#[derive(Debug, Default)]
+pub struct ModuleIndex {
+    files: HashSet<String>,
+    tsconfigs: Vec<TsConfig>,
+    reexports: HashMap<String, Vec<ReExport>>,
+    /// Rust module file → child module name → its file
+    rust_children: HashMap<String, HashMap<String, String>>,
+    /// Rust module file → the file declaring it; crate roots have none
+    rust_parents: HashMap<String, String>,
+    /// Go module path and directory of each go.mod, longest path first
+    go_modules: Vec<(String, String)>,
+    /// Directory → its non-test Go files
+    go_dirs: HashMap<String, Vec<String>>,
+}
+
+static TS_IMPORT_FROM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^[ \t]*import\s+(?:type\s+)?([\w$*{][^;'"]*?)\s+from\s+['"]([^'"]+)['"]"#).unwrap()
+});
+static TS_SIDE_EFFECT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^[ \t]*import\s+['"]([^'"]+)['"]"#).unwrap());
+static TS_EXPORT_FROM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^[ \t]*export\s+(?:type\s+)?(\*(?:\s+as\s+[\w$]+)?|\{[^}]*\})\s+from\s+['"]([^'"]+)['"]"#).unwrap()
+});
+static TS_REQUIRE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\b(?:require|import)\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap());
+static RUST_MOD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^[ \t]*(?:#\[path\s*=\s*"([^"]+)"\]\s*)?(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*;"#).unwrap()
+});
+static RUST_INLINE_MOD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*\{").unwrap());
+static RUST_USE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*(pub(?:\([^)]*\))?\s+)?use\s+([^;]+);").unwrap());
+static GO_IMPORT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^[ \t]*import\s+(?:([\w.]+)\s+)?"([^"]+)""#).unwrap());
+static GO_IMPORT_BLOCK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^[ \t]*import\s*\(([^)]*)\)").unwrap());
+static GO_IMPORT_SPEC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^[ \t]*(?:([\w.]+)\s+)?"([^"]+)""#).unwrap());
+static GO_VERSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^v\d+$").unwrap());
+static TRAILING_COMMA: LazyLock<Regex> = LazyLock::new(|| Regex::new(r",(\s*[}\]])").unwrap());
+
+/// Extensions tried, in order, for an extensionless TypeScript/JavaScript specifier
+const TS_EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts", "js", "jsx", "mjs", "cjs"];
+
+/// Barrel chains longer than this are assumed to be cycles
+const MAX_REEXPORT_DEPTH: usize = 16;
+
+impl ModuleIndex {
+    /// Read tsconfig/jsconfig files from the walked tree and the re-exports of
+    /// every scanned module.
+    pub fn build(walked: &[WalkedFile], file_contents: &HashMap<String, String>) -> Self {
+        let mut index = ModuleIndex {
+            files: file_contents.keys().cloned().collect(),
+            ..ModuleIndex::default()
+        };
+
+        let by_path: HashMap<String, &WalkedFile> =
+            walked.iter().map(|w| (w.relative_path.replace('\\', "/"), w)).collect();
+        for (path, walked_file) in &by_path {
+            let base = path.rsplit('/').next().unwrap_or(path);
+            if base == "go.mod"
+                && !path.split('/').any(|s| s == "vendor")
+                && let Ok(text) = std::fs::read_to_string(&walked_file.path)
+                && let Some(module) = text.lines().find_map(|l| l.trim().strip_prefix("module "))
+            {
+                let module = module.trim().trim_matches('"').to_string();
+                index.go_modules.push((module, parent(path).to_string()));
+            }
+            if base == "tsconfig.json" || base == "jsconfig.json" {
+                if path.split('/').any(|s| s == "node_modules") {
+                    continue;
+                }
+                index.tsconfigs.push(load_tsconfig(path, &by_path, 0));
+            }
+        }
+        // Deepest config first, so the nearest one wins
+        index.tsconfigs.sort_by_key(|c| std::cmp::Reverse(c.dir.len()));
+        index.go_modules.sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+        for file in file_contents.keys() {
+            if language(file) == Some("go") && !file.ends_with("_test.go") {
+                index.go_dirs.entry(parent(file).to_string()).or_default().push(file.clone());
+            }
+        }
+        for files in index.go_dirs.values_mut() {
+            files.sort();
+        }
+
+        index.build_rust_tree(file_contents);
+        for (file, content) in file_contents {
+            if language(file) == Some("rust") {
+                let reexports: Vec<ReExport> = rust_uses(content)
+                    .filter(|(_, public, _, _)| *public)
+                    .filter_map(|(_, _, inline, path)| {
+                        let (module, rest) = index.resolve_rust(file, &inline, &path.segments).ok()?;
+                        let names = match rest.first() {
+                            _ if path.glob => None,
+                            None => {
+                                let exported = path.alias.clone().or_else(|| path.segments.last().cloned())?;
+                                Some(vec![(exported, "*".to_string())])
+                            }
+                            Some(item) => {
+                                let exported = path.alias.clone().unwrap_or_else(|| item.clone());
+                                Some(vec![(exported, item.clone())])
+                            }
+                        };
+                        Some(ReExport { names, from: module })
+                    })
+                    .collect();
+                if !reexports.is_empty() {
+                    index.reexports.insert(file.clone(), reexports);
+                }
+                continue;
+            }
+            if !is_ts(file) {
+                continue;
+            }
+            let reexports: Vec<ReExport> = TS_EXPORT_FROM
+                .captures_iter(content)
+                .filter_map(|cap| {
+                    let from = index.resolve_ts(file, &cap[2])?;
+                    let clause = cap[1].trim();
+                    let names = match clause.strip_prefix('*') {
+                        Some(rest) => {
+                            let alias = rest.trim().strip_prefix("as").map(str::trim);
+                            alias.map(|a| vec![(a.to_string(), "*".to_string())])
+                        }
+                        None => Some(
+                            named_bindings(clause)
+                                .into_iter()
+                                .map(|(original, local)| (local, original))
+                                .collect(),
+                        ),
+                    };
+                    Some(ReExport { names, from })
+                })
+                .collect();
+            if !reexports.is_empty() {
+                index.reexports.insert(file.clone(), reexports);
+            }
+        }
+        index
+    }
+
+    /// The imports of `file` with the line of each, or `None` when its language
+    /// has no resolver and the caller should fall back to matching specifiers
+    /// by file stem.
+    pub fn imports(&self, file: &str, content: &str) -> Option<Vec<(u32, Import)>> {
+        if language(file) == Some("rust") {
+            return Some(self.rust_imports(file, content));
+        }
+        if language(file) == Some("go") {
+            // A package is every file of its directory
+            return Some(
+                go_imports(content)
+                    .into_iter()
+                    .flat_map(|(line, _, path)| match self.go_package_files(&path) {
+                        Some(files) => files
+                            .iter()
+                            .map(|f| {
+                                let import = Import::Module {
+                                    file: f.clone(),
+                                    names: None,
+                                };
+                                (line, import)
+                            })
+                            .collect(),
+                        None => vec![(line, Import::Unresolved(path))],
+                    })
+                    .collect(),
+            );
+        }
+        if !is_ts(file) {
+            return None;
+        }
+        let mut imports = Vec::new();
+        let mut add = |cap: &regex::Captures, specifier: &str, names: Option<Vec<String>>| {
+            let line = line_of(content, cap.get(0).map_or(0, |m| m.start()));
+            imports.push((line, match self.resolve_ts(file, specifier) {
+                Some(target) => Import::Module { file: target, names },
+                None => Import::Unresolved(specifier.to_string()),
+            }));
+        };
+        for cap in TS_IMPORT_FROM.captures_iter(content) {
+            add(&cap, &cap[2], import_names(&cap[1]));
+        }
+        for cap in TS_EXPORT_FROM.captures_iter(content) {
+            let clause = cap[1].trim();
+            let names = (!clause.starts_with('*'))
+                .then(|| named_bindings(clause).into_iter().map(|(original, _)| original).collect());
+            add(&cap, &cap[2], names);
+        }
+        for cap in TS_SIDE_EFFECT.captures_iter(content).chain(TS_REQUIRE.captures_iter(content)) {
+            add(&cap, &cap[1], None);
+        }
+        Some(imports)
+    }
+
+    /// Follow barrel re-exports from `file` to the module that defines `name`.
+    /// `defines(file, name)` says whether a file has a component of that name.
+    /// Returns the defining file and the name there, `"*"` when a whole
+    /// module is re-exported under `name` (`export * as name from`).
+    pub fn export_origin(
+        &self,
+        file: &str,
+        name: &str,
+        defines: &dyn Fn(&str, &str) -> bool,
+    ) -> Option<(String, String)> {
+        self.export_origin_at(file, name, defines, 0)
+    }
+
+    fn export_origin_at(
+        &self,
+        file: &str,
+        name: &str,
+        defines: &dyn Fn(&str, &str) -> bool,
+        depth: usize,
+    ) -> Option<(String, String)> {
+        if defines(file, name) {
+            return Some((file.to_string(), name.to_string()));
+        }
+        if depth >= MAX_REEXPORT_DEPTH {
+            return None;
+        }
+        self.reexports.get(file)?.iter().find_map(|re| match &re.names {
+            None => self.export_origin_at(&re.from, name, defines, depth + 1),
+            Some(names) => {
+                let (_, original) = names.iter().find(|(exported, _)| exported == name)?;
+                if original == "*" {
+                    Some((re.from.clone(), "*".to_string()))
+                } else {
+                    self.export_origin_at(&re.from, original, defines, depth + 1)
+                }
+            }
+        })
+    }
+
+    /// Link each `mod name;` to `name.rs` or `name/mod.rs` beside its parent:
+    /// in the parent's own directory for `lib.rs`, `main.rs`, `mod.rs` and
+    /// crate roots, otherwise in the directory named after the parent.
+    fn build_rust_tree(&mut self, file_contents: &HashMap<String, String>) {
+        for (file, content) in file_contents {
+            if language(file) != Some("rust") {
+                continue;
+            }
+            let dir = parent(file);
+            let base = file.rsplit('/').next().unwrap_or(file);
+            let stem = base.strip_suffix(".rs").unwrap_or(base);
+            let own_dir = matches!(stem, "lib" | "main" | "mod");
+            for cap in RUST_MOD.captures_iter(content) {
+                let name = &cap[2];
+                let candidates: Vec<String> = match cap.get(1) {
+                    // `#[path]` is relative to the declaring file's directory
+                    Some(path) => vec![join(dir, path.as_str())],
+                    None => {
+                        let nested = join(dir, stem);
+                        let dirs: Vec<&str> = if own_dir { vec![dir] } else { vec![&nested, dir] };
+                        dirs.iter()
+                            .flat_map(|d| [join(d, &format!("{name}.rs")), join(d, &format!("{name}/mod.rs"))])
+                            .collect()
+                    }
+                };
+                if let Some(child) = candidates.into_iter().find(|c| file_contents.contains_key(c)) {
+                    self.rust_parents.insert(child.clone(), file.clone());
+                    self.rust_children.entry(file.clone()).or_default().insert(name.to_string(), child);
+                }
+            }
+        }
+    }
+
+    /// Imports of a Rust file: each used item with the module file it is
+    /// defined or re-exported in. Paths leaving the crate (`serde::Deserialize`,
+    /// `acme_core::db`) stay unresolved for package matching.
+    fn rust_imports(&self, file: &str, content: &str) -> Vec<(u32, Import)> {
+        rust_uses(content)
+            .filter_map(|(line, _, inline, path)| match self.resolve_rust(file, &inline, &path.segments) {
+                Ok((module, rest)) => Some((line, Import::Module {
+                    file: module,
+                    names: match rest.first() {
+                        Some(item) if !path.glob => Some(vec![item.clone()]),
+                        _ => None,
+                    },
+                })),
+                Err(true) => Some((line, Import::Unresolved(path.segments.join("::")))),
+                Err(false) => None,
+            })
+            .collect()
+    }
+
+    /// The module file a Rust path starts in and descends to, and the
+    /// segments left after it (the item, then any associated names). `Err(true)`
+    /// marks a path into another crate, `Err(false)` one that cannot be followed
+    /// (`super` above a crate root).
+    fn resolve_rust(&self, file: &str, inline: &[String], segments: &[String]) -> Result<(String, Vec<String>), bool> {
+        let Some(first) = segments.first() else {
+            return Err(false);
+        };
+        let mut module = file.to_string();
+        let mut depth = inline.len();
+        let mut rest = segments;
+        match first.as_str() {
+            "crate" => {
+                while let Some(up) = self.rust_parents.get(&module) {
+                    module = up.clone();
+                }
+                rest = &segments[1..];
+            }
+            "self" => rest = &segments[1..],
+            "super" => {
+                while rest.first().is_some_and(|s| s == "super") {
+                    if depth > 0 {
+                        depth -= 1;
+                    } else {
+                        module = self.rust_parents.get(&module).ok_or(false)?.clone();
+                    }
+                    rest = &rest[1..];
+                }
+            }
+            name => {
+                // 2018 paths start at a child module of the current one, else another crate
+                let local = inline.is_empty() && self.rust_children.get(file).is_some_and(|c| c.contains_key(name));
+                if !local {
+                    return Err(true);
+                }
+            }
+        }
+        while let Some(child) = rest.first().and_then(|name| self.rust_children.get(&module)?.get(name)) {
+            module = child.clone();
+            rest = &rest[1..];
+        }
+        Ok((module, rest.to_vec()))
+    }
+
+    /// The files of each Go package `file` imports from this scan, by the name
+    /// it is qualified with (`store` for `.../internal/store`).
+    pub fn go_packages(&self, file: &str, content: &str) -> HashMap<String, Vec<String>> {
+        if language(file) != Some("go") {
+            return HashMap::new();
+        }
+        go_imports(content)
+            .into_iter()
+            .filter_map(|(_, name, path)| Some((name, self.go_package_files(&path)?.clone())))
+            .collect()
+    }
+
+    /// Files of the package a Go import path names: the directory under the
+    /// go.mod whose module path is the longest prefix of it.
+    fn go_package_files(&self, import_path: &str) -> Option<&Vec<String>> {
+        self.go_modules.iter().find_map(|(module, dir)| {
+            let rest = import_path.strip_prefix(module.as_str())?;
+            if !rest.is_empty() && !rest.starts_with('/') {
+                return None;
+            }
+            self.go_dirs.get(&join(dir, rest))
+        })
+    }
+
+    /// The scanned file a TypeScript/JavaScript specifier loads from `importer`:
+    /// relative paths, then tsconfig `paths` aliases, then `baseUrl`.
+    fn resolve_ts(&self, importer: &str, specifier: &str) -> Option<String> {
+        if specifier.starts_with('.') {
+            let dir = parent(importer);
+            return self.ts_file(&join(dir, specifier));
+        }
+        let config = self.tsconfigs.iter().find(|c| c.dir.is_empty() || importer.starts_with(&format!("{}/", c.dir)))?;
+        for (pattern, targets) in &config.paths {
+            let Some(wildcard) = match_alias(pattern, specifier) else {
+                continue;
+            };
+            for target in targets {
+                let path = join(&config.paths_base, &target.replacen('*', wildcard, 1));
+                if let Some(file) = self.ts_file(&path) {
+                    return Some(file);
+                }
+            }
+        }
+        let base = config.base_url.as_deref()?;
+        self.ts_file(&join(base, specifier))
+    }
+
+    /// A scanned file for a module path: as written, with each extension, or
+    /// its `index` file. A `.js` specifier also finds the `.ts` source.
+    fn ts_file(&self, path: &str) -> Option<String> {
+        if self.files.contains(path) {
+            return Some(path.to_string());
+        }
+        let stem = ["js", "jsx", "mjs", "cjs"]
+            .iter()
+            .find_map(|ext| path.strip_suffix(&format!(".{ext}")))
+            .unwrap_or(path);
+        for base in [stem.to_string(), format!("{path}/index")] {
+            for ext in TS_EXTENSIONS {
+                let candidate = format!("{base}.{ext}");
+                if self.files.contains(&candidate) {
+                    return Some(candidate);
+                }
+            }
+        }
+        None
+    }
+}
+
+fn language(file: &str) -> Option<&'static str> {
+    detect_language(std::path::Path::new(file))
+}
+
+fn is_ts(file: &str) -> bool {
+    matches!(language(file), Some("typescript" | "javascript"))
+}
+
+/// `(public, inline module path, use path)` for every path of every `use`
+/// in a Rust file. The inline path names the `mod x { … }` blocks around
+/// the statement, which `super` and `self` start from.
+fn rust_uses(content: &str) -> impl Iterator<Item = (u32, bool, Vec<String>, UsePath)> + '_ {
+    let inline_mods: Vec<(usize, usize, String)> = RUST_INLINE_MOD
+        .captures_iter(content)
+        .filter_map(|cap| {
+            let start = cap.get(0)?.end() - 1;
+            let body = delimited(content, start, b'{', b'}')?;
+            Some((start, start + body.len() + 1, cap[1].to_string()))
+        })
+        .collect();
+    RUST_USE.captures_iter(content).flat_map(move |cap| {
+        let offset = cap.get(0).map_or(0, |m| m.start());
+        let inline: Vec<String> = inline_mods
+            .iter()
+            .filter(|(start, end, _)| *start < offset && offset < *end)
+            .map(|(_, _, name)| name.clone())
+            .collect();
+        let line = line_of(content, offset);
+        let public = cap.get(1).is_some();
+        let mut paths = Vec::new();
+        expand_use_tree(&[], &cap[2], &mut paths);
+        paths.into_iter().map(move |path| (line, public, inline.clone(), path))
+    })
+}
+
+/// `(line, qualifier, import path)` of each Go import, single or in an
+/// `import (…)` block. The qualifier is the alias, else the last path element
+/// (skipping a `/v2` major version); blank and dot imports have none and are
+/// skipped.
+fn go_imports(content: &str) -> Vec<(u32, String, String)> {
+    let single = GO_IMPORT.captures_iter(content).map(|cap| (0, cap));
+    let blocks = GO_IMPORT_BLOCK.captures_iter(content).flat_map(|block| {
+        let specs = block.get(1).map_or((0, ""), |m| (m.start(), m.as_str()));
+        GO_IMPORT_SPEC.captures_iter(specs.1).map(move |cap| (specs.0, cap)).collect::<Vec<_>>()
+    });
+    single
+        .chain(blocks)
+        .filter_map(|(base, cap)| {
+            let line = line_of(content, base + cap.get(0).map_or(0, |m| m.start()));
+            let path = cap[2].to_string();
+            let name = match cap.get(1).map(|m| m.as_str()) {
+                Some("_" | ".") => return None,
+                Some(alias) => alias.to_string(),
+                None => {
+                    let mut elements = path.rsplit('/');
+                    let last = elements.next().unwrap_or(&path);
+                    let last = if GO_VERSION.is_match(last) { elements.next().unwrap_or(last) } else { last };
+                    last.to_string()
+                }
+            };
+            Some((line, name, path))
+        })
+        .collect()
+}
+
+/// Flatten a Rust use tree (`a::{b::C, d as e, f::*, self}`) into paths
+/// prefixed with `prefix`. `self` in a group names the group's module.
+fn expand_use_tree(prefix: &[String], tree: &str, out: &mut Vec<UsePath>) {
+    let tree = tree.trim().trim_start_matches("::");
+    if let Some(open) = tree.find('{') {
+        let mut base = prefix.to_vec();
+        base.extend(segments(tree[..open].trim_end_matches("::")));
+        let inner = delimited(tree, open, b'{', b'}').unwrap_or("");
+        for part in split_top_level(inner, b",", false, false) {
+            expand_use_tree(&base, part, out);
+        }
+        return;
+    }
+    let (path, alias) = match tree.split_once(" as ") {
+        Some((path, alias)) => (path.trim(), Some(alias.trim().to_string())),
+        None => (tree, None),
+    };
+    let mut segments_list = prefix.to_vec();
+    segments_list.extend(segments(path));
+    let glob = segments_list.last().is_some_and(|s| s == "*");
+    if glob || segments_list.last().is_some_and(|s| s == "self") {
+        segments_list.pop();
+    }
+    if segments_list.is_empty() {
+        return;
+    }
+    let alias = alias.filter(|a| a != "_");
+    out.push(UsePath {
+        segments: segments_list,
+        alias,
+        glob,
+    });
+}
+
+fn segments(path: &str) -> impl Iterator<Item = String> + '_ {
+    path.split("::").map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+/// Names bound by an import clause (`{ A, B as C }` → `A`, `B`), or `None`
+/// when it has a default or namespace binding and uses the whole module.
+fn import_names(clause: &str) -> Option<Vec<String>> {
+    let clause = clause.trim();
+    if !clause.starts_with('{') {
+        return None;
+    }
+    Some(named_bindings(clause).into_iter().map(|(original, _)| original).collect())
+}
+
+/// `(original, local)` pairs of a `{ A, type B, C as D }` list.
+fn named_bindings(clause: &str) -> Vec<(String, String)> {
+    clause
+        .trim()
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .split(',')
+        .filter_map(|item| {
+            let item = item.trim();
+            let item = item.strip_prefix("type ").unwrap_or(item).trim();
+            if item.is_empty() {
+                return None;
+            }
+            Some(match item.split_once(" as ") {
+                Some((original, local)) => (original.trim().to_string(), local.trim().to_string()),
+                None => (item.to_string(), item.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// The part of `specifier` matched by the `*` of a tsconfig alias (`""` for an
+/// exact alias).
+fn match_alias<'a>(pattern: &str, specifier: &'a str) -> Option<&'a str> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => specifier
+            .strip_prefix(prefix)?
+            .strip_suffix(suffix)
+            .filter(|_| specifier.len() >= prefix.len() + suffix.len()),
+        None => (pattern == specifier).then_some(""),
+    }
+}
+
+/// Read `compilerOptions.baseUrl` and `paths`, after those of an `extends`ed
+/// config in the tree.
+fn load_tsconfig(path: &str, files: &HashMap<String, &WalkedFile>, depth: usize) -> TsConfig {
+    let dir = parent(path).to_string();
+    let mut config = TsConfig {
+        dir: dir.clone(),
+        paths_base: dir.clone(),
+        ..TsConfig::default()
+    };
+    let Some(text) = files.get(path).and_then(|w| std::fs::read_to_string(&w.path).ok()) else {
+        return config;
+    };
+    let cleaned = TRAILING_COMMA.replace_all(&strip_json_comments(&text), "$1").into_owned();
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&cleaned) else {
+        return config;
+    };
+
+    if let Some(parent_config) = json.get("extends").and_then(|e| e.as_str())
+        && parent_config.starts_with('.')
+        && depth < 4
+    {
+        let mut extended = join(&dir, parent_config);
+        if !extended.ends_with(".json") {
+            extended.push_str(".json");
+        }
+        let inherited = load_tsconfig(&extended, files, depth + 1);
+        config.base_url = inherited.base_url;
+        config.paths = inherited.paths;
+        config.paths_base = inherited.paths_base;
+    }
+
+    let options = json.get("compilerOptions");
+    if let Some(base_url) = options.and_then(|o| o.get("baseUrl")).and_then(|b| b.as_str()) {
+        let base = join(&dir, base_url);
+        config.paths_base = base.clone();
+        config.base_url = Some(base);
+    }
+    if let Some(paths) = options.and_then(|o| o.get("paths")).and_then(|p| p.as_object()) {
+        if config.base_url.is_none() {
+            config.paths_base = dir.clone();
+        }
+        config.paths = paths
+            .iter()
+            .map(|(alias, targets)| {
+                let targets = targets
+                    .as_array()
+                    .map(|list| list.iter().filter_map(|t| t.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default();
+                (alias.clone(), targets)
+            })
+            .collect();
+        // Longest alias prefix first, as TypeScript matches them
+        config.paths.sort_by_key(|(alias, _)| std::cmp::Reverse(alias.split('*').next().map_or(0, str::len)));
+    }
+    config
+}
+
+/// JSON with `//` and `/* */` comments removed, leaving string contents alone.
+fn strip_json_comments(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    let mut start = 0;
+    let mut in_string = false;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            if b == b'\\' {
+                i += 1;
+            } else if b == b'"' {
+                in_string = false;
+            }
+        } else if b == b'"' {
+            in_string = true;
+        } else if b == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            out.push_str(&text[start..i]);
+            i = text[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            start = i;
+            continue;
+        } else if b == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            out.push_str(&text[start..i]);
+            i = text[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4);
+            start = i;
+            continue;
+        }
+        i += 1;
+    }
+    out.push_str(&text[start.min(text.len())..]);
+    out
+}
+
+/// Directory part of a relative path (`""` at the root).
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// Join `rel` onto `dir`, resolving `.` and `..` segments.
+fn join(dir: &str, rel: &str) -> String {
+    let mut parts: Vec<&str> = dir.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+    for segment in rel.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            s => parts.push(s),
+        }
+    }
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(files: &[(&str, &str)], configs: &[(&str, &str)]) -> (std::path::PathBuf, ModuleIndex) {
+        static NEXT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let n = NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("sysvista-modules-{}-{n}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let walked: Vec<WalkedFile> = configs
+            .iter()
+            .map(|(rel, content)| {
+                let path = dir.join(rel);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(&path, content).unwrap();
+                WalkedFile {
+                    path,
+                    relative_path: rel.to_string(),
+                }
+            })
+            .collect();
+        let contents: HashMap<String, String> = files.iter().map(|(f, c)| (f.to_string(), c.to_string())).collect();
+        let built = ModuleIndex::build(&walked, &contents);
+        (dir, built)
+    }
+
+    fn imports(index: &ModuleIndex, file: &str, content: &str) -> Vec<Import> {
+        index.imports(file, content).unwrap().into_iter().map(|(_, import)| import).collect()
+    }
+
+    #[test]
+    fn resolves_relative_alias_and_index_imports() {
+        let app = r#"import { UserService } from "../a/utils";
+import Default, { helper } from "./b/utils";
+import * as api from "@/lib/api";
+import { Button } from "components/button";
+import "./polyfills.js";
+import React from "react";
+"#;
+        let (dir, index) = index(
+            &[
+                ("web/src/app/main.ts", app),
+                ("web/src/a/utils.ts", ""),
+                ("web/src/app/b/utils.tsx", ""),
+                ("web/src/lib/api/index.ts", ""),
+                ("web/src/components/button.tsx", ""),
+                ("web/src/app/polyfills.ts", ""),
+            ],
+            &[(
+                "web/tsconfig.json",
+                "{\n  // aliases\n  \"compilerOptions\": {\n    \"baseUrl\": \"src\",\n    \"paths\": { \"@/*\": [\"./*\"] },\n  },\n}\n",
+            )],
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let module = |file: &str, names: Option<&[&str]>| Import::Module {
+            file: file.to_string(),
+            names: names.map(|n| n.iter().map(|s| s.to_string()).collect()),
+        };
+        assert_eq!(
+            imports(&index, "web/src/app/main.ts", app),
+            vec![
+                module("web/src/a/utils.ts", Some(&["UserService"])),
+                module("web/src/app/b/utils.tsx", None),
+                module("web/src/lib/api/index.ts", None),
+                module("web/src/components/button.tsx", Some(&["Button"])),
+                Import::Unresolved("react".to_string()),
+                module("web/src/app/polyfills.ts", None),
+            ]
+        );
+        assert!(index.imports("api/main.py", "from x import y").is_none());
+    }
+
+    #[test]
+    fn follows_barrel_reexports() {
+        let (dir, index) = index(
+            &[
+                ("src/models/index.ts", "export * from \"./user\";\nexport { Order as PurchaseOrder } from \"./order\";\nexport * as billing from \"./billing\";\n"),
+                ("src/models/user.ts", "export class User {}\n"),
+                ("src/models/order.ts", "export class Order {}\n"),
+                ("src/models/billing.ts", "export class Invoice {}\n"),
+            ],
+            &[],
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let defined = [("src/models/user.ts", "User"), ("src/models/order.ts", "Order")];
+        let defines = |file: &str, name: &str| defined.contains(&(file, name));
+        let origin = |name: &str| index.export_origin("src/models/index.ts", name, &defines);
+        assert_eq!(origin("User"), Some(("src/models/user.ts".to_string(), "User".to_string())));
+        assert_eq!(origin("PurchaseOrder"), Some(("src/models/order.ts".to_string(), "Order".to_string())));
+        assert_eq!(origin("billing"), Some(("src/models/billing.ts".to_string(), "*".to_string())));
+        assert_eq!(origin("Missing"), None);
+    }
+
+    #[test]
+    fn resolves_rust_paths_through_the_module_tree() {
+        let scanner = "pub mod models;\nmod util;\n\nuse self::models::{Invoice, billing::{self, Total as Sum}};\nuse crate::output::*;\nuse serde::Deserialize;\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n    use super::super::output::Report;\n}\n";
+        let (dir, index) = index(
+            &[
+                ("src/main.rs", "mod output;\nmod scanner;\n\nuse scanner::models::Invoice;\n"),
+                ("src/output.rs", "pub use crate::scanner::models::Invoice as Bill;\n"),
+                ("src/scanner/mod.rs", scanner),
+                ("src/scanner/models.rs", "pub mod billing;\n"),
+                ("src/scanner/models/billing.rs", "use super::super::util::round;\n"),
+                ("src/scanner/util.rs", ""),
+            ],
+            &[],
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let module = |file: &str, names: Option<&[&str]>| Import::Module {
+            file: file.to_string(),
+            names: names.map(|n| n.iter().map(|s| s.to_string()).collect()),
+        };
+        assert_eq!(
+            imports(&index, "src/main.rs", "use scanner::models::Invoice;\n"),
+            vec![module("src/scanner/models.rs", Some(&["Invoice"]))]
+        );
+        assert_eq!(
+            imports(&index, "src/scanner/mod.rs", scanner),
+            vec![
+                module("src/scanner/models.rs", Some(&["Invoice"])),
+                module("src/scanner/models/billing.rs", None),
+                module("src/scanner/models/billing.rs", Some(&["Total"])),
+                module("src/output.rs", None),
+                Import::Unresolved("serde::Deserialize".to_string()),
+                module("src/scanner/mod.rs", None),
+                module("src/output.rs", Some(&["Report"])),
+            ]
+        );
+        assert_eq!(
+            imports(&index, "src/scanner/models/billing.rs", "use super::super::util::round;\n"),
+            vec![module("src/scanner/util.rs", Some(&["round"]))]
+        );
+
+        let defines = |file: &str, name: &str| file == "src/scanner/models.rs" && name == "Invoice";
+        assert_eq!(
+            index.export_origin("src/output.rs", "Bill", &defines),
+            Some(("src/scanner/models.rs".to_string(), "Invoice".to_string()))
+        );
+    }
+}
@@ -0,0 +1,1065 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::output::schema::{ComponentKind, DetectedComponent, ModelField, SourceLocation};
+
+use super::fields::{delimited, find_top_level, split_top_level};
+use super::make_id;
+use super::spans::definition_end;
+
+// ---------------------------------------------------------------------------
+// ORM table definitions — which models are backed by database tables
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Column {
+    pub(crate) name: String,
+    pub(crate) type_name: Option<String>,
+    pub(crate) primary_key: bool,
+    /// Referenced `table.column` (or `Model.column` when only the class is known)
+    pub(crate) foreign_key: Option<String>,
+    pub(crate) optional: bool,
+    pub(crate) default: Option<String>,
+}
+
+#[derive(Debug)]
+struct OrmModel {
+    name: String,
+    orm: &'static str,
+    table: String,
+    columns: Vec<Column>,
+    line_start: u32,
+}
+
+// ---------------------------------------------------------------------------
+// Regex patterns
+// ---------------------------------------------------------------------------
+
+static PYTHON_CLASS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^class\s+(\w+)\s*\(([^)]*)\)\s*:").unwrap());
+
+static TABLENAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"__tablename__\s*=\s*["']([^"']+)["']"#).unwrap());
+
+static DJANGO_DB_TABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"db_table\s*=\s*["']([^"']+)["']"#).unwrap());
+
+static DJANGO_ABSTRACT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"abstract\s*=\s*True").unwrap());
+
+static QUOTED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"^["']([^"']*)["']$"#).unwrap());
+
+static TYPEORM_ENTITY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*@Entity\s*\(([^)]*)\)\s*(?:@\w+(?:\([^)]*\))?\s*)*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+(\w+)",
+    )
+    .unwrap()
+});
+
+static TS_OBJECT_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bname\s*:\s*["'`]([^"'`]+)["'`]"#).unwrap());
+
+static ARROW_TARGET: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"=>\s*(\w+)").unwrap());
+
+static PRISMA_MODEL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^model\s+(\w+)\s*\{").unwrap());
+
+static PRISMA_FIELD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\w+)\s+(\w+)(\[\])?(\?)?\s*(.*)$").unwrap());
+
+static PRISMA_MAP: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"@@?map\(\s*(?:name\s*:\s*)?"([^"]+)"\s*\)"#).unwrap());
+
+static PRISMA_RELATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"@relation\([^)]*fields\s*:\s*\[([^\]]*)\][^)]*references\s*:\s*\[([^\]]*)\]").unwrap()
+});
+
+static DIESEL_TABLE_MACRO: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*(?:diesel::)?table!\s*\{").unwrap());
+
+static DIESEL_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:#\[[^\]]*\]\s*)*(?:\w+\.)?(\w+)\s*\(([^)]*)\)\s*\{([^}]*)\}").unwrap()
+});
+
+static DIESEL_JOINABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:diesel::)?joinable!\s*\(\s*(\w+)\s*->\s*(\w+)\s*\(\s*(\w+)\s*\)\s*\)").unwrap()
+});
+
+static DIESEL_STRUCT_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"#\[(?:diesel\(\s*)?table_name\s*=\s*"?(?:\w+::)*(\w+)"?"#).unwrap()
+});
+
+static GORM_TABLE_NAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"func\s*\(\s*(?:\w+\s+)?\*?(\w+)\s*\)\s*TableName\(\)\s*string\s*\{\s*return\s*"([^"]+)""#).unwrap()
+});
+
+// ---------------------------------------------------------------------------
+// Naming helpers
+// ---------------------------------------------------------------------------
+
+/// `UserID` → `user_id`, `HTTPRequestLog` → `http_request_log`
+pub(crate) fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let acronym_end = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev_lower || acronym_end {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// English plural used by GORM's default naming strategy
+pub(crate) fn pluralize(word: &str) -> String {
+    if word.ends_with('y') && !word.ends_with("ay") && !word.ends_with("ey") && !word.ends_with("oy") {
+        format!("{}ies", &word[..word.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|s| word.ends_with(s)) {
+        format!("{word}es")
+    } else {
+        format!("{word}s")
+    }
+}
+
+pub(crate) fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    QUOTED.captures(s).and_then(|c| c.get(1)).map_or(s, |m| m.as_str())
+}
+
+/// 1-based line of a byte offset, which may fall mid-line
+pub(crate) fn line_of(content: &str, offset: usize) -> u32 {
+    content[..offset].matches('\n').count() as u32 + 1
+}
+
+/// A call's callee, positional arguments and keyword arguments:
+/// `Column(String(50), ForeignKey("users.id"), nullable=True)`
+pub(crate) struct Call<'a> {
+    pub(crate) callee: &'a str,
+    pub(crate) positional: Vec<&'a str>,
+    pub(crate) keywords: HashMap<&'a str, &'a str>,
+}
+
+pub(crate) fn parse_call(value: &str) -> Option<Call<'_>> {
+    let open = value.find('(')?;
+    let args = delimited(value, open, b'(', b')')?;
+    let mut positional = Vec::new();
+    let mut keywords = HashMap::new();
+    for arg in split_top_level(args, b",", false, false) {
+        let arg = arg.trim();
+        match find_top_level(arg, b'=', false) {
+            Some(eq) => {
+                keywords.insert(arg[..eq].trim(), arg[eq + 1..].trim());
+            }
+            None => positional.push(arg),
+        }
+    }
+    Some(Call { callee: value[..open].trim(), positional, keywords })
+}
+
+/// The full text of a Python class, and the statements of its body at the
+/// body's own indentation level (nested classes such as `Meta` are skipped).
+fn python_class_body(content: &str, line_start: u32) -> (String, Vec<String>) {
+    let lines: Vec<&str> = content.lines().collect();
+    let end = definition_end(content, "python", line_start).unwrap_or(lines.len() as u32) as usize;
+    let decl = lines[line_start as usize - 1..end.min(lines.len())].join("\n");
+    let Some(colon) = find_top_level(&decl, b':', false) else {
+        return (decl, Vec::new());
+    };
+    let members = split_top_level(&decl[colon + 1..], b"\n", false, false);
+    let indent_of = |m: &str| m.len() - m.trim_start().len();
+    let Some(base) = members.iter().map(|m| indent_of(m)).find(|&i| i > 0) else {
+        return (decl, Vec::new());
+    };
+    let statements = members
+        .into_iter()
+        .filter(|m| indent_of(m) == base)
+        .map(|m| m.trim().to_string())
+        .collect();
+    (decl, statements)
+}
+
+// ---------------------------------------------------------------------------
+// Python: SQLAlchemy and Django
+// ---------------------------------------------------------------------------
+
+const SQLALCHEMY_COLUMN_CALLS: &[&str] = &["Column", "mapped_column"];
+
+/// Fill a column from the arguments of a SQLAlchemy `Column(...)` /
+/// `mapped_column(...)` call. A leading string argument names the column.
+pub(crate) fn apply_column_call(column: &mut Column, call: &Call) {
+    let mut positional = call.positional.iter();
+    if let Some(first) = call.positional.first()
+        && QUOTED.is_match(first)
+    {
+        column.name = unquote(first).to_string();
+        positional.next();
+    }
+    for arg in positional {
+        if let Some(fk) = parse_call(arg).filter(|c| c.callee.ends_with("ForeignKey")) {
+            column.foreign_key = fk.positional.first().map(|t| unquote(t).to_string());
+        } else if column.type_name.is_none() {
+            column.type_name = Some(arg.to_string());
+        }
+    }
+    column.primary_key = call.keywords.get("primary_key") == Some(&"True");
+    if let Some(nullable) = call.keywords.get("nullable") {
+        column.optional = *nullable == "True";
+    }
+    column.default = call
+        .keywords
+        .get("default")
+        .or_else(|| call.keywords.get("server_default"))
+        .map(|d| d.to_string());
+}
+
+fn sqlalchemy_column(statement: &str) -> Option<Column> {
+    let eq = find_top_level(statement, b'=', false);
+    let target = &statement[..eq.unwrap_or(statement.len())];
+    let value = eq.map(|e| statement[e + 1..].trim());
+    let (name, annotation) = match find_top_level(target, b':', false) {
+        Some(colon) => (target[..colon].trim(), Some(target[colon + 1..].trim())),
+        None => (target.trim(), None),
+    };
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_') || name.starts_with("__") {
+        return None;
+    }
+    let mapped = annotation.and_then(|a| a.strip_prefix("Mapped[")).and_then(|a| a.strip_suffix(']'));
+    let call = value.and_then(parse_call);
+    let is_column_call = call.as_ref().is_some_and(|c| {
+        SQLALCHEMY_COLUMN_CALLS.contains(&c.callee.rsplit('.').next().unwrap_or(c.callee))
+    });
+    // `Mapped[...]` without a value is a column; relationships and properties are not
+    if !is_column_call && (mapped.is_none() || value.is_some()) {
+        return None;
+    }
+
+    let mut column = Column {
+        name: name.to_string(),
+        type_name: mapped.map(String::from),
+        optional: mapped.is_some_and(|m| m.starts_with("Optional[") || m.contains("| None")),
+        ..Default::default()
+    };
+    if let Some(call) = call {
+        apply_column_call(&mut column, &call);
+    }
+    Some(column)
+}
+
+/// Django's default table name: `<app_label>_<model name lowercased>`
+fn django_app_label(file: &str) -> String {
+    let parts: Vec<&str> = file.split('/').collect();
+    let dir = match parts.as_slice() {
+        [.., app, "models", _] => app,
+        [.., app, _] => app,
+        _ => "app",
+    };
+    dir.to_string()
+}
+
+fn django_reference(target: &str, app: &str) -> String {
+    let target = unquote(target);
+    match target.split_once('.') {
+        Some((other_app, model)) => format!("{other_app}_{}", model.to_lowercase()),
+        None => format!("{app}_{}", target.to_lowercase()),
+    }
+}
+
+fn django_column(statement: &str, app: &str, model_table: &str) -> Option<Column> {
+    let eq = find_top_level(statement, b'=', false)?;
+    let name = statement[..eq].trim();
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let call = parse_call(statement[eq + 1..].trim())?;
+    let field_type = call.callee.rsplit('.').next().unwrap_or(call.callee);
+    let relation = matches!(field_type, "ForeignKey" | "OneToOneField");
+    if !(field_type.ends_with("Field") || relation) || field_type == "ManyToManyField" {
+        return None;
+    }
+    let mut column = Column {
+        name: name.to_string(),
+        type_name: Some(field_type.to_string()),
+        primary_key: call.keywords.get("primary_key") == Some(&"True"),
+        optional: call.keywords.get("null") == Some(&"True"),
+        default: call.keywords.get("default").map(|d| d.to_string()),
+        ..Default::default()
+    };
+    if relation {
+        let target = call.positional.first().or_else(|| call.keywords.get("to"))?;
+        let table = if unquote(target) == "self" {
+            model_table.to_string()
+        } else {
+            django_reference(target, app)
+        };
+        column.name = format!("{name}_id");
+        column.foreign_key = Some(format!("{table}.id"));
+    }
+    if let Some(db_column) = call.keywords.get("db_column") {
+        column.name = unquote(db_column).to_string();
+    }
+    Some(column)
+}
+
+/// SQLAlchemy and Django models of a file. A Django model subclasses
+/// `models.Model` or a model class defined earlier in the same file: abstract
+/// bases lend their fields, concrete ones link through `<parent>_ptr_id`. Bases
+/// imported from other files are not followed, so their subclasses are missed.
+fn python_models(content: &str, file: &str) -> Vec<OrmModel> {
+    let mut models: Vec<OrmModel> = Vec::new();
+    let mut abstract_bases: HashMap<String, Vec<Column>> = HashMap::new();
+    for cap in PYTHON_CLASS.captures_iter(content) {
+        let name = cap[1].to_string();
+        let bases: Vec<&str> = cap[2].split(',').map(str::trim).collect();
+        let line_start = line_of(content, cap.get(0).unwrap().start());
+        let (body_text, body) = python_class_body(content, line_start);
+
+        let concrete_parent = models
+            .iter()
+            .find(|m| m.orm == "django" && bases.contains(&m.name.as_str()));
+        if bases.contains(&"models.Model")
+            || concrete_parent.is_some()
+            || bases.iter().any(|b| abstract_bases.contains_key(*b))
+        {
+            let app = django_app_label(file);
+            let table = DJANGO_DB_TABLE
+                .captures(&body_text)
+                .map_or_else(|| format!("{app}_{}", name.to_lowercase()), |c| c[1].to_string());
+            let own: Vec<Column> = body.iter().filter_map(|s| django_column(s, &app, &table)).collect();
+            let mut columns: Vec<Column> = bases
+                .iter()
+                .filter_map(|b| abstract_bases.get(*b))
+                .flatten()
+                .filter(|inherited| !own.iter().any(|c| c.name == inherited.name))
+                .cloned()
+                .collect();
+            columns.extend(own);
+            if DJANGO_ABSTRACT.is_match(&body_text) {
+                abstract_bases.insert(name, columns);
+                continue;
+            }
+            if let Some(parent) = concrete_parent {
+                // Multi-table inheritance: the parent link is the primary key
+                columns.insert(0, Column {
+                    name: format!("{}_ptr_id", parent.name.to_lowercase()),
+                    type_name: Some("OneToOneField".to_string()),
+                    primary_key: true,
+                    foreign_key: Some(format!("{}.id", parent.table)),
+                    ..Default::default()
+                });
+            } else if !columns.iter().any(|c| c.primary_key) {
+                // Django adds an implicit auto-incrementing `id`
+                columns.insert(0, Column {
+                    name: "id".to_string(),
+                    type_name: Some("AutoField".to_string()),
+                    primary_key: true,
+                    ..Default::default()
+                });
+            }
+            models.push(OrmModel { name, orm: "django", table, columns, line_start });
+            continue;
+        }
+
+        let tablename = TABLENAME.captures(&body_text).map(|c| c[1].to_string());
+        let columns: Vec<Column> = body.iter().filter_map(|s| sqlalchemy_column(s)).collect();
+        let declarative = tablename.is_some() || columns.iter().any(|c| c.primary_key);
+        if !declarative || columns.is_empty() {
+            continue;
+        }
+        let table = tablename.unwrap_or_else(|| snake_case(&name));
+        models.push(OrmModel { name, orm: "sqlalchemy", table, columns, line_start });
+    }
+    models
+}
+
+// ---------------------------------------------------------------------------
+// TypeORM
+// ---------------------------------------------------------------------------
+
+const TYPEORM_COLUMNS: &[&str] = &[
+    "Column", "PrimaryColumn", "PrimaryGeneratedColumn", "CreateDateColumn", "UpdateDateColumn",
+    "DeleteDateColumn", "VersionColumn", "ObjectIdColumn",
+];
+
+fn typeorm_models(content: &str) -> Vec<OrmModel> {
+    let mut models = Vec::new();
+    for cap in TYPEORM_ENTITY.captures_iter(content) {
+        let name = cap[2].to_string();
+        let args = cap[1].trim();
+        let table = if QUOTED.is_match(args) {
+            unquote(args).to_string()
+        } else {
+            TS_OBJECT_NAME
+                .captures(args)
+                .map_or_else(|| snake_case(&name), |c| c[1].to_string())
+        };
+        let class_start = cap.get(2).unwrap().start();
+        let Some(body) = delimited(content, class_start, b'{', b'}') else {
+            continue;
+        };
+
+        let mut columns = Vec::new();
+        let mut decorators: Vec<&str> = Vec::new();
+        for member in split_top_level(body, b";\n", true, false) {
+            let member = member.trim();
+            if member.starts_with('@') {
+                decorators.push(member);
+                continue;
+            }
+            let pending = std::mem::take(&mut decorators);
+            let Some(colon) = find_top_level(member, b':', true) else {
+                continue;
+            };
+            let prop = member[..colon].trim().trim_end_matches(['?', '!']);
+            if !prop.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                continue;
+            }
+            let ts_type = member[colon + 1..].split('=').next().unwrap_or("").trim();
+            let decorator = |n: &str| {
+                pending.iter().find(|d| d[1..].starts_with(n) && d[1 + n.len()..].trim_start().starts_with('('))
+            };
+
+            if let Some(col) = TYPEORM_COLUMNS.iter().find_map(|n| decorator(n).map(|d| (n, d))) {
+                let (kind, text) = col;
+                columns.push(Column {
+                    name: TS_OBJECT_NAME.captures(text).map_or(prop.to_string(), |c| c[1].to_string()),
+                    type_name: Some(ts_type.to_string()),
+                    primary_key: kind.starts_with("Primary"),
+                    optional: text.contains("nullable: true") || member[..colon].ends_with('?'),
+                    ..Default::default()
+                });
+            } else if let Some(relation) = decorator("ManyToOne").or_else(|| decorator("OneToOne")) {
+                // The owning side holds the join column
+                let join = decorator("JoinColumn");
+                if relation.starts_with("@OneToOne") && join.is_none() {
+                    continue;
+                }
+                let Some(target) = ARROW_TARGET.captures(relation).map(|c| c[1].to_string()) else {
+                    continue;
+                };
+                let column = join
+                    .and_then(|j| TS_OBJECT_NAME.captures(j))
+                    .map_or_else(|| format!("{prop}Id"), |c| c[1].to_string());
+                columns.push(Column {
+                    name: column,
+                    foreign_key: Some(format!("{target}.id")),
+                    optional: relation.contains("nullable: true"),
+                    ..Default::default()
+                });
+            }
+        }
+        let line_start = line_of(content, cap.get(0).unwrap().start() + (cap[0].len() - cap[0].trim_start().len()));
+        models.push(OrmModel { name, orm: "typeorm", table, columns, line_start });
+    }
+    models
+}
+
+// ---------------------------------------------------------------------------
+// Prisma
+// ---------------------------------------------------------------------------
+
+fn prisma_models(content: &str) -> Vec<OrmModel> {
+    let names: Vec<&str> = PRISMA_MODEL.captures_iter(content).map(|c| c.get(1).unwrap().as_str()).collect();
+    let mut models = Vec::new();
+    for cap in PRISMA_MODEL.captures_iter(content) {
+        let name = cap[1].to_string();
+        let start = cap.get(0).unwrap().start();
+        let Some(body) = delimited(content, start, b'{', b'}') else {
+            continue;
+        };
+        let mut table = name.clone();
+        let mut columns: Vec<Column> = Vec::new();
+        let mut relations: Vec<(Vec<String>, String, Vec<String>)> = Vec::new();
+        let mut compound_key: Vec<String> = Vec::new();
+
+        for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if line.starts_with("@@") {
+                if let Some(m) = PRISMA_MAP.captures(line) {
+                    table = m[1].to_string();
+                } else if let Some(rest) = line.strip_prefix("@@id(") {
+                    compound_key = delimited(rest, 0, b'[', b']')
+                        .map(|k| k.split(',').map(|c| c.trim().to_string()).collect())
+                        .unwrap_or_default();
+                }
+                continue;
+            }
+            let Some(field) = PRISMA_FIELD.captures(line) else {
+                continue;
+            };
+            let ty = &field[2];
+            let attrs = &field[5];
+            if let Some(rel) = PRISMA_RELATION.captures(attrs) {
+                let split = |s: &str| s.split(',').map(|c| c.trim().to_string()).collect::<Vec<_>>();
+                relations.push((split(&rel[1]), ty.to_string(), split(&rel[2])));
+            }
+            // Relation fields are navigations, not columns
+            if names.contains(&ty) {
+                continue;
+            }
+            let column_name = PRISMA_MAP.captures(attrs).map_or(field[1].to_string(), |m| m[1].to_string());
+            let default = attrs
+                .find("@default(")
+                .and_then(|i| delimited(attrs, i, b'(', b')'))
+                .map(String::from);
+            columns.push(Column {
+                name: column_name,
+                type_name: Some(format!("{ty}{}", field.get(3).map_or("", |m| m.as_str()))),
+                primary_key: attrs.split_whitespace().any(|a| a == "@id"),
+                optional: field.get(4).is_some(),
+                default,
+                ..Default::default()
+            });
+        }
+        for column in columns.iter_mut() {
+            column.primary_key |= compound_key.contains(&column.name);
+        }
+        for (fields, target, references) in relations {
+            for (field, reference) in fields.iter().zip(references.iter()) {
+                if let Some(column) = columns.iter_mut().find(|c| &c.name == field) {
+                    column.foreign_key = Some(format!("{target}.{reference}"));
+                }
+            }
+        }
+        models.push(OrmModel { name, orm: "prisma", table, columns, line_start: line_of(content, start) });
+    }
+    models
+}
+
+// ---------------------------------------------------------------------------
+// Diesel
+// ---------------------------------------------------------------------------
+
+fn diesel_tables(content: &str) -> Vec<OrmModel> {
+    let mut models = Vec::new();
+    for m in DIESEL_TABLE_MACRO.find_iter(content) {
+        let Some(body) = delimited(content, m.start(), b'{', b'}') else {
+            continue;
+        };
+        let body_start = m.start() + content[m.start()..].find('{').unwrap_or(0) + 1;
+        for table in DIESEL_TABLE.captures_iter(body) {
+            let name = table[1].to_string();
+            let keys: Vec<&str> = table[2].split(',').map(str::trim).collect();
+            let columns = table[3]
+                .split(',')
+                .filter_map(|c| c.split_once("->"))
+                .map(|(col, ty)| {
+                    let col = col.trim().rsplit(']').next().unwrap_or(col).trim().to_string();
+                    let ty = ty.trim();
+                    Column {
+                        primary_key: keys.contains(&col.as_str()),
+                        optional: ty.starts_with("Nullable<"),
+                        type_name: Some(ty.to_string()),
+                        name: col,
+                        ..Default::default()
+                    }
+                })
+                .collect();
+            let offset = body_start + table.get(1).unwrap().start();
+            models.push(OrmModel {
+                table: name.clone(),
+                name,
+                orm: "diesel",
+                columns,
+                line_start: line_of(content, offset),
+            });
+        }
+    }
+    for join in DIESEL_JOINABLE.captures_iter(content) {
+        if let Some(model) = models.iter_mut().find(|m| m.table == join[1])
+            && let Some(column) = model.columns.iter_mut().find(|c| c.name == join[3])
+        {
+            column.foreign_key = Some(format!("{}.id", &join[2]));
+        }
+    }
+    models
+}
+
+/// Rust structs mapped with `#[diesel(table_name = users)]`
+fn diesel_structs(content: &str, components: &[DetectedComponent]) -> Vec<OrmModel> {
+    let lines: Vec<&str> = content.lines().collect();
+    components
+        .iter()
+        .filter(|c| c.kind == ComponentKind::Model)
+        .filter_map(|comp| {
+            let start = comp.source.line_start? as usize;
+            let end = comp.source.line_end.map_or(start, |e| e as usize).min(lines.len());
+            let text = lines.get(start - 1..end)?.join("\n");
+            let table = DIESEL_STRUCT_TABLE.captures(&text)?[1].to_string();
+            let columns = comp
+                .model_fields
+                .iter()
+                .flatten()
+                .map(|f| Column {
+                    name: f.name.clone(),
+                    type_name: f.type_name.clone(),
+                    optional: f.optional,
+                    ..Default::default()
+                })
+                .collect();
+            Some(OrmModel { name: comp.name.clone(), orm: "diesel", table, columns, line_start: start as u32 })
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// GORM
+// ---------------------------------------------------------------------------
+
+/// Go types that map to a single column rather than a relation
+fn is_go_scalar(ty: &str) -> bool {
+    let base = ty.trim_start_matches(['*', '[', ']']);
+    base.starts_with(|c: char| c.is_lowercase()) || base.contains('.')
+}
+
+fn gorm_models(content: &str, components: &[DetectedComponent]) -> Vec<OrmModel> {
+    let table_names: HashMap<String, String> = GORM_TABLE_NAME
+        .captures_iter(content)
+        .map(|c| (c[1].to_string(), c[2].to_string()))
+        .collect();
+
+    let mut models = Vec::new();
+    for comp in components.iter().filter(|c| c.kind == ComponentKind::Model && c.language == "go") {
+        let Some(fields) = comp.model_fields.as_ref() else {
+            continue;
+        };
+        let embeds_model = fields.iter().any(|f| f.type_name.as_deref() == Some("gorm.Model"));
+        if !embeds_model && !fields.iter().any(|f| f.tags.contains_key("gorm")) {
+            continue;
+        }
+
+        let mut columns = Vec::new();
+        if embeds_model {
+            columns.push(Column { name: "id".to_string(), type_name: Some("uint".to_string()), primary_key: true, ..Default::default() });
+            for name in ["created_at", "updated_at", "deleted_at"] {
+                columns.push(Column { name: name.to_string(), type_name: Some("time.Time".to_string()), ..Default::default() });
+            }
+        }
+        let gorm_opt = |f: &ModelField, key: &str| -> Option<String> {
+            f.tags.get("gorm")?.split(';').find_map(|o| {
+                let (k, v) = o.split_once(':').unwrap_or((o, ""));
+                k.trim().eq_ignore_ascii_case(key).then(|| v.trim().to_string())
+            })
+        };
+        for field in fields {
+            let ty = field.type_name.as_deref().unwrap_or("");
+            if ty == "gorm.Model" || field.tags.get("gorm").is_some_and(|g| g == "-") {
+                continue;
+            }
+            if !is_go_scalar(ty) {
+                // Belongs-to: `User User` with a sibling `UserID`, or an explicit foreignKey tag.
+                // Has-many slices are owned by the other side.
+                if ty.starts_with("[]") {
+                    continue;
+                }
+                let target = ty.trim_start_matches('*');
+                let fk_field = gorm_opt(field, "foreignKey").unwrap_or_else(|| format!("{}ID", field.name));
+                let references = gorm_opt(field, "references").map_or("id".to_string(), |r| snake_case(&r));
+                let fk_column = snake_case(&fk_field);
+                if let Some(fk) = fields.iter().find(|f| f.name == fk_field) {
+                    let column = gorm_opt(fk, "column").unwrap_or(fk_column);
+                    columns.retain(|c: &Column| c.name != column);
+                    columns.push(Column {
+                        name: column,
+                        type_name: fk.type_name.clone(),
+                        foreign_key: Some(format!("{target}.{references}")),
+                        optional: fk.optional,
+                        ..Default::default()
+                    });
+                }
+                continue;
+            }
+            let column = gorm_opt(field, "column").unwrap_or_else(|| snake_case(&field.name));
+            if columns.iter().any(|c: &Column| c.name == column) {
+                continue;
+            }
+            columns.push(Column {
+                primary_key: gorm_opt(field, "primaryKey").is_some() || gorm_opt(field, "primary_key").is_some() || field.name == "ID",
+                type_name: field.type_name.clone(),
+                optional: field.optional,
+                default: gorm_opt(field, "default"),
+                name: column,
+                ..Default::default()
+            });
+        }
+        let table = table_names
+            .get(&comp.name)
+            .cloned()
+            .unwrap_or_else(|| pluralize(&snake_case(&comp.name)));
+        models.push(OrmModel {
+            name: comp.name.clone(),
+            orm: "gorm",
+            table,
+            columns,
+            line_start: comp.source.line_start.unwrap_or(1),
+        });
+    }
+    models
+}
+
+// ---------------------------------------------------------------------------
+// Component annotation
+// ---------------------------------------------------------------------------
+
+/// Record a table's name and its column, primary key and foreign key lists.
+pub(crate) fn insert_table_metadata(metadata: &mut HashMap<String, String>, table: &str, columns: &[Column]) {
+    let join = |f: &dyn Fn(&Column) -> Option<String>| -> String {
+        columns.iter().filter_map(f).collect::<Vec<_>>().join(",")
+    };
+    metadata.insert("table".to_string(), table.to_string());
+    metadata.insert("columns".to_string(), join(&|c| Some(c.name.clone())));
+    let primary_key = join(&|c| c.primary_key.then(|| c.name.clone()));
+    if !primary_key.is_empty() {
+        metadata.insert("primary_key".to_string(), primary_key);
+    }
+    let foreign_keys = join(&|c| c.foreign_key.as_ref().map(|fk| format!("{}->{fk}", c.name)));
+    if !foreign_keys.is_empty() {
+        metadata.insert("foreign_keys".to_string(), foreign_keys);
+    }
+}
+
+pub(crate) fn column_fields(columns: &[Column]) -> Vec<ModelField> {
+    columns
+        .iter()
+        .map(|c| ModelField {
+            name: c.name.clone(),
+            type_name: c.type_name.clone(),
+            optional: c.optional,
+            default: c.default.clone(),
+            tags: HashMap::new(),
+        })
+        .collect()
+}
+
+fn apply(model: &OrmModel, comp: &mut DetectedComponent) {
+    comp.metadata.insert("orm".to_string(), model.orm.to_string());
+    insert_table_metadata(&mut comp.metadata, &model.table, &model.columns);
+    if comp.model_fields.is_none() && !model.columns.is_empty() {
+        comp.model_fields = Some(column_fields(&model.columns));
+    }
+}
+
+/// Point Django foreign keys at the table their target model really uses. A
+/// reference is written as the target's default `<app>_<model>` table, which a
+/// `Meta.db_table` on the target, possibly in another file, overrides.
+pub fn resolve_django_tables(components: &mut [DetectedComponent]) {
+    let is_django = |c: &DetectedComponent| c.metadata.get("orm").is_some_and(|o| o == "django");
+    let tables: HashMap<String, String> = components
+        .iter()
+        .filter(|c| is_django(c))
+        .filter_map(|c| {
+            let table = c.metadata.get("table")?;
+            let default = format!("{}_{}", django_app_label(&c.source.file), c.name.to_lowercase());
+            (default != *table).then(|| (default, table.clone()))
+        })
+        .collect();
+    if tables.is_empty() {
+        return;
+    }
+    for comp in components.iter_mut().filter(|c| is_django(c)) {
+        let Some(keys) = comp.metadata.get_mut("foreign_keys") else {
+            continue;
+        };
+        let resolved: Vec<String> = keys
+            .split(',')
+            .map(|key| {
+                let target = key.split_once("->").and_then(|(column, reference)| {
+                    let (table, target_column) = reference.rsplit_once('.')?;
+                    Some(format!("{column}->{}.{target_column}", tables.get(table)?))
+                });
+                target.unwrap_or_else(|| key.to_string())
+            })
+            .collect();
+        *keys = resolved.join(",");
+    }
+}
+
+/// Detect ORM table definitions in a file. Models already found by the model
+/// detectors are annotated in place; ORM classes they missed are added, replacing
+/// the service the class heuristic guessed for the same class.
+pub fn annotate_orm_models(
+    components: &mut Vec<DetectedComponent>,
+    content: &str,
+    language: &str,
+    file: &str,
+) {
+    let found = match language {
+        "python" => python_models(content, file),
+        "typescript" | "javascript" => typeorm_models(content),
+        "prisma" => prisma_models(content),
+        "rust" => {
+            let mut models = diesel_tables(content);
+            models.extend(diesel_structs(content, components));
+            models
+        }
+        "go" => gorm_models(content, components),
+        _ => return,
+    };
+
+    for model in found {
+        if let Some(existing) = components
+            .iter_mut()
+            .find(|c| c.kind == ComponentKind::Model && c.name == model.name)
+        {
+            apply(&model, existing);
+            continue;
+        }
+        components.retain(|c| {
+            !(c.kind == ComponentKind::Service
+                && c.name == model.name
+                && c.source.file == file
+                && c.metadata.get("detection").map(String::as_str) == Some("class_heuristic"))
+        });
+        let mut comp = DetectedComponent {
+            id: make_id("model", &model.name, file),
+            name: model.name.clone(),
+            kind: ComponentKind::Model,
+            language: language.to_string(),
+            source: SourceLocation {
+                file: file.to_string(),
+                line_start: Some(model.line_start),
+                line_end: definition_end(content, language, model.line_start),
+            },
+            ..Default::default()
+        };
+        apply(&model, &mut comp);
+        components.push(comp);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotate(content: &str, language: &str, file: &str, mut components: Vec<DetectedComponent>) -> Vec<DetectedComponent> {
+        annotate_orm_models(&mut components, content, language, file);
+        components
+    }
+
+    fn meta<'a>(comp: &'a DetectedComponent, key: &str) -> Option<&'a str> {
+        comp.metadata.get(key).map(String::as_str)
+    }
+
+    #[test]
+    fn naming_helpers() {
+        assert_eq!(snake_case("UserID"), "user_id");
+        assert_eq!(snake_case("HTTPRequestLog"), "http_request_log");
+        assert_eq!(pluralize("category"), "categories");
+        assert_eq!(pluralize("address"), "addresses");
+        assert_eq!(pluralize("user"), "users");
+    }
+
+    #[test]
+    fn sqlalchemy_declarative_models() {
+        let content = r#"
+class User(Base):
+    __tablename__ = "users"
+
+    id: Mapped[int] = mapped_column(primary_key=True)
+    email: Mapped[Optional[str]]
+    posts: Mapped[List["Post"]] = relationship(back_populates="author")
+
+class Post(db.Model):
+    __tablename__ = "posts"
+    id = db.Column(db.Integer, primary_key=True)
+    author_id = db.Column(
+        db.Integer, db.ForeignKey("users.id"), nullable=False
+    )
+    title = Column("post_title", String(200), default="untitled")
+"#;
+        let comps = annotate(content, "python", "app/models.py", Vec::new());
+        assert_eq!(comps.len(), 2);
+        assert_eq!(meta(&comps[0], "orm"), Some("sqlalchemy"));
+        assert_eq!(meta(&comps[0], "table"), Some("users"));
+        assert_eq!(meta(&comps[0], "columns"), Some("id,email"));
+        assert_eq!(meta(&comps[0], "primary_key"), Some("id"));
+        assert_eq!(meta(&comps[1], "columns"), Some("id,author_id,post_title"));
+        assert_eq!(meta(&comps[1], "foreign_keys"), Some("author_id->users.id"));
+        let fields = comps[1].model_fields.as_ref().unwrap();
+        assert_eq!(fields[2].default.as_deref(), Some("\"untitled\""));
+    }
+
+    #[test]
+    fn orm_class_replaces_the_heuristic_service_guess() {
+        let content = "class User(Base):\n    __tablename__ = \"users\"\n    id = Column(Integer, primary_key=True)\n";
+        let masked = crate::scanner::lexer::mask(content, "python");
+        let services = crate::scanner::services::detect_services(&masked.text, &masked.code, "python", "app/models.py");
+        assert_eq!(services.len(), 1);
+
+        let comps = annotate(content, "python", "app/models.py", services);
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].kind, ComponentKind::Model);
+        assert_eq!(comps[0].id, make_id("model", "User", "app/models.py"));
+        assert_eq!(meta(&comps[0], "table"), Some("users"));
+    }
+
+    #[test]
+    fn django_models_with_implicit_ids() {
+        let content = r#"
+class Author(models.Model):
+    name = models.CharField(max_length=100)
+
+class Book(models.Model):
+    author = models.ForeignKey(Author, on_delete=models.CASCADE)
+    editor = models.ForeignKey("accounts.User", null=True, on_delete=models.SET_NULL)
+    tags = models.ManyToManyField("Tag")
+
+    class Meta:
+        db_table = "library_books"
+
+class Base(models.Model):
+    class Meta:
+        abstract = True
+"#;
+        let comps = annotate(content, "python", "library/models.py", Vec::new());
+        assert_eq!(comps.len(), 2);
+        assert_eq!(meta(&comps[0], "table"), Some("library_author"));
+        assert_eq!(meta(&comps[0], "columns"), Some("id,name"));
+        assert_eq!(meta(&comps[1], "table"), Some("library_books"));
+        assert_eq!(
+            meta(&comps[1], "foreign_keys"),
+            Some("author_id->library_author.id,editor_id->accounts_user.id")
+        );
+    }
+
+    #[test]
+    fn django_foreign_keys_use_the_target_db_table() {
+        let customers = "class Customer(models.Model):\n    name = models.CharField(max_length=80)\n\n    class Meta:\n        db_table = \"customers\"\n";
+        let orders = "class Order(models.Model):\n    customer = models.ForeignKey(\"crm.Customer\", on_delete=models.CASCADE)\n";
+        let mut comps = annotate(customers, "python", "crm/models.py", Vec::new());
+        comps.extend(annotate(orders, "python", "shop/models.py", Vec::new()));
+        assert_eq!(meta(&comps[1], "foreign_keys"), Some("customer_id->crm_customer.id"));
+
+        resolve_django_tables(&mut comps);
+        assert_eq!(meta(&comps[1], "foreign_keys"), Some("customer_id->customers.id"));
+        let edges = crate::scanner::relationships::infer_foreign_key_edges(&comps);
+        assert_eq!((edges[0].from_id.as_str(), edges[0].to_id.as_str()), (comps[1].id.as_str(), comps[0].id.as_str()));
+    }
+
+    #[test]
+    fn django_models_inherit_from_base_models_in_the_file() {
+        let content = r#"
+class TimeStamped(models.Model):
+    created = models.DateTimeField(auto_now_add=True)
+
+    class Meta:
+        abstract = True
+
+class Place(TimeStamped):
+    name = models.CharField(max_length=50)
+
+class Restaurant(Place):
+    serves_pizza = models.BooleanField(default=False)
+"#;
+        let comps = annotate(content, "python", "places/models.py", Vec::new());
+        let names: Vec<&str> = comps.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Place", "Restaurant"]);
+        assert_eq!(meta(&comps[0], "columns"), Some("id,created,name"));
+        assert_eq!(meta(&comps[1], "columns"), Some("place_ptr_id,serves_pizza"));
+        assert_eq!(meta(&comps[1], "primary_key"), Some("place_ptr_id"));
+        assert_eq!(meta(&comps[1], "foreign_keys"), Some("place_ptr_id->places_place.id"));
+    }
+
+    #[test]
+    fn typeorm_entities() {
+        let content = r#"
+@Entity({ name: "orders" })
+export class Order {
+  @PrimaryGeneratedColumn()
+  id: number;
+
+  @Column({ name: "total_cents", nullable: true })
+  total?: number;
+
+  @ManyToOne(() => Customer, (c) => c.orders)
+  @JoinColumn({ name: "customer_id" })
+  customer: Customer;
+
+  @OneToMany(() => LineItem, (li) => li.order)
+  items: LineItem[];
+}
+"#;
+        let comps = annotate(content, "typescript", "src/order.entity.ts", Vec::new());
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].source.line_start, Some(2));
+        assert_eq!(meta(&comps[0], "table"), Some("orders"));
+        assert_eq!(meta(&comps[0], "columns"), Some("id,total_cents,customer_id"));
+        assert_eq!(meta(&comps[0], "primary_key"), Some("id"));
+        assert_eq!(meta(&comps[0], "foreign_keys"), Some("customer_id->Customer.id"));
+    }
+
+    #[test]
+    fn prisma_schema_models() {
+        let content = r#"
+model User {
+  id    Int     @id @default(autoincrement())
+  email String  @unique
+  posts Post[]
+  @@map("users")
+}
+
+model Post {
+  id       Int    @id
+  author   User   @relation(fields: [authorId], references: [id])
+  authorId Int    @map("author_id")
+  title    String?
+}
+"#;
+        let comps = annotate(content, "prisma", "prisma/schema.prisma", Vec::new());
+        assert_eq!(comps.len(), 2);
+        assert_eq!(meta(&comps[0], "table"), Some("users"));
+        assert_eq!(meta(&comps[0], "columns"), Some("id,email"));
+        assert_eq!(comps[0].model_fields.as_ref().unwrap()[0].default.as_deref(), Some("autoincrement()"));
+        assert_eq!(meta(&comps[1], "columns"), Some("id,author_id,title"));
+        assert_eq!(meta(&comps[1], "foreign_keys"), None);
+    }
+
+    #[test]
+    fn diesel_schema_and_mapped_structs() {
+        let content = r#"
+diesel::table! {
+    users (id) {
+        id -> Int4,
+        name -> Varchar,
+    }
+}
+
+diesel::table! {
+    posts (id) {
+        id -> Int4,
+        user_id -> Int4,
+        body -> Nullable<Text>,
+    }
+}
+
+diesel::joinable!(posts -> users (user_id));
+"#;
+        let comps = annotate(content, "rust", "src/schema.rs", Vec::new());
+        assert_eq!(comps.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), vec!["users", "posts"]);
+        assert_eq!(meta(&comps[1], "foreign_keys"), Some("user_id->users.id"));
+        assert!(comps[1].model_fields.as_ref().unwrap()[2].optional);
+    }
+
+    #[test]
+    fn gorm_tags_and_embedded_model() {
+        let content = "package m\n\ntype Order struct {\n\tgorm.Model\n\tCustomerID uint\n\tCustomer   Customer\n\tCode string `gorm:\"column:order_code;default:'x'\"`\n\tItems []Item\n}\n\nfunc (Order) TableName() string { return \"shop_orders\" }\n";
+        let index = crate::scanner::syntax::parse(content, "go", "m/order.go").unwrap();
+        let models = crate::scanner::models::detect_models_from_syntax(content, "go", "m/order.go", &index);
+        let comps = annotate(content, "go", "m/order.go", models);
+        assert_eq!(comps.len(), 1);
+        assert_eq!(meta(&comps[0], "orm"), Some("gorm"));
+        assert_eq!(meta(&comps[0], "table"), Some("shop_orders"));
+        assert_eq!(
+            meta(&comps[0], "columns"),
+            Some("id,created_at,updated_at,deleted_at,customer_id,order_code")
+        );
+        assert_eq!(meta(&comps[0], "foreign_keys"), Some("customer_id->Customer.id"));
+    }
+}
@@ -0,0 +1,321 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::output::schema::{DetectedComponent, Package};
+
+use super::file_walker::WalkedFile;
+
+// ---------------------------------------------------------------------------
+// Packages — members of npm/pnpm, Cargo, Go and Python workspaces
+// ---------------------------------------------------------------------------
+
+/// A package manifest and what it declares.
+#[derive(Debug, Default)]
+struct Manifest {
+    /// Package name, when the manifest defines a package
+    name: Option<String>,
+    /// Member globs, when the manifest declares a workspace
+    members: Vec<String>,
+    /// Member globs excluded from the workspace
+    exclude: Vec<String>,
+}
+
+/// Manifest file names by ecosystem.
+const MANAGERS: &[(&str, &str)] = &[
+    ("package.json", "npm"),
+    ("pnpm-workspace.yaml", "npm"),
+    ("Cargo.toml", "cargo"),
+    ("go.mod", "go"),
+    ("go.work", "go"),
+    ("pyproject.toml", "python"),
+];
+
+/// Find the packages of a monorepo. A workspace declaration (`package.json`
+/// `workspaces`, `pnpm-workspace.yaml`, Cargo `[workspace]`, `go.work`, uv
+/// `[tool.uv.workspace]`) limits its ecosystem to the declared members plus the
+/// root package; otherwise every named manifest is a package. A tree with a
+/// single package is not a monorepo and yields none.
+pub fn detect_packages(files: &[WalkedFile]) -> Vec<Package> {
+    // (directory, manager) → manifest, merged across files of the same ecosystem
+    let mut manifests: HashMap<(String, &str), Manifest> = HashMap::new();
+    for file in files {
+        let path = file.relative_path.replace('\\', "/");
+        if path.split('/').any(|s| s == "node_modules" || s == "vendor") {
+            continue;
+        }
+        let (dir, base) = match path.rsplit_once('/') {
+            Some((dir, base)) => (dir.to_string(), base),
+            None => (String::new(), path.as_str()),
+        };
+        let Some(&(_, manager)) = MANAGERS.iter().find(|(name, _)| *name == base) else {
+            continue;
+        };
+        let Ok(text) = std::fs::read_to_string(&file.path) else {
+            continue;
+        };
+        let parsed = parse_manifest(base, &text);
+        let entry = manifests.entry((dir, manager)).or_default();
+        if parsed.name.is_some() {
+            entry.name = parsed.name;
+        }
+        entry.members.extend(parsed.members);
+        entry.exclude.extend(parsed.exclude);
+    }
+
+    let mut packages: Vec<Package> = Vec::new();
+    for manager in ["npm", "cargo", "go", "python"] {
+        let workspaces: Vec<(&String, &Manifest)> = manifests
+            .iter()
+            .filter(|((_, m), manifest)| *m == manager && !manifest.members.is_empty())
+            .map(|((dir, _), manifest)| (dir, manifest))
+            .collect();
+        for ((dir, m), manifest) in &manifests {
+            if *m != manager {
+                continue;
+            }
+            let Some(name) = &manifest.name else {
+                continue;
+            };
+            let member = workspaces.is_empty()
+                || workspaces.iter().any(|(root, ws)| {
+                    *root == dir
+                        || (matches_any(root, &ws.members, dir) && !matches_any(root, &ws.exclude, dir))
+                });
+            if member {
+                packages.push(Package {
+                    name: name.clone(),
+                    path: dir.clone(),
+                    manager: manager.to_string(),
+                });
+            }
+        }
+    }
+
+    if packages.len() < 2 {
+        return Vec::new();
+    }
+    packages.sort_by(|a, b| (&a.path, &a.manager).cmp(&(&b.path, &b.manager)));
+    packages.dedup_by(|a, b| a.path == b.path);
+    packages
+}
+
+/// Set each component's `package` to the package with the deepest path
+/// containing its source file.
+pub fn assign_packages(components: &mut [DetectedComponent], packages: &[Package]) {
+    for comp in components.iter_mut() {
+        comp.package = packages
+            .iter()
+            .filter(|p| contains(&p.path, &comp.source.file))
+            .max_by_key(|p| p.path.len())
+            .map(|p| p.name.clone());
+    }
+}
+
+/// Whether `file` lies under the package directory `dir` (`""` is the root).
+fn contains(dir: &str, file: &str) -> bool {
+    dir.is_empty() || file.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn parse_manifest(base: &str, text: &str) -> Manifest {
+    match base {
+        "package.json" => {
+            let Ok(json) = serde_json::from_str::<serde_json::Value>(text) else {
+                return Manifest::default();
+            };
+            let workspaces = json.get("workspaces");
+            let members = workspaces
+                .and_then(|w| w.as_array().or_else(|| w.get("packages").and_then(|p| p.as_array())))
+                .map(|list| list.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                .unwrap_or_default();
+            Manifest {
+                name: json.get("name").and_then(|n| n.as_str()).map(str::to_string),
+                members,
+                exclude: Vec::new(),
+            }
+        }
+        "pnpm-workspace.yaml" => {
+            let (exclude, members): (Vec<String>, Vec<String>) =
+                yaml_list(text, "packages").into_iter().partition(|g| g.starts_with('!'));
+            Manifest {
+                name: None,
+                members,
+                exclude: exclude.into_iter().map(|g| g[1..].to_string()).collect(),
+            }
+        }
+        "Cargo.toml" | "pyproject.toml" => {
+            let Ok(toml) = text.parse::<toml::Table>() else {
+                return Manifest::default();
+            };
+            let get = |path: &[&str]| -> Option<&toml::Value> {
+                let mut value = toml.get(path[0])?;
+                for key in &path[1..] {
+                    value = value.get(key)?;
+                }
+                Some(value)
+            };
+            let strings = |path: &[&str]| -> Vec<String> {
+                get(path)
+                    .and_then(|v| v.as_array())
+                    .map(|list| list.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default()
+            };
+            let (name, workspace) = if base == "Cargo.toml" {
+                (get(&["package", "name"]), vec!["workspace"])
+            } else {
+                (
+                    get(&["project", "name"]).or_else(|| get(&["tool", "poetry", "name"])),
+                    vec!["tool", "uv", "workspace"],
+                )
+            };
+            let members_path: Vec<&str> = workspace.iter().copied().chain(["members"]).collect();
+            let exclude_path: Vec<&str> = workspace.iter().copied().chain(["exclude"]).collect();
+            Manifest {
+                name: name.and_then(|n| n.as_str()).map(str::to_string),
+                members: strings(&members_path),
+                exclude: strings(&exclude_path),
+            }
+        }
+        "go.mod" => Manifest {
+            name: text
+                .lines()
+                .find_map(|l| l.trim().strip_prefix("module "))
+                .map(|m| m.trim().trim_matches('"').to_string()),
+            ..Manifest::default()
+        },
+        "go.work" => Manifest {
+            members: go_work_uses(text),
+            ..Manifest::default()
+        },
+        _ => Manifest::default(),
+    }
+}
+
+/// Items of a top-level YAML block list (`key:` followed by `- item` lines).
+fn yaml_list(text: &str, key: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut inside = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with([' ', '\t', '-']) && !trimmed.is_empty() {
+            inside = trimmed.strip_suffix(':').is_some_and(|k| k.trim() == key);
+            continue;
+        }
+        if inside && let Some(item) = trimmed.strip_prefix('-') {
+            let item = item.split(" #").next().unwrap_or(item);
+            items.push(item.trim().trim_matches(|c| c == '"' || c == '\'').to_string());
+        }
+    }
+    items
+}
+
+/// Directories listed by `use ./dir` and `use ( ./a ./b )` in a go.work file.
+fn go_work_uses(text: &str) -> Vec<String> {
+    let mut uses = Vec::new();
+    let mut block = false;
+    for line in text.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if block {
+            if line == ")" {
+                block = false;
+            } else if !line.is_empty() {
+                uses.push(line.to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("use") {
+            let rest = rest.trim();
+            if rest == "(" {
+                block = true;
+            } else if !rest.is_empty() {
+                uses.push(rest.to_string());
+            }
+        }
+    }
+    uses
+}
+
+/// Whether `dir` matches one of the member globs declared in workspace `root`.
+fn matches_any(root: &str, globs: &[String], dir: &str) -> bool {
+    globs.iter().any(|glob| {
+        let glob = glob.trim_start_matches("./").trim_end_matches('/');
+        let full = if root.is_empty() || glob.is_empty() {
+            format!("{root}{glob}")
+        } else {
+            format!("{root}/{glob}")
+        };
+        let pattern = regex::escape(&full).replace(r"\*\*", ".*").replace(r"\*", "[^/]*");
+        Regex::new(&format!("^{pattern}$")).is_ok_and(|re| re.is_match(dir))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(files: &[(&str, &str)]) -> (std::path::PathBuf, Vec<WalkedFile>) {
+        let dir = std::env::temp_dir().join(format!("sysvista-packages-{}-{}", std::process::id(), files.len()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let walked = files
+            .iter()
+            .map(|(rel, content)| {
+                let path = dir.join(rel);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(&path, content).unwrap();
+                WalkedFile {
+                    path,
+                    relative_path: rel.to_string(),
+                }
+            })
+            .collect();
+        (dir, walked)
+    }
+
+    #[test]
+    fn npm_and_cargo_workspaces() {
+        let (dir, files) = walk(&[
+            ("package.json", r#"{"name": "root", "private": true, "workspaces": ["packages/*"]}"#),
+            ("packages/ui/package.json", r#"{"name": "@acme/ui"}"#),
+            ("packages/api/package.json", r#"{"name": "@acme/api"}"#),
+            ("examples/demo/package.json", r#"{"name": "demo"}"#),
+            ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/scratch\"]\n"),
+            ("crates/core/Cargo.toml", "[package]\nname = \"acme-core\"\n"),
+            ("crates/scratch/Cargo.toml", "[package]\nname = \"scratch\"\n"),
+        ]);
+        let packages = detect_packages(&files);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let found: Vec<(&str, &str, &str)> =
+            packages.iter().map(|p| (p.name.as_str(), p.path.as_str(), p.manager.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("root", "", "npm"),
+                ("acme-core", "crates/core", "cargo"),
+                ("@acme/api", "packages/api", "npm"),
+                ("@acme/ui", "packages/ui", "npm"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pnpm_go_work_and_python_projects() {
+        let (dir, files) = walk(&[
+            ("pnpm-workspace.yaml", "packages:\n  - 'apps/*'\n  - '!apps/legacy'\n"),
+            ("apps/web/package.json", r#"{"name": "web"}"#),
+            ("apps/legacy/package.json", r#"{"name": "legacy"}"#),
+            ("go.work", "go 1.22\n\nuse (\n\t./services/users\n)\n"),
+            ("services/users/go.mod", "module github.com/acme/users\n\ngo 1.22\n"),
+            ("tools/cli/pyproject.toml", "[project]\nname = \"acme-cli\"\n"),
+        ]);
+        let packages = detect_packages(&files);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["web", "github.com/acme/users", "acme-cli"]);
+    }
+
+    #[test]
+    fn package_paths_contain_whole_directories() {
+        assert!(contains("", "src/main.rs"));
+        assert!(contains("packages/ui", "packages/ui/src/index.ts"));
+        assert!(!contains("packages/ui", "packages/ui-kit/index.ts"));
+    }
+}
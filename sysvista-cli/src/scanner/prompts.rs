@@ -4,7 +4,9 @@ use std::sync::LazyLock;
 
 use crate::output::schema::{ComponentKind, DetectedComponent, SourceLocation};
 
+use super::lexer::is_code;
 use super::make_id;
+use super::spans::definition_end;
 
 // ---------------------------------------------------------------------------
 // Internal types for structural tracing
@@ -272,15 +274,15 @@ fn trace_sdk_call(content: &str, api_call_line: usize) -> TracedPromptContext {
     // Scan backward from the API call line
     for i in (lookback_start..call_idx).rev() {
         let line = lines.get(i).unwrap_or(&"");
-        if enclosing_function.is_none() {
-            if let Some(cap) = PYTHON_DEF.captures(line) {
-                let func_name = cap[1].to_string();
-                if func_name == "__init__" {
-                    // Skip __init__, keep looking for class name
-                    enclosing_function = None;
-                } else {
-                    enclosing_function = Some(func_name);
-                }
+        if enclosing_function.is_none()
+            && let Some(cap) = PYTHON_DEF.captures(line)
+        {
+            let func_name = cap[1].to_string();
+            if func_name == "__init__" {
+                // Skip __init__, keep looking for class name
+                enclosing_function = None;
+            } else {
+                enclosing_function = Some(func_name);
             }
         }
         if let Some(cap) = PYTHON_CLASS.captures(line) {
@@ -290,11 +292,11 @@ fn trace_sdk_call(content: &str, api_call_line: usize) -> TracedPromptContext {
     }
 
     // If enclosing function is still None (was __init__ or not found), use class name
-    if enclosing_function.is_none() {
-        if let Some(ref class_name) = enclosing_class {
-            // Convert class name: CamelCase → snake_case style
-            enclosing_function = Some(camel_to_snake(class_name));
-        }
+    if enclosing_function.is_none()
+        && let Some(ref class_name) = enclosing_class
+    {
+        // Convert class name: CamelCase → snake_case style
+        enclosing_function = Some(camel_to_snake(class_name));
     }
 
     // --- Full context window: find system kwarg, get_prompt, builder call ---
@@ -304,12 +306,12 @@ fn trace_sdk_call(content: &str, api_call_line: usize) -> TracedPromptContext {
 
     let system_prompt_source = SYSTEM_KWARG
         .captures(&context_window)
-        .and_then(|cap| {
+        .map(|cap| {
             let val = cap[1].trim().to_string();
             if let Some(gp) = GET_PROMPT_CALL.captures(&val) {
-                Some(format!("get_prompt({})", gp[1].trim()))
+                format!("get_prompt({})", gp[1].trim())
             } else {
-                Some(val)
+                val
             }
         });
 
@@ -379,10 +381,10 @@ fn resolve_subtype(
     }
 
     // 2. Keyword match on enclosing function name
-    if let Some(fname) = function_name {
-        if let Some(subtype) = match_keywords(fname) {
-            return subtype;
-        }
+    if let Some(fname) = function_name
+        && let Some(subtype) = match_keywords(fname)
+    {
+        return subtype;
     }
 
     // 3. Keyword match on file stem
@@ -404,6 +406,7 @@ fn resolve_subtype(
 
 pub fn detect_prompts(
     content: &str,
+    code: &str,
     language: &str,
     file: &str,
 ) -> Vec<DetectedComponent> {
@@ -415,6 +418,9 @@ pub fn detect_prompts(
     let mut builder_def_lines: HashSet<u32> = HashSet::new();
     for cap in BUILDER_DEF.captures_iter(content) {
         let match_start = cap.get(0).unwrap().start();
+        if !is_code(code, match_start) {
+            continue;
+        }
         let line_num = content[..match_start].lines().count() as u32 + 1;
         // Mark the definition and a window around it as covered
         for l in line_num..line_num + 20 {
@@ -425,6 +431,9 @@ pub fn detect_prompts(
     // Process instructor pattern first (more specific, has response_model=)
     for cap in INSTRUCTOR_PATTERN.captures_iter(content) {
         let match_start = cap.get(0).unwrap().start();
+        if !is_code(code, match_start) {
+            continue;
+        }
         let line_num = content[..match_start].lines().count() as u32 + 1;
 
         let ctx = trace_sdk_call(content, line_num as usize);
@@ -458,22 +467,20 @@ pub fn detect_prompts(
             source: SourceLocation {
                 file: file.to_string(),
                 line_start: Some(line_num),
-                line_end: None,
+                line_end: definition_end(content, language, line_num),
             },
             metadata,
-            transport_protocol: None,
-            http_method: None,
-            http_path: None,
-            model_fields: None,
             prompt_subtype: Some("extractor".to_string()),
-            consumes: None,
-            produces: None,
+            ..Default::default()
         });
     }
 
     // Process SDK API calls (skip lines already covered by instructor)
     for cap in SDK_API_CALL.captures_iter(content) {
         let match_start = cap.get(0).unwrap().start();
+        if !is_code(code, match_start) {
+            continue;
+        }
         let line_num = content[..match_start].lines().count() as u32 + 1;
 
         // Skip if already covered by instructor pattern
@@ -521,16 +528,11 @@ pub fn detect_prompts(
             source: SourceLocation {
                 file: file.to_string(),
                 line_start: Some(line_num),
-                line_end: None,
+                line_end: definition_end(content, language, line_num),
             },
             metadata,
-            transport_protocol: None,
-            http_method: None,
-            http_path: None,
-            model_fields: None,
             prompt_subtype: Some(subtype.to_string()),
-            consumes: None,
-            produces: None,
+            ..Default::default()
         });
     }
 
@@ -542,6 +544,9 @@ pub fn detect_prompts(
         for cap in pattern.regex.captures_iter(content) {
             let name = cap[pattern.name_group].to_string();
             let match_start = cap.get(0).unwrap().start();
+            if !is_code(code, match_start) {
+                continue;
+            }
             let line_num = content[..match_start].lines().count() as u32 + 1;
 
             // Skip if this line is already covered
@@ -564,16 +569,11 @@ pub fn detect_prompts(
                 source: SourceLocation {
                     file: file.to_string(),
                     line_start: Some(line_num),
-                    line_end: None,
+                    line_end: definition_end(content, language, line_num),
                 },
                 metadata,
-                transport_protocol: None,
-                http_method: None,
-                http_path: None,
-                model_fields: None,
                 prompt_subtype: Some(subtype.to_string()),
-                consumes: None,
-                produces: None,
+                ..Default::default()
             });
         }
     }
@@ -585,6 +585,11 @@ pub fn detect_prompts(
 mod tests {
     use super::*;
 
+    fn detect(content: &str, language: &str, file: &str) -> Vec<DetectedComponent> {
+        let masked = crate::scanner::lexer::mask(content, language);
+        detect_prompts(&masked.text, &masked.code, language, file)
+    }
+
     // -----------------------------------------------------------------------
     // Existing framework tests — unchanged behavior
     // -----------------------------------------------------------------------
@@ -599,7 +604,7 @@ route_prompt = ChatPromptTemplate.from_messages([
     ("human", "{input}"),
 ])
 "#;
-        let comps = detect_prompts(content, "python", "src/prompts/router.py");
+        let comps = detect(content, "python", "src/prompts/router.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "route_prompt");
         assert_eq!(comps[0].kind, ComponentKind::Prompt);
@@ -618,7 +623,7 @@ class TextGenerator:
         )
         return response.content[0].text
 "#;
-        let comps = detect_prompts(content, "python", "src/llm/generate.py");
+        let comps = detect(content, "python", "src/llm/generate.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "generate");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("generator"));
@@ -643,7 +648,7 @@ class CommentGenerator:
         )
         return response.content[0].text
 "#;
-        let comps = detect_prompts(content, "python", "comment_generator.py");
+        let comps = detect(content, "python", "comment_generator.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "generate");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("generator"));
@@ -670,7 +675,7 @@ class RelevanceFilter:
         result = json.loads(response.content[0].text)
         return result["score"] > 0.5
 "#;
-        let comps = detect_prompts(content, "python", "relevance_filter.py");
+        let comps = detect(content, "python", "relevance_filter.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "evaluate");
         // Structural: json.loads + "score" field → classifier
@@ -690,7 +695,7 @@ class CommentCritic:
         result = json.loads(response.content[0].text)
         return result["verdict"]
 "#;
-        let comps = detect_prompts(content, "python", "comment_critic.py");
+        let comps = detect(content, "python", "comment_critic.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "critique");
         // Structural: json.loads + "verdict" field → validator
@@ -708,7 +713,7 @@ class EntityExtractor:
             messages=[{"role": "user", "content": text}],
         )
 "#;
-        let comps = detect_prompts(content, "python", "src/extract.py");
+        let comps = detect(content, "python", "src/extract.py");
         let extractors: Vec<_> = comps
             .iter()
             .filter(|c| c.prompt_subtype.as_deref() == Some("extractor"))
@@ -724,7 +729,7 @@ class SentimentClassifier(dspy.Signature):
     text: str = dspy.InputField()
     sentiment: str = dspy.OutputField()
 "#;
-        let comps = detect_prompts(content, "python", "src/classify.py");
+        let comps = detect(content, "python", "src/classify.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "SentimentClassifier");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("classifier"));
@@ -738,7 +743,7 @@ const summaryPrompt = ChatPromptTemplate.fromMessages([
   ["human", "{text}"],
 ]);
 "#;
-        let comps = detect_prompts(content, "typescript", "src/prompts/summary.ts");
+        let comps = detect(content, "typescript", "src/prompts/summary.ts");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "summaryPrompt");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("summarizer"));
@@ -752,7 +757,7 @@ async def validate_output(text: str) -> bool:
     """Check if the output meets quality standards."""
     ...
 "#;
-        let comps = detect_prompts(content, "python", "src/validators.py");
+        let comps = detect(content, "python", "src/validators.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "validate_output");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("validator"));
@@ -763,7 +768,7 @@ async def validate_output(text: str) -> bool:
         let content = r#"
 reply_prompt = ChatPromptTemplate.from_template("Reply to: {message}")
 "#;
-        let comps = detect_prompts(content, "python", "src/chat.py");
+        let comps = detect(content, "python", "src/chat.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("generator"));
     }
@@ -776,7 +781,7 @@ def get_prompt(name: str) -> str:
     path = PROMPTS_DIR / f"{name}.md"
     return path.read_text().strip()
 "#;
-        let comps = detect_prompts(content, "python", "prompts/__init__.py");
+        let comps = detect(content, "python", "prompts/__init__.py");
         assert!(comps.is_empty());
     }
 
@@ -789,7 +794,7 @@ def process(data):
 class DataProcessor:
     pass
 "#;
-        let comps = detect_prompts(content, "python", "src/utils.py");
+        let comps = detect(content, "python", "src/utils.py");
         assert!(comps.is_empty());
     }
 
@@ -818,7 +823,7 @@ class RelevanceFilter:
         relevant = result["relevant"]
         return RelevanceResult(score=score, relevant=relevant)
 "#;
-        let comps = detect_prompts(content, "python", "relevance_filter.py");
+        let comps = detect(content, "python", "relevance_filter.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "evaluate");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("classifier"));
@@ -851,7 +856,7 @@ class CommentGenerator:
         )
         return response.content[0].text.strip()
 "#;
-        let comps = detect_prompts(content, "python", "comment_generator.py");
+        let comps = detect(content, "python", "comment_generator.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "generate");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("generator"));
@@ -879,7 +884,7 @@ class CommentCritic:
         feedback = parsed["feedback"]
         return CritiqueResult(verdict=verdict, feedback=feedback)
 "#;
-        let comps = detect_prompts(content, "python", "comment_critic.py");
+        let comps = detect(content, "python", "comment_critic.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "critique");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("validator"));
@@ -902,7 +907,7 @@ class TextFilter:
 "#;
         // No JSON parse, no .text → Unknown handling
         // "classify" in function name → classifier via keyword fallback
-        let comps = detect_prompts(content, "python", "filter.py");
+        let comps = detect(content, "python", "filter.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "classify_text");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("classifier"));
@@ -924,7 +929,7 @@ class Agent:
         return response
 "#;
         // No JSON, no .text → Unknown; "run" has no keyword; "agent.py" has no keyword → default generator
-        let comps = detect_prompts(content, "python", "agent.py");
+        let comps = detect(content, "python", "agent.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "run");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("generator"));
@@ -948,7 +953,7 @@ def _build_comment_prompt(result):
     """Build the prompt for generating an engagement comment."""
     return f"Draft a comment for: {result.message.content}"
 "#;
-        let comps = detect_prompts(content, "python", "comment_generator.py");
+        let comps = detect(content, "python", "comment_generator.py");
         // Only the API call component, NOT the builder function
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "generate");
@@ -968,7 +973,7 @@ summary_prompt = ChatPromptTemplate.from_messages([
     ("human", "{text}"),
 ])
 "#;
-        let comps = detect_prompts(content, "python", "src/summarize.py");
+        let comps = detect(content, "python", "src/summarize.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "summary_prompt");
         assert_eq!(comps[0].prompt_subtype.as_deref(), Some("summarizer"));
@@ -989,7 +994,7 @@ class ContentClassifier:
         confidence = data["confidence"]
         return {"label": label, "confidence": confidence}
 "#;
-        let comps = detect_prompts(content, "python", "classifier.py");
+        let comps = detect(content, "python", "classifier.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "classify");
         // json.loads + "label"/"confidence" → classifier
@@ -1034,7 +1039,7 @@ class DualGenerator:
         )
         return second.content[0].text
 "#;
-        let comps = detect_prompts(content, "python", "dual_generator.py");
+        let comps = detect(content, "python", "dual_generator.py");
         assert_eq!(comps.len(), 2, "both API calls should be detected");
         // Both should share the same enclosing function name
         assert_eq!(comps[0].name, "generate");
@@ -1055,9 +1060,18 @@ class RelevanceScorer:
         )
         return response.content[0].text
 "#;
-        let comps = detect_prompts(content, "python", "scorer.py");
+        let comps = detect(content, "python", "scorer.py");
         assert_eq!(comps.len(), 1);
         // __init__ skipped, class name used: RelevanceScorer → relevance_scorer
         assert_eq!(comps[0].name, "relevance_scorer");
     }
+
+    #[test]
+    fn skips_sdk_calls_inside_string_literals() {
+        let content = r#"
+def explain():
+    return "call it as response = client.messages.create(model=...)"
+"#;
+        assert!(detect(content, "python", "src/docs.py").is_empty());
+    }
 }
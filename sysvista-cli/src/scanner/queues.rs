@@ -0,0 +1,200 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::output::schema::{ComponentKind, DetectedComponent, SourceLocation, TransportProtocol};
+
+use super::lexer::is_code;
+use super::make_id;
+use super::orm::line_of;
+use super::spans::definition_end;
+
+// ---------------------------------------------------------------------------
+// Message queues — topic consumers (transports) and publishes (outbound)
+// ---------------------------------------------------------------------------
+
+/// A pattern whose first group is the topic or queue name.
+struct TopicPattern {
+    broker: &'static str,
+    regex: Regex,
+}
+
+fn topic(broker: &'static str, pattern: &str) -> TopicPattern {
+    TopicPattern {
+        broker,
+        regex: Regex::new(pattern).unwrap(),
+    }
+}
+
+static CONSUMER_PATTERNS: LazyLock<Vec<TopicPattern>> = LazyLock::new(|| {
+    vec![
+        // Spring listeners
+        topic("kafka", r#"@KafkaListener\s*\([^)]*?topics\s*=\s*\{?\s*"([^"]+)""#),
+        topic("rabbitmq", r#"@RabbitListener\s*\([^)]*?queues\s*=\s*\{?\s*"([^"]+)""#),
+        topic("sqs", r#"@SqsListener\s*\(\s*(?:value\s*=\s*)?\{?\s*"([^"]+)""#),
+        topic("jms", r#"@JmsListener\s*\([^)]*?destination\s*=\s*"([^"]+)""#),
+        // NestJS microservices
+        topic("nestjs", r#"@(?:EventPattern|MessagePattern)\s*\(\s*['"]([^'"]+)['"]"#),
+        // kafkajs, kafka-python, confluent-kafka, aiokafka
+        topic("kafka", r#"\.subscribe\s*\(\s*\{\s*topics?\s*:\s*\[?\s*['"]([^'"]+)['"]"#),
+        topic("kafka", r#"\b(?:AIO)?KafkaConsumer\s*\(\s*['"]([^'"]+)['"]"#),
+        topic("kafka", r#"\.subscribe\s*\(\s*\[\s*['"]([^'"]+)['"]"#),
+        topic("kafka", r#"\bkafka\.ReaderConfig\s*\{[^}]*?Topic\s*:\s*"([^"]+)""#),
+        // RabbitMQ (pika, amqplib)
+        topic("rabbitmq", r#"\bbasic_consume\s*\(\s*(?:queue\s*=\s*)?['"]([^'"]+)['"]"#),
+        topic("rabbitmq", r#"\bchannel\.consume\s*\(\s*['"]([^'"]+)['"]"#),
+        // NATS
+        topic("nats", r#"\.(?:Subscribe|QueueSubscribe|subscribe)\s*\(\s*["']([\w.>*-]+)["']\s*,"#),
+    ]
+});
+
+static PUBLISH_PATTERNS: LazyLock<Vec<TopicPattern>> = LazyLock::new(|| {
+    vec![
+        // kafkajs `producer.send({ topic: "x" })`, kafka-python `producer.send("x")`
+        topic("kafka", r#"\w*[Pp]roducer\w*\s*\.\s*send\s*\(\s*\{\s*topic\s*:\s*['"]([^'"]+)['"]"#),
+        topic("kafka", r#"\w*[Pp]roducer\w*\s*\.\s*(?:send|send_and_wait|produce)\s*\(\s*(?:topic\s*=\s*)?['"]([^'"]+)['"]"#),
+        topic("kafka", r#"\w*[Kk]afkaTemplate\s*\.\s*send\s*\(\s*"([^"]+)""#),
+        topic("kafka", r#"\b(?:kafka\.Writer|sarama\.ProducerMessage)\s*\{[^}]*?Topic\s*:\s*"([^"]+)""#),
+        // RabbitMQ
+        topic("rabbitmq", r#"\w*[Rr]abbitTemplate\s*\.\s*convertAndSend\s*\(\s*"([^"]+)""#),
+        topic("rabbitmq", r#"\bbasic_publish\s*\([^)]*?routing_key\s*=\s*['"]([^'"]+)['"]"#),
+        topic("rabbitmq", r#"\bchannel\.sendToQueue\s*\(\s*['"]([^'"]+)['"]"#),
+        // JMS / SQS
+        topic("jms", r#"\w*[Jj]msTemplate\s*\.\s*convertAndSend\s*\(\s*"([^"]+)""#),
+        topic("sqs", r#"\w*[Ss]qsTemplate\s*\.\s*send\s*\(\s*"([^"]+)""#),
+        // NATS
+        topic("nats", r#"\b(?:nc|nats|conn|js)\s*\.\s*(?:Publish|publish)\s*\(\s*["']([\w.-]+)["']"#),
+    ]
+});
+
+/// One message published to a topic or queue.
+#[derive(Debug, Clone)]
+pub struct QueuePublish {
+    pub topic: String,
+    pub broker: &'static str,
+    pub line: u32,
+}
+
+/// Topic and queue consumers, as `mq` transports named after the topic.
+pub fn detect_queue_consumers(content: &str, code: &str, language: &str, file: &str) -> Vec<DetectedComponent> {
+    let mut components: Vec<DetectedComponent> = Vec::new();
+    for pattern in CONSUMER_PATTERNS.iter() {
+        for cap in pattern.regex.captures_iter(content) {
+            if !is_code(code, cap.get(0).unwrap().start()) {
+                continue;
+            }
+            let name = cap[1].to_string();
+            let id = make_id("transport", &format!("mq:{name}"), file);
+            if components.iter().any(|c| c.id == id) {
+                continue;
+            }
+            let line = line_of(content, cap.get(0).unwrap().start());
+            components.push(DetectedComponent {
+                id,
+                name: name.clone(),
+                kind: ComponentKind::Transport,
+                language: language.to_string(),
+                source: SourceLocation {
+                    file: file.to_string(),
+                    line_start: Some(line),
+                    line_end: definition_end(content, language, line),
+                },
+                metadata: HashMap::from([
+                    ("topic".to_string(), name),
+                    ("broker".to_string(), pattern.broker.to_string()),
+                ]),
+                transport_protocol: Some(TransportProtocol::Mq),
+                ..Default::default()
+            });
+        }
+    }
+    components
+}
+
+/// Messages published to a literal topic or queue name, from calls that start
+/// in `code`.
+pub fn detect_queue_publishes(content: &str, code: &str) -> Vec<QueuePublish> {
+    let mut publishes: Vec<QueuePublish> = Vec::new();
+    for pattern in PUBLISH_PATTERNS.iter() {
+        for cap in pattern.regex.captures_iter(content) {
+            if !is_code(code, cap.get(0).unwrap().start()) {
+                continue;
+            }
+            let line = line_of(content, cap.get(0).unwrap().start());
+            if publishes.iter().any(|p| p.line == line && p.topic == cap[1]) {
+                continue;
+            }
+            publishes.push(QueuePublish {
+                topic: cap[1].to_string(),
+                broker: pattern.broker,
+                line,
+            });
+        }
+    }
+    publishes.sort_by_key(|p| p.line);
+    publishes
+}
+
+/// A node for a topic that no consumer in the scan reads. One node per topic.
+pub fn outbound_topic(publish: &QueuePublish, language: &str, file: &str) -> DetectedComponent {
+    DetectedComponent {
+        id: make_id("transport", &format!("outbound:mq:{}", publish.topic), ""),
+        name: publish.topic.clone(),
+        kind: ComponentKind::Transport,
+        language: language.to_string(),
+        source: SourceLocation {
+            file: file.to_string(),
+            line_start: Some(publish.line),
+            line_end: Some(publish.line),
+        },
+        metadata: HashMap::from([
+            ("direction".to_string(), "outbound".to_string()),
+            ("topic".to_string(), publish.topic.clone()),
+            ("broker".to_string(), publish.broker.to_string()),
+        ]),
+        transport_protocol: Some(TransportProtocol::Mq),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::lexer::mask;
+
+    #[test]
+    fn detects_consumers_and_publishes() {
+        let java = r#"
+@KafkaListener(topics = "orders.created", groupId = "billing")
+public void onOrder(OrderEvent event) {
+    kafkaTemplate.send("invoices.issued", invoice);
+}
+"#;
+        let masked = mask(java, "java");
+        let consumers = detect_queue_consumers(&masked.text, &masked.code, "java", "Billing.java");
+        assert_eq!(consumers.len(), 1);
+        assert_eq!(consumers[0].name, "orders.created");
+        assert_eq!(consumers[0].transport_protocol, Some(TransportProtocol::Mq));
+        assert_eq!(consumers[0].metadata.get("broker").map(String::as_str), Some("kafka"));
+
+        let publishes = detect_queue_publishes(&masked.text, &masked.code);
+        assert_eq!(publishes.len(), 1);
+        assert_eq!((publishes[0].topic.as_str(), publishes[0].line), ("invoices.issued", 4));
+
+        let py = "ch.basic_consume(queue='emails', on_message_callback=send)\nch.basic_publish(exchange='', routing_key='audit', body=b)\nproducer.send('orders.created', value=order)\n";
+        let masked = mask(py, "python");
+        let names: Vec<String> =
+            detect_queue_consumers(&masked.text, &masked.code, "python", "w.py").into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["emails"]);
+        let topics: Vec<String> = detect_queue_publishes(&masked.text, &masked.code).into_iter().map(|p| p.topic).collect();
+        assert_eq!(topics, vec!["audit", "orders.created"]);
+    }
+
+    #[test]
+    fn skips_consumers_and_publishes_inside_string_literals() {
+        let py = "USAGE = \"ch.basic_consume(queue='emails') then producer.send('audit')\"\n";
+        let masked = mask(py, "python");
+        assert!(detect_queue_consumers(&masked.text, &masked.code, "python", "w.py").is_empty());
+        assert!(detect_queue_publishes(&masked.text, &masked.code).is_empty());
+    }
+}
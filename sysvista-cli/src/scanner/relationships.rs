@@ -1,22 +1,23 @@
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 
-use crate::output::schema::{DetectedComponent, DetectedEdge};
+use crate::output::schema::{
+    ComponentKind, DetectedComponent, DetectedEdge, EdgeEvidence, EdgeRelation, SourceLocation, TransportProtocol,
+};
 
-// Import patterns for various languages
+use super::grpc_clients::{detect_grpc_stubs, outbound_service};
+use super::http_calls::{detect_http_calls, outbound_endpoint, path_segments};
+use super::language::detect_language;
+use super::modules::{Import, ModuleIndex};
+use super::orm::{line_of, pluralize, snake_case};
+use super::queues::{detect_queue_publishes, outbound_topic};
+
+// Import patterns for languages without a module resolver
 static IMPORT_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
     vec![
-        // TypeScript/JavaScript: import ... from "..."
-        Regex::new(r#"(?m)import\s+.*?from\s+['"]([^'"]+)['"]"#).unwrap(),
-        // TypeScript/JavaScript: require("...")
-        Regex::new(r#"(?m)require\s*\(\s*['"]([^'"]+)['"]"#).unwrap(),
-        // Rust: use crate::...
-        Regex::new(r"(?m)^use\s+(?:crate::)?(\S+);").unwrap(),
         // Python: from ... import ...
         Regex::new(r"(?m)^from\s+(\S+)\s+import").unwrap(),
-        // Go: import "..."
-        Regex::new(r#"(?m)import\s+(?:\w+\s+)?"([^"]+)""#).unwrap(),
     ]
 });
 
@@ -42,53 +43,215 @@ fn build_file_index(components: &[DetectedComponent]) -> HashMap<String, Vec<usi
 fn build_name_index(components: &[DetectedComponent]) -> HashMap<String, Vec<usize>> {
     let mut index: HashMap<String, Vec<usize>> = HashMap::new();
     for (i, comp) in components.iter().enumerate() {
+        // Migration tables are linked through `maps_to`; their names (`users`) are too common
+        if comp.metadata.contains_key("migrations") {
+            continue;
+        }
         index.entry(comp.name.clone()).or_default().push(i);
     }
     index
 }
 
+/// Build a map from workspace package name to the components in it
+fn build_package_index(components: &[DetectedComponent]) -> HashMap<&str, Vec<usize>> {
+    let mut index: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, comp) in components.iter().enumerate() {
+        if let Some(package) = &comp.package {
+            index.entry(package.as_str()).or_default().push(i);
+        }
+    }
+    index
+}
+
+/// File stems that make a file its package's (or directory's) entry module
+const ENTRY_STEMS: &[&str] = &["index", "lib", "__init__", "mod", "main"];
+
+/// Resolve an import that names a workspace package (`@acme/ui/button`,
+/// `acme_core::db::Pool`, `acme_cli.commands`, `github.com/acme/users/store`)
+/// to the components of the module it points at inside that package: the
+/// longest prefix of the remaining path matching a file or directory, or the
+/// package's entry files for a bare package import.
+fn resolve_package_import(
+    import: &str,
+    components: &[DetectedComponent],
+    packages: &HashMap<&str, Vec<usize>>,
+) -> Option<Vec<usize>> {
+    let mut names: Vec<&&str> = packages.keys().collect();
+    names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+    let (members, rest) = names.into_iter().find_map(|name| {
+        [name.to_string(), name.replace('-', "_")].into_iter().find_map(|key| {
+            let rest = import.strip_prefix(key.as_str())?;
+            let rest = match rest.strip_prefix("::").or_else(|| rest.strip_prefix(['/', '.'])) {
+                Some(rest) => rest,
+                None if rest.is_empty() => rest,
+                None => return None,
+            };
+            Some((&packages[*name], rest))
+        })
+    })?;
+
+    let module = |i: usize| {
+        let file = components[i].source.file.as_str();
+        file.rsplit_once('.').map_or(file, |(m, _)| m)
+    };
+    let segments: Vec<&str> = rest.split(['/', ':', '.']).filter(|s| !s.is_empty()).collect();
+    for k in (1..=segments.len()).rev() {
+        let prefix = segments[..k].join("/");
+        let ends = |path: &str| path == prefix || path.ends_with(&format!("/{prefix}"));
+        let hits: Vec<usize> = members
+            .iter()
+            .copied()
+            .filter(|&i| {
+                let module = module(i);
+                let dir = module.rsplit_once('/').map_or("", |(d, _)| d);
+                ends(module)
+                    || ends(dir)
+                    || ENTRY_STEMS.iter().any(|stem| module.strip_suffix(stem).is_some_and(|m| ends(m.trim_end_matches('/'))))
+            })
+            .collect();
+        if !hits.is_empty() {
+            return Some(hits);
+        }
+    }
+
+    // Bare package import: its entry files, else its shallowest files
+    let depth = |i: usize| components[i].source.file.matches('/').count();
+    let entries: Vec<usize> = members
+        .iter()
+        .copied()
+        .filter(|&i| ENTRY_STEMS.iter().any(|stem| module(i).rsplit('/').next() == Some(stem)))
+        .collect();
+    let pool = if entries.is_empty() { members.clone() } else { entries };
+    let shallowest = pool.iter().map(|&i| depth(i)).min()?;
+    Some(pool.into_iter().filter(|&i| depth(i) == shallowest).collect())
+}
+
 /// Read file contents and extract import paths, returning resolved file paths
-fn extract_imports(content: &str) -> Vec<String> {
+fn extract_imports(content: &str) -> Vec<(u32, String)> {
     let mut imports = Vec::new();
     for pattern in IMPORT_PATTERNS.iter() {
         for cap in pattern.captures_iter(content) {
-            imports.push(cap[1].to_string());
+            let line = line_of(content, cap.get(0).map_or(0, |m| m.start()));
+            imports.push((line, cap[1].to_string()));
         }
     }
     imports
 }
 
-/// Infer edges between components based on imports and type references
+/// Longest evidence snippet kept, in characters
+const MAX_SNIPPET: usize = 120;
+
+/// Evidence for an inferred edge: `heuristic` matched `snippet` (first line,
+/// trimmed and shortened) at `line` of `file`.
+fn evidence(heuristic: &str, file: &str, line: Option<u32>, snippet: &str) -> Option<EdgeEvidence> {
+    let snippet = snippet.lines().next().unwrap_or("").trim();
+    let snippet = match snippet.char_indices().nth(MAX_SNIPPET) {
+        Some((cut, _)) => format!("{}…", &snippet[..cut]),
+        None => snippet.to_string(),
+    };
+    Some(EdgeEvidence {
+        heuristic: heuristic.to_string(),
+        source: SourceLocation {
+            file: file.to_string(),
+            line_start: line,
+            line_end: None,
+        },
+        snippet: (!snippet.is_empty()).then_some(snippet),
+    })
+}
+
+/// Text of a 1-based line.
+fn line_text(content: &str, line: u32) -> &str {
+    content.lines().nth(line.saturating_sub(1) as usize).unwrap_or("")
+}
+
+/// Evidence at a definition, e.g. the model whose field names another.
+fn evidence_at(heuristic: &str, comp: &DetectedComponent, snippet: &str) -> Option<EdgeEvidence> {
+    evidence(heuristic, &comp.source.file, comp.source.line_start, snippet)
+}
+
+/// Infer edges between components based on imports and type references.
+/// Imports are read from `file_contents`; references only count occurrences in
+/// `code_contents`, where comments and string literals are masked, and
+/// resolved to files through `modules` where the language allows.
 pub fn infer_edges(
     components: &[DetectedComponent],
     file_contents: &HashMap<String, String>,
+    code_contents: &HashMap<String, String>,
+    modules: &ModuleIndex,
 ) -> Vec<DetectedEdge> {
     let mut edges = Vec::new();
     let file_index = build_file_index(components);
     let name_index = build_name_index(components);
+    let package_index = build_package_index(components);
+
+    // Components defined in a file under a given name, for named imports
+    let defines = |file: &str, name: &str| {
+        file_index
+            .get(file)
+            .is_some_and(|list| list.iter().any(|&i| components[i].source.file == file && components[i].name == name))
+    };
+    let in_file = |file: &str| -> Vec<usize> {
+        file_index
+            .get(file)
+            .map(|list| list.iter().copied().filter(|&i| components[i].source.file == file).collect())
+            .unwrap_or_default()
+    };
 
     // For each file, find imports and create edges
     for (file, content) in file_contents {
-        let imports = extract_imports(content);
-        let source_components: Vec<usize> = file_index.get(file.as_str()).cloned().unwrap_or_default();
-
-        for import_path in &imports {
-            // Try to resolve the import to a file in our index
-            let import_stem = std::path::Path::new(import_path)
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or(import_path);
-
-            // Find target components that might match this import
-            let target_indices: Vec<usize> = file_index
-                .get(import_stem)
-                .cloned()
-                .or_else(|| {
-                    // Try matching by last segment of path
-                    let last_segment = import_path.rsplit('/').next().unwrap_or(import_path);
-                    file_index.get(last_segment).cloned()
-                })
-                .unwrap_or_default();
+        // Datastores and outbound endpoints are found in a file but are not its code
+        let source_components: Vec<usize> = in_file(file)
+            .into_iter()
+            .filter(|&i| components[i].kind != ComponentKind::Datastore && !is_outbound(&components[i]))
+            .collect();
+        // Languages with a module resolver bind imports to files; the rest
+        // fall back to matching the specifier against packages and file stems
+        let imports = modules.imports(file, content).unwrap_or_else(|| {
+            extract_imports(content).into_iter().map(|(line, path)| (line, Import::Unresolved(path))).collect()
+        });
+
+        for (line, import) in &imports {
+            let (heuristic, target_indices): (&str, Vec<usize>) = match import {
+                // `use super::*` in a test module, a file importing itself
+                Import::Module { file: target, .. } if target == file => ("module_import", Vec::new()),
+                Import::Module { file: target, names: None } => ("module_import", in_file(target)),
+                Import::Module { file: target, names: Some(names) } => ("named_import", names
+                    .iter()
+                    .filter_map(|name| modules.export_origin(target, name, &defines))
+                    .flat_map(|(origin, name)| {
+                        in_file(&origin)
+                            .into_iter()
+                            .filter(move |&i| name == "*" || components[i].name == name)
+                    })
+                    .collect()),
+                Import::Unresolved(import_path) => {
+                    // Try to resolve the import to a file in our index
+                    let import_stem = std::path::Path::new(import_path)
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or(import_path);
+
+                    // Find target components that might match this import: a workspace
+                    // package by name first, then file stems, preferring the importer's package
+                    let by_package = resolve_package_import(import_path, components, &package_index);
+                    let heuristic = if by_package.is_some() { "package_import" } else { "import_stem" };
+                    let targets = by_package
+                        .or_else(|| {
+                            let by_stem = file_index.get(import_stem).cloned().or_else(|| {
+                                // Try matching by last segment of path
+                                let last_segment = import_path.rsplit('/').next().unwrap_or(import_path);
+                                file_index.get(last_segment).cloned()
+                            })?;
+                            let package = source_components.first().and_then(|&i| components[i].package.as_ref());
+                            let local: Vec<usize> =
+                                by_stem.iter().copied().filter(|&i| components[i].package.as_ref() == package).collect();
+                            Some(if package.is_some() && !local.is_empty() { local } else { by_stem })
+                        })
+                        .unwrap_or_default();
+                    (heuristic, targets)
+                }
+            };
 
             for &src_idx in &source_components {
                 for &tgt_idx in &target_indices {
@@ -98,6 +261,9 @@ pub fn infer_edges(
                             to_id: components[tgt_idx].id.clone(),
                             label: Some("imports".to_string()),
                             payload_type: None,
+                            evidence: evidence(heuristic, file, Some(*line), line_text(content, *line)),
+                            confidence: None,
+                            relations: Vec::new(),
                         });
                     }
                 }
@@ -105,6 +271,7 @@ pub fn infer_edges(
         }
 
         // Look for type name references in file content
+        let code = code_contents.get(file).map_or(content.as_str(), String::as_str);
         for &src_idx in &source_components {
             for (name, target_indices) in &name_index {
                 // Skip self-references and very short names (likely false positives)
@@ -114,7 +281,7 @@ pub fn infer_edges(
                 // Check if this type name appears in the file content as a word boundary match
                 let pattern = format!(r"\b{}\b", regex::escape(name));
                 if let Ok(re) = Regex::new(&pattern) {
-                    let matches: Vec<_> = re.find_iter(content).collect();
+                    let matches: Vec<_> = re.find_iter(code).collect();
                     // Need at least 2 matches to infer a reference (one is likely the definition)
                     let is_definition_file = target_indices
                         .iter()
@@ -122,6 +289,7 @@ pub fn infer_edges(
                     let threshold = if is_definition_file { 2 } else { 1 };
 
                     if matches.len() >= threshold {
+                        let line = line_of(code, matches[0].start());
                         for &tgt_idx in target_indices {
                             if src_idx != tgt_idx
                                 && components[tgt_idx].source.file != *file
@@ -131,6 +299,9 @@ pub fn infer_edges(
                                     to_id: components[tgt_idx].id.clone(),
                                     label: Some("references".to_string()),
                                     payload_type: None,
+                                    evidence: evidence("name_reference", file, Some(line), line_text(content, line)),
+                                    confidence: None,
+                                    relations: Vec::new(),
                                 });
                             }
                         }
@@ -140,13 +311,514 @@ pub fn infer_edges(
         }
     }
 
-    // Deduplicate edges
+    // Deduplicate edges, keeping both `imports` and `references` for a pair
+    edges.sort_by(|a, b| (&a.from_id, &a.to_id, &a.label).cmp(&(&b.from_id, &b.to_id, &b.label)));
+    edges.dedup_by(|a, b| a.from_id == b.from_id && a.to_id == b.to_id && a.label == b.label);
+
+    edges
+}
+
+/// Type identifiers, taking the last segment of qualified names (`models.User` → `User`)
+static TYPE_IDENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z_][\w]*(?:(?:\.|::)[A-Za-z_]\w*)*").unwrap());
+
+/// Markers of a collection or map type: a field typed with one of these holds many values
+const COLLECTION_MARKERS: &[&str] = &[
+    "Vec<", "VecDeque<", "HashSet<", "BTreeSet<", "HashMap<", "BTreeMap<", "[]", "map[", "list[",
+    "List[", "List<", "set[", "Set[", "Set<", "dict[", "Dict[", "Sequence[", "Iterable[", "tuple[",
+    "Tuple[", "Array<", "ReadonlyArray<", "Record<", "Map<", "repeated ", "map<", "Collection<",
+    "MutableList<", "ICollection<", "IEnumerable<", "IList<", "IReadOnlyList<",
+];
+
+fn is_collection_type(ty: &str) -> bool {
+    COLLECTION_MARKERS.iter().any(|m| ty.contains(m))
+}
+
+/// Infer structural edges between models:
+/// - contains / contains_many: model → model named in one of its field types
+/// - extends: model → declared base class or interface
+///
+/// Names resolve to a model in the same file first, then to models of the same language.
+pub fn infer_model_edges(components: &[DetectedComponent]) -> Vec<DetectedEdge> {
+    let mut models_by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, comp) in components.iter().enumerate() {
+        if comp.kind == ComponentKind::Model {
+            models_by_name.entry(comp.name.as_str()).or_default().push(i);
+        }
+    }
+
+    let resolve = |from: &DetectedComponent, name: &str| -> Vec<usize> {
+        let name = name.rsplit(['.', ':']).next().unwrap_or(name);
+        let Some(candidates) = models_by_name.get(name) else {
+            return Vec::new();
+        };
+        let same_file: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|&i| components[i].source.file == from.source.file)
+            .collect();
+        if !same_file.is_empty() {
+            return same_file;
+        }
+        candidates
+            .iter()
+            .copied()
+            .filter(|&i| components[i].language == from.language)
+            .collect()
+    };
+
+    let mut edges = Vec::new();
+    let push = |edges: &mut Vec<DetectedEdge>, from: &DetectedComponent, to: usize, label: &str, snippet: &str| {
+        if components[to].id != from.id {
+            let heuristic = if label == "extends" { "base_type" } else { "field_type" };
+            edges.push(DetectedEdge {
+                from_id: from.id.clone(),
+                to_id: components[to].id.clone(),
+                label: Some(label.to_string()),
+                payload_type: None,
+                evidence: evidence_at(heuristic, from, snippet),
+                confidence: None,
+                relations: Vec::new(),
+            });
+        }
+    };
+
+    for model in components.iter().filter(|c| c.kind == ComponentKind::Model) {
+        if let Some(bases) = model.metadata.get("extends") {
+            for base in bases.split(',') {
+                for target in resolve(model, base) {
+                    push(&mut edges, model, target, "extends", base);
+                }
+            }
+        }
+
+        for field in model.model_fields.iter().flatten() {
+            let Some(ty) = field.type_name.as_deref() else {
+                continue;
+            };
+            let label = if is_collection_type(ty) { "contains_many" } else { "contains" };
+            for ident in TYPE_IDENT.find_iter(ty) {
+                for target in resolve(model, ident.as_str()) {
+                    push(&mut edges, model, target, label, &format!("{}: {ty}", field.name));
+                }
+            }
+        }
+    }
+
+    // A model holding both `Item` and `Vec<Item>` keeps the stronger `contains_many`
+    edges.sort_by(|a, b| {
+        (&a.from_id, &a.to_id, &a.label).cmp(&(&b.from_id, &b.to_id, &b.label))
+    });
+    edges.dedup_by(|a, b| a.from_id == b.from_id && a.to_id == b.to_id && a.label == b.label);
+    let many: HashSet<(String, String)> = edges
+        .iter()
+        .filter(|e| e.label.as_deref() == Some("contains_many"))
+        .map(|e| (e.from_id.clone(), e.to_id.clone()))
+        .collect();
+    edges.retain(|e| {
+        e.label.as_deref() != Some("contains") || !many.contains(&(e.from_id.clone(), e.to_id.clone()))
+    });
+
+    edges
+}
+
+/// Emit `foreign_key` edges from ORM models to the tables their keys reference.
+/// Each `foreign_keys` entry is `column->table.column`; the target resolves by
+/// table name first, then by model name (TypeORM and GORM reference classes).
+pub fn infer_foreign_key_edges(components: &[DetectedComponent]) -> Vec<DetectedEdge> {
+    let tables: Vec<&DetectedComponent> = components
+        .iter()
+        .filter(|c| c.kind == ComponentKind::Model && c.metadata.contains_key("table"))
+        .collect();
+    let resolve = |from: &DetectedComponent, target: &str| -> Option<&DetectedComponent> {
+        let same_orm = |c: &&&DetectedComponent| c.metadata.get("orm") == from.metadata.get("orm");
+        let by_table = |c: &&&DetectedComponent| c.metadata.get("table").map(String::as_str) == Some(target);
+        let by_name = |c: &&&DetectedComponent| c.name == target;
+        tables
+            .iter()
+            .filter(same_orm)
+            .find(|c| c.source.file == from.source.file && by_table(c))
+            .or_else(|| tables.iter().filter(same_orm).find(by_table))
+            .or_else(|| tables.iter().filter(same_orm).find(by_name))
+            .copied()
+    };
+
+    let mut edges = Vec::new();
+    for model in &tables {
+        let Some(keys) = model.metadata.get("foreign_keys") else {
+            continue;
+        };
+        for key in keys.split(',') {
+            let Some((_, reference)) = key.split_once("->") else {
+                continue;
+            };
+            let table = reference.rsplit_once('.').map_or(reference, |(t, _)| t);
+            if let Some(target) = resolve(model, table)
+                && target.id != model.id
+            {
+                edges.push(DetectedEdge {
+                    from_id: model.id.clone(),
+                    to_id: target.id.clone(),
+                    label: Some("foreign_key".to_string()),
+                    payload_type: None,
+                    evidence: evidence_at("foreign_key", model, key),
+                    confidence: None,
+                    relations: Vec::new(),
+                });
+            }
+        }
+    }
     edges.sort_by(|a, b| (&a.from_id, &a.to_id).cmp(&(&b.from_id, &b.to_id)));
     edges.dedup_by(|a, b| a.from_id == b.from_id && a.to_id == b.to_id);
+    edges
+}
+
+/// Emit `maps_to` edges from code and ORM models to the migration table that
+/// stores them: by the ORM's `table` name, else by the model name as written,
+/// snake_cased or pluralised (`OrderItem` → `order_items`).
+pub fn infer_table_mapping_edges(components: &[DetectedComponent]) -> Vec<DetectedEdge> {
+    let tables: Vec<&DetectedComponent> = components
+        .iter()
+        .filter(|c| c.kind == ComponentKind::Model && c.metadata.contains_key("migrations"))
+        .collect();
+    if tables.is_empty() {
+        return Vec::new();
+    }
+
+    let mut edges = Vec::new();
+    for model in components
+        .iter()
+        .filter(|c| c.kind == ComponentKind::Model && !c.metadata.contains_key("migrations"))
+    {
+        let candidates = match model.metadata.get("table") {
+            Some(table) => vec![table.to_lowercase()],
+            None => {
+                let snake = snake_case(&model.name);
+                vec![model.name.to_lowercase(), pluralize(&snake), snake]
+            }
+        };
+        if let Some(table) = candidates
+            .iter()
+            .find_map(|name| tables.iter().find(|t| t.name.eq_ignore_ascii_case(name)))
+        {
+            edges.push(DetectedEdge {
+                from_id: model.id.clone(),
+                to_id: table.id.clone(),
+                label: Some("maps_to".to_string()),
+                payload_type: None,
+                evidence: evidence_at("table_name", model, &table.name),
+                confidence: None,
+                relations: Vec::new(),
+            });
+        }
+    }
+    edges
+}
 
+/// Method-name prefixes (lowercased, underscores removed) that read from a datastore
+const READ_METHODS: &[&str] = &[
+    "get", "find", "select", "fetch", "scan", "search", "count", "aggregate", "exists", "list", "read",
+    "load", "download", "mget", "hget", "hmget", "smembers", "sismember", "zrange", "zscore", "lrange",
+    "llen", "keys", "ttl", "head", "batchget", "distinct", "lookup", "query",
+];
+
+/// Method-name prefixes that write to a datastore
+const WRITE_METHODS: &[&str] = &[
+    "set", "put", "insert", "update", "delete", "del", "remove", "save", "create", "upsert", "add", "incr",
+    "decr", "push", "lpush", "rpush", "hset", "hdel", "sadd", "srem", "zadd", "zrem", "expire", "write",
+    "upload", "copy", "bulk", "index", "replace", "append", "persist", "drop", "truncate", "batchwrite",
+    "mset", "hmset", "flush",
+];
+
+/// Methods that run a statement; the SQL verb in the text decides read or write
+const STATEMENT_METHODS: &[&str] = &["execute", "executemany", "exec", "query", "raw", "run", "fetch"];
+
+static SQL_VERB: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(select|insert|update|delete|upsert|merge|replace|create|alter|drop|truncate)\b").unwrap()
+});
+
+static CHAINED_METHOD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\.\s*(\w+)").unwrap());
+
+static IMPORT_LINE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^\s*(?:import|from|use|export)\b.*$|^.*\brequire\s*\(.*$").unwrap());
+
+/// Classify a datastore client call as `reads` or `writes`. Statement methods
+/// (`execute`, `query`) look at the SQL on the same line.
+fn datastore_access(method: &str, line_text: &str) -> Option<&'static str> {
+    let method = method.to_lowercase().replace('_', "");
+    if STATEMENT_METHODS.contains(&method.as_str())
+        && let Some(verb) = SQL_VERB.captures(line_text)
+    {
+        return Some(if verb[1].eq_ignore_ascii_case("select") { "reads" } else { "writes" });
+    }
+    if READ_METHODS.iter().any(|p| method.starts_with(p)) {
+        Some("reads")
+    } else if WRITE_METHODS.iter().any(|p| method.starts_with(p)) {
+        Some("writes")
+    } else {
+        None
+    }
+}
+
+/// Emit `reads`/`writes` edges from services and handlers to datastores. A
+/// datastore's `clients` (`file#variable`) are followed into the defining file
+/// and into files that import the variable; each method called on the client
+/// inside a component's body classifies the access.
+pub fn infer_datastore_edges(
+    components: &[DetectedComponent],
+    file_contents: &HashMap<String, String>,
+    code_contents: &HashMap<String, String>,
+) -> Vec<DetectedEdge> {
+    let users: Vec<&DetectedComponent> = components
+        .iter()
+        .filter(|c| matches!(c.kind, ComponentKind::Service | ComponentKind::Transport))
+        .collect();
+
+    let mut edges = Vec::new();
+    for store in components.iter().filter(|c| c.kind == ComponentKind::Datastore) {
+        let Some(clients) = store.metadata.get("clients") else {
+            continue;
+        };
+        for client in clients.split(',') {
+            let Some((defined_in, var)) = client.split_once('#') else {
+                continue;
+            };
+            let Ok(call) = Regex::new(&format!(r"\b{}((?:\s*\.\s*\w+\s*(?:\([^()]*\))?)+)", regex::escape(var))) else {
+                continue;
+            };
+            let word = Regex::new(&format!(r"\b{}\b", regex::escape(var))).unwrap();
+
+            for user in &users {
+                let file = user.source.file.as_str();
+                let Some(text) = file_contents.get(file) else {
+                    continue;
+                };
+                let imported = IMPORT_LINE.find_iter(text).any(|l| word.is_match(l.as_str()));
+                if file != defined_in && !imported {
+                    continue;
+                }
+                let (Some(start), Some(end)) = (user.source.line_start, user.source.line_end) else {
+                    continue;
+                };
+                let code = code_contents.get(file).unwrap_or(text);
+                let text_lines: Vec<&str> = text.lines().collect();
+                // Label → first line classifying an access that way
+                let mut labels: Vec<(&str, usize)> = Vec::new();
+                for (i, line) in code.lines().enumerate().take(end as usize).skip(start as usize - 1) {
+                    for chain in call.captures_iter(line) {
+                        let line_text = text_lines.get(i).copied().unwrap_or(line);
+                        for m in CHAINED_METHOD.captures_iter(&chain[1]) {
+                            if let Some(label) = datastore_access(&m[1], line_text)
+                                && !labels.iter().any(|(l, _)| *l == label)
+                            {
+                                labels.push((label, i));
+                            }
+                        }
+                    }
+                }
+                labels.sort();
+                for (label, i) in labels {
+                    let line_text = text_lines.get(i).copied().unwrap_or("");
+                    edges.push(DetectedEdge {
+                        from_id: user.id.clone(),
+                        to_id: store.id.clone(),
+                        label: Some(label.to_string()),
+                        payload_type: None,
+                        evidence: evidence("datastore_client", file, Some(i as u32 + 1), line_text),
+                        confidence: None,
+                        relations: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+    edges.sort_by(|a, b| (&a.from_id, &a.to_id, &a.label).cmp(&(&b.from_id, &b.to_id, &b.label)));
+    edges.dedup_by(|a, b| a.from_id == b.from_id && a.to_id == b.to_id && a.label == b.label);
     edges
 }
 
+/// Services, transports, transforms and prompts of one file: the components
+/// an outbound call can be made from.
+fn call_sites<'a>(components: &'a [DetectedComponent], file: &str) -> Vec<&'a DetectedComponent> {
+    components
+        .iter()
+        .filter(|c| {
+            c.source.file == file
+                && matches!(
+                    c.kind,
+                    ComponentKind::Service | ComponentKind::Transport | ComponentKind::Transform | ComponentKind::Prompt
+                )
+        })
+        .collect()
+}
+
+/// The innermost call site whose span contains `line`.
+fn enclosing<'a>(sites: &[&'a DetectedComponent], line: u32) -> Option<&'a DetectedComponent> {
+    sites
+        .iter()
+        .filter(|c| {
+            c.source.line_start.is_some_and(|s| s <= line) && c.source.line_end.is_some_and(|e| e >= line)
+        })
+        .min_by_key(|c| c.source.line_end.unwrap_or(0) - c.source.line_start.unwrap_or(0))
+        .copied()
+}
+
+/// Collects outbound edges and the endpoint nodes they point at when nothing in
+/// the scan serves the target.
+#[derive(Default)]
+struct OutboundLinks {
+    endpoints: Vec<DetectedComponent>,
+    edges: Vec<DetectedEdge>,
+}
+
+impl OutboundLinks {
+    /// Link the call site enclosing `line` to each of `targets`, or to
+    /// `endpoint` when there are none. A call outside every call site has no
+    /// edge to carry, so it adds no endpoint either.
+    fn link(
+        &mut self,
+        sites: &[&DetectedComponent],
+        line: u32,
+        targets: &[&DetectedComponent],
+        endpoint: impl FnOnce() -> DetectedComponent,
+        label: &str,
+        evidence: Option<EdgeEvidence>,
+    ) {
+        let Some(caller) = enclosing(sites, line) else {
+            return;
+        };
+        let to_ids: Vec<String> = if targets.is_empty() {
+            let endpoint = endpoint();
+            let id = endpoint.id.clone();
+            if !self.endpoints.iter().any(|e| e.id == id) {
+                self.endpoints.push(endpoint);
+            }
+            vec![id]
+        } else {
+            targets.iter().map(|t| t.id.clone()).collect()
+        };
+        for to_id in to_ids {
+            if caller.id != to_id {
+                self.edges.push(DetectedEdge {
+                    from_id: caller.id.clone(),
+                    to_id,
+                    label: Some(label.to_string()),
+                    payload_type: None,
+                    evidence: evidence.clone(),
+                    confidence: None,
+                    relations: Vec::new(),
+                });
+            }
+        }
+    }
+
+    fn finish(mut self) -> (Vec<DetectedComponent>, Vec<DetectedEdge>) {
+        self.edges.sort_by(|a, b| (&a.from_id, &a.to_id, &a.label).cmp(&(&b.from_id, &b.to_id, &b.label)));
+        self.edges.dedup_by(|a, b| a.from_id == b.from_id && a.to_id == b.to_id && a.label == b.label);
+        (self.endpoints, self.edges)
+    }
+}
+
+/// Whether a transport is an outbound endpoint node rather than one the scan serves.
+pub fn is_outbound(comp: &DetectedComponent) -> bool {
+    comp.metadata.get("direction").is_some_and(|d| d == "outbound")
+}
+
+/// The inbound transport serving `method` and `path`, if any.
+pub fn route_for<'a>(routes: &[&'a DetectedComponent], method: &str, path: &str) -> Option<&'a DetectedComponent> {
+    let segments = path_segments(path);
+    routes
+        .iter()
+        .find(|r| {
+            r.transport_protocol == Some(TransportProtocol::Http)
+                && !is_outbound(r)
+                && r.http_method.as_deref().is_none_or(|m| m.eq_ignore_ascii_case(method))
+                && r.http_path.as_deref().is_some_and(|p| path_segments(p) == segments)
+        })
+        .copied()
+}
+
+/// Every consumer of a queue topic; a published message reaches all of them.
+pub fn topic_consumers<'a>(transports: &[&'a DetectedComponent], topic: &str) -> Vec<&'a DetectedComponent> {
+    transports
+        .iter()
+        .filter(|t| {
+            t.transport_protocol == Some(TransportProtocol::Mq)
+                && !is_outbound(t)
+                && t.metadata.get("topic").is_some_and(|name| name == topic)
+        })
+        .copied()
+        .collect()
+}
+
+/// The proto service a gRPC stub was generated from.
+pub fn grpc_service<'a>(transports: &[&'a DetectedComponent], service: &str) -> Option<&'a DetectedComponent> {
+    transports
+        .iter()
+        .find(|t| t.transport_protocol == Some(TransportProtocol::Grpc) && !is_outbound(t) && t.name == service)
+        .copied()
+}
+
+/// Emit outbound edges from services and handlers:
+/// - `http_call` for HTTP requests; a call whose method and path match a
+///   transport in the scan links straight to it (a frontend
+///   `fetch("/api/users")` to the backend's `GET /api/users`)
+/// - `publishes` for messages sent to a topic, linked to its consumer
+/// - `grpc_call` for generated gRPC stubs, linked to the proto service
+///
+/// Any other call links to an outbound endpoint node, returned for the caller
+/// to add. The caller is the innermost service, transport, transform or prompt
+/// whose span contains the call. Calls only count where they start in
+/// `code_contents`.
+///
+/// Calls at module level or in a function that is not a component have no
+/// caller and are skipped: a frontend `fetch` only links to the backend route
+/// from a detected service, handler or prompt.
+pub fn infer_outbound_edges(
+    components: &[DetectedComponent],
+    file_contents: &HashMap<String, String>,
+    code_contents: &HashMap<String, String>,
+) -> (Vec<DetectedComponent>, Vec<DetectedEdge>) {
+    let transports: Vec<&DetectedComponent> =
+        components.iter().filter(|c| c.kind == ComponentKind::Transport).collect();
+
+    let mut files: Vec<&String> = file_contents.keys().collect();
+    files.sort();
+
+    let mut links = OutboundLinks::default();
+    for file in files {
+        let Some(language) = detect_language(std::path::Path::new(file)) else {
+            continue;
+        };
+        let content = &file_contents[file];
+        let code = code_contents.get(file).unwrap_or(content);
+        let sites = call_sites(components, file);
+
+        for call in detect_http_calls(content, code, language) {
+            // A route registered on this line is served here, not called
+            if transports.iter().any(|t| t.source.file == *file && t.source.line_start == Some(call.line)) {
+                continue;
+            }
+            let route: Vec<_> = route_for(&transports, &call.method, &call.path).into_iter().collect();
+            let found = evidence("http_client", file, Some(call.line), line_text(content, call.line));
+            links.link(&sites, call.line, &route, || outbound_endpoint(&call, language, file), "http_call", found);
+        }
+
+        for publish in detect_queue_publishes(content, code) {
+            let consumers = topic_consumers(&transports, &publish.topic);
+            let found = evidence("queue_publish", file, Some(publish.line), line_text(content, publish.line));
+            links.link(&sites, publish.line, &consumers, || outbound_topic(&publish, language, file), "publishes", found);
+        }
+
+        for stub in detect_grpc_stubs(content, code) {
+            let service: Vec<_> = grpc_service(&transports, &stub.service).into_iter().collect();
+            let found = evidence("grpc_stub", file, Some(stub.line), line_text(content, stub.line));
+            links.link(&sites, stub.line, &service, || outbound_service(&stub, language, file), "grpc_call", found);
+        }
+    }
+    links.finish()
+}
+
 /// Extract the first payload type from a transport's consumes/produces fields.
 fn transport_payload(comp: &DetectedComponent) -> Option<String> {
     comp.consumes
@@ -156,13 +828,40 @@ fn transport_payload(comp: &DetectedComponent) -> Option<String> {
         .cloned()
 }
 
+/// Evidence for a match at byte `offset` of a component's `body_text`.
+fn body_evidence(heuristic: &str, comp: &DetectedComponent, body: &str, offset: usize) -> Option<EdgeEvidence> {
+    let line = line_of(body, offset);
+    let start = comp.source.line_start.unwrap_or(1);
+    evidence(heuristic, &comp.source.file, Some(start + line - 1), line_text(body, line))
+}
+
+/// A component's body text: its exact definition span when known, otherwise a
+/// fixed window of `fallback` lines from the definition line.
+fn body_text(comp: &DetectedComponent, lines: &[&str], fallback: usize) -> String {
+    let start_line = comp.source.line_start.unwrap_or(1) as usize;
+    let start_idx = start_line.saturating_sub(1);
+    let end_line = comp
+        .source
+        .line_end
+        .map_or(start_line + fallback, |end| end as usize)
+        .min(lines.len());
+    if start_idx >= end_line {
+        return String::new();
+    }
+    lines[start_idx..end_line].join("\n")
+}
+
 /// Infer flow edges that represent request-handling relationships:
 /// - handles: service → transport (route defined in same file as service)
 /// - persists: transport/service → model (body references model types)
 /// - transforms: transform → model (transform references model types)
+///
+/// Bodies are matched on `code_contents`, so a model named only in a string or
+/// comment is not referenced; evidence quotes the line from `file_contents`.
 pub fn infer_flow_edges(
     components: &[DetectedComponent],
     file_contents: &HashMap<String, String>,
+    code_contents: &HashMap<String, String>,
 ) -> Vec<DetectedEdge> {
     use crate::output::schema::ComponentKind;
 
@@ -174,10 +873,11 @@ pub fn infer_flow_edges(
         by_file.entry(comp.source.file.as_str()).or_default().push(comp);
     }
 
-    // Collect all model names and precompile their word-boundary regexes once
+    // Collect all model names and precompile their word-boundary regexes once.
+    // Migration tables are reached through the code model that `maps_to` them.
     let model_regexes: Vec<(&str, &str, Regex)> = components
         .iter()
-        .filter(|c| c.kind == ComponentKind::Model && c.name.len() >= 3)
+        .filter(|c| c.kind == ComponentKind::Model && c.name.len() >= 3 && !c.metadata.contains_key("migrations"))
         .filter_map(|c| {
             let pattern = format!(r"\b{}\b", regex::escape(&c.name));
             Regex::new(&pattern).ok().map(|re| (c.id.as_str(), c.name.as_str(), re))
@@ -198,6 +898,9 @@ pub fn infer_flow_edges(
                     to_id: tp.id.clone(),
                     label: Some("handles".to_string()),
                     payload_type: transport_payload(tp),
+                    evidence: evidence_at("same_file_route", tp, &tp.name),
+                    confidence: None,
+                    relations: Vec::new(),
                 });
             }
         }
@@ -205,7 +908,7 @@ pub fn infer_flow_edges(
         // service --invokes_prompt--> prompt (prompt defined within service body window)
         for svc in &services {
             let svc_start = svc.source.line_start.unwrap_or(1);
-            let svc_end = svc_start + 150;
+            let svc_end = svc.source.line_end.unwrap_or(svc_start + 150);
             for pr in &prompts {
                 let pr_line = pr.source.line_start.unwrap_or(0);
                 if pr_line >= svc_start && pr_line <= svc_end {
@@ -214,6 +917,9 @@ pub fn infer_flow_edges(
                         to_id: pr.id.clone(),
                         label: Some("invokes_prompt".to_string()),
                         payload_type: None,
+                        evidence: evidence_at("prompt_in_body", pr, &pr.name),
+                        confidence: None,
+                        relations: Vec::new(),
                     });
                 }
             }
@@ -221,22 +927,26 @@ pub fn infer_flow_edges(
 
         // transport --persists--> model (handler body references model name)
         if let Some(content) = file_contents.get(*file) {
+            let code = code_contents.get(*file).unwrap_or(content);
             let lines: Vec<&str> = content.lines().collect();
+            let code_lines: Vec<&str> = code.lines().collect();
 
             for tp in &transports {
-                let start_line = tp.source.line_start.unwrap_or(1) as usize;
-                // Scan ~50 lines from the transport definition (handler body)
-                let end_line = (start_line + 50).min(lines.len());
-                let start_idx = if start_line > 0 { start_line - 1 } else { 0 };
-                let body = lines[start_idx..end_line].join("\n");
+                let body = body_text(tp, &lines, 50);
+                let code_body = body_text(tp, &code_lines, 50);
 
                 for &(model_id, model_name, ref re) in &model_regexes {
-                    if model_id != tp.id && re.is_match(&body) {
+                    if model_id != tp.id
+                        && let Some(m) = re.find(&code_body)
+                    {
                         edges.push(DetectedEdge {
                             from_id: tp.id.clone(),
                             to_id: model_id.to_string(),
                             label: Some("persists".to_string()),
                             payload_type: Some(model_name.to_string()),
+                            evidence: body_evidence("model_in_body", tp, &body, m.start()),
+                            confidence: None,
+                            relations: Vec::new(),
                         });
                     }
                 }
@@ -244,18 +954,21 @@ pub fn infer_flow_edges(
 
             // transform --transforms--> model (transform body references model name)
             for tf in &transforms {
-                let start_line = tf.source.line_start.unwrap_or(1) as usize;
-                let end_line = (start_line + 50).min(lines.len());
-                let start_idx = if start_line > 0 { start_line - 1 } else { 0 };
-                let body = lines[start_idx..end_line].join("\n");
+                let body = body_text(tf, &lines, 50);
+                let code_body = body_text(tf, &code_lines, 50);
 
                 for &(model_id, model_name, ref re) in &model_regexes {
-                    if model_id != tf.id && re.is_match(&body) {
+                    if model_id != tf.id
+                        && let Some(m) = re.find(&code_body)
+                    {
                         edges.push(DetectedEdge {
                             from_id: tf.id.clone(),
                             to_id: model_id.to_string(),
                             label: Some("transforms".to_string()),
                             payload_type: Some(model_name.to_string()),
+                            evidence: body_evidence("model_in_body", tf, &body, m.start()),
+                            confidence: None,
+                            relations: Vec::new(),
                         });
                     }
                 }
@@ -263,18 +976,21 @@ pub fn infer_flow_edges(
 
             // prompt --persists--> model (prompt body references model name)
             for pr in &prompts {
-                let start_line = pr.source.line_start.unwrap_or(1) as usize;
-                let end_line = (start_line + 50).min(lines.len());
-                let start_idx = if start_line > 0 { start_line - 1 } else { 0 };
-                let body = lines[start_idx..end_line].join("\n");
+                let body = body_text(pr, &lines, 50);
+                let code_body = body_text(pr, &code_lines, 50);
 
                 for &(model_id, model_name, ref re) in &model_regexes {
-                    if model_id != pr.id && re.is_match(&body) {
+                    if model_id != pr.id
+                        && let Some(m) = re.find(&code_body)
+                    {
                         edges.push(DetectedEdge {
                             from_id: pr.id.clone(),
                             to_id: model_id.to_string(),
                             label: Some("persists".to_string()),
                             payload_type: Some(model_name.to_string()),
+                            evidence: body_evidence("model_in_body", pr, &body, m.start()),
+                            confidence: None,
+                            relations: Vec::new(),
                         });
                     }
                 }
@@ -282,18 +998,21 @@ pub fn infer_flow_edges(
 
             // service --persists--> model (service body references model name)
             for svc in &services {
-                let start_line = svc.source.line_start.unwrap_or(1) as usize;
-                let end_line = (start_line + 150).min(lines.len());
-                let start_idx = if start_line > 0 { start_line - 1 } else { 0 };
-                let body = lines[start_idx..end_line].join("\n");
+                let body = body_text(svc, &lines, 150);
+                let code_body = body_text(svc, &code_lines, 150);
 
                 for &(model_id, model_name, ref re) in &model_regexes {
-                    if model_id != svc.id && re.is_match(&body) {
+                    if model_id != svc.id
+                        && let Some(m) = re.find(&code_body)
+                    {
                         edges.push(DetectedEdge {
                             from_id: svc.id.clone(),
                             to_id: model_id.to_string(),
                             label: Some("persists".to_string()),
                             payload_type: Some(model_name.to_string()),
+                            evidence: body_evidence("model_in_body", svc, &body, m.start()),
+                            confidence: None,
+                            relations: Vec::new(),
                         });
                     }
                 }
@@ -321,6 +1040,9 @@ pub fn infer_flow_edges(
                         to_id: comp.id.clone(),
                         label: Some("consumes".to_string()),
                         payload_type: Some(type_name.clone()),
+                        evidence: evidence_at("signature_type", comp, type_name),
+                        confidence: None,
+                        relations: Vec::new(),
                     });
                 }
             }
@@ -335,6 +1057,9 @@ pub fn infer_flow_edges(
                         to_id: model_id.to_string(),
                         label: Some("produces".to_string()),
                         payload_type: Some(type_name.clone()),
+                        evidence: evidence_at("signature_type", comp, type_name),
+                        confidence: None,
+                        relations: Vec::new(),
                     });
                 }
             }
@@ -358,50 +1083,112 @@ static BACKGROUND_DISPATCH_PATTERN: LazyLock<Regex> =
 static AWAIT_CALL_PATTERN: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"await\s+(\w+)\s*\(").unwrap());
 
-// Python import: "from .foo import bar", "from . import bar", "from foo import bar", "import bar"
-static PYTHON_IMPORT_ALIAS: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(?m)^(?:from\s+(\.?\S+)\s+)?import\s+(\w+)(?:\s+as\s+(\w+))?").unwrap());
+// Python import: "from .foo import bar", "from . import bar", "from foo import bar, baz",
+// "from foo import (bar, baz)", "import bar"; group 2 is the whole import list
+static PYTHON_IMPORT_ALIAS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^(?:from\s+(\.*[\w.]*)\s+)?import\s+(\([^)]*\)|(?:[^\n#;\\]|\\\n)+)").unwrap()
+});
+
+/// One entry of an import list: "bar", "bar as b", "pkg.mod as m"
+static PYTHON_IMPORT_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([\w.]+)(?:\s+as\s+(\w+))?$").unwrap());
+
+/// A single Python import binding: the raw module path (leading dots kept)
+/// and the name imported from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ImportBinding {
+    pub module: String,
+    pub name: String,
+}
+
+/// Build a map from local alias to the import binding that introduced it
+pub(crate) fn build_import_bindings(content: &str) -> HashMap<String, ImportBinding> {
+    let mut bindings = HashMap::new();
+    for cap in PYTHON_IMPORT_ALIAS.captures_iter(content) {
+        let from = cap.get(1).map(|m| m.as_str());
+        let list = cap[2].trim_start_matches('(').trim_end_matches(')');
+        for entry in list.split(',') {
+            // Drop comments and line continuations inside the list
+            let entry: String = entry
+                .lines()
+                .map(|line| line.split('#').next().unwrap_or("").trim_end_matches('\\'))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let Some(names) = PYTHON_IMPORT_NAME.captures(entry.trim()) else {
+                continue;
+            };
+            let imported = &names[1];
+            let (alias, module, name) = match (from, names.get(2)) {
+                (Some(module), alias) => (alias.map_or(imported, |a| a.as_str()), module, imported),
+                // "import pkg.mod as m" binds m to pkg.mod
+                (None, Some(alias)) => {
+                    let (module, name) = imported.rsplit_once('.').unwrap_or(("", imported));
+                    (alias.as_str(), module, name)
+                }
+                // "import pkg.mod" binds only pkg
+                (None, None) => {
+                    let head = imported.split('.').next().unwrap_or(imported);
+                    (head, "", head)
+                }
+            };
+            bindings.insert(
+                alias.to_string(),
+                ImportBinding {
+                    module: module.to_string(),
+                    name: name.to_string(),
+                },
+            );
+        }
+    }
+    bindings
+}
 
 /// Build a map from module alias to imported module path for a single file
 fn build_import_index(content: &str) -> HashMap<String, String> {
     let mut index = HashMap::new();
-    for cap in PYTHON_IMPORT_ALIAS.captures_iter(content) {
-        let module_path = cap.get(1).map(|m| m.as_str()).unwrap_or("");
-        let imported_name = &cap[2];
-        let alias = cap.get(3).map(|m| m.as_str()).unwrap_or(imported_name);
-
+    for (alias, binding) in build_import_bindings(content) {
         // "from .foo import bar" → bar -> foo
         // "from . import bar"   → bar -> bar  (bare-dot: imported name IS the module)
         // "from foo import bar" → bar -> foo
         // "import bar"          → bar -> bar
-        let is_bare_dot = module_path == "." || module_path == "..";
-        if !module_path.is_empty() && !is_bare_dot {
+        let is_bare_dot = binding.module == "." || binding.module == "..";
+        if !binding.module.is_empty() && !is_bare_dot {
             // Strip leading dot from relative imports (e.g., ".crud" → "crud")
-            let clean = module_path.trim_start_matches('.');
-            index.insert(alias.to_string(), clean.to_string());
+            let clean = binding.module.trim_start_matches('.');
+            index.insert(alias, clean.to_string());
         } else {
-            index.insert(alias.to_string(), imported_name.to_string());
+            index.insert(alias, binding.name);
         }
     }
     index
 }
 
+/// Scan-wide indexes used to resolve call targets.
+struct CallIndex<'a> {
+    components: &'a [DetectedComponent],
+    name_index: HashMap<String, Vec<usize>>,
+    stem_to_file: HashMap<String, Vec<String>>,
+    by_file: HashMap<&'a str, Vec<&'a DetectedComponent>>,
+}
+
 /// Scan a component body for calls to other components using module.function(),
-/// background dispatch, and awaited function call patterns.
+/// background dispatch, and awaited function call patterns. Calls are matched
+/// on `code_body`, the body's code view; `body` is quoted as evidence.
 fn scan_body_for_calls(
     source: &DetectedComponent,
     body: &str,
+    code_body: &str,
     payload_type: Option<String>,
     import_index: &HashMap<String, String>,
-    name_index: &HashMap<String, Vec<usize>>,
-    components: &[DetectedComponent],
-    stem_to_file: &HashMap<String, Vec<String>>,
-    by_file: &HashMap<&str, Vec<&DetectedComponent>>,
+    packages: &HashMap<String, Vec<String>>,
+    index: &CallIndex,
 ) -> Vec<DetectedEdge> {
+    let components = index.components;
+    let name_index = &index.name_index;
     let mut edges = Vec::new();
 
     // 1. Module function calls: module.function()
-    for cap in MODULE_CALL_PATTERN.captures_iter(body) {
+    for cap in MODULE_CALL_PATTERN.captures_iter(code_body) {
         let module_alias = &cap[1];
         let func_name = &cap[2];
 
@@ -410,32 +1197,42 @@ fn scan_body_for_calls(
             continue;
         }
 
-        let resolved = import_index.get(module_alias);
-
-        let target = resolve_call_target(
-            func_name,
-            resolved.map(|s| s.as_str()),
-            module_alias,
-            name_index,
-            components,
-            stem_to_file,
-            by_file,
-        );
+        // A qualifier naming an imported package of the scan (Go) resolves
+        // within that package only
+        let target = match packages.get(module_alias) {
+            Some(files) => files
+                .iter()
+                .filter_map(|f| index.by_file.get(f.as_str()))
+                .flatten()
+                .find(|c| c.name == func_name)
+                .map(|c| (c.id.clone(), "package_call")),
+            None => {
+                let resolved = import_index.get(module_alias);
+                resolve_call_target(func_name, resolved.map(|s| s.as_str()), module_alias, index)
+            }
+        };
 
-        if let Some(target_id) = target {
-            if target_id != source.id {
-                edges.push(DetectedEdge {
-                    from_id: source.id.clone(),
-                    to_id: target_id,
-                    label: Some("calls".to_string()),
-                    payload_type: payload_type.clone(),
-                });
+        if let Some((target_id, heuristic)) = target
+            && target_id != source.id
+        {
+            let mut found = body_evidence(heuristic, source, body, cap.get(0).map_or(0, |m| m.start()));
+            if let Some(found) = &mut found {
+                found.snippet = Some(cap[0].to_string());
             }
+            edges.push(DetectedEdge {
+                from_id: source.id.clone(),
+                to_id: target_id,
+                label: Some("calls".to_string()),
+                payload_type: payload_type.clone(),
+                evidence: found,
+                confidence: None,
+                relations: Vec::new(),
+            });
         }
     }
 
     // 2. Background dispatch: background_tasks.add_task(func, ...)
-    for cap in BACKGROUND_DISPATCH_PATTERN.captures_iter(body) {
+    for cap in BACKGROUND_DISPATCH_PATTERN.captures_iter(code_body) {
         let func_name = &cap[1];
         if let Some(targets) = name_index.get(func_name) {
             for &idx in targets {
@@ -445,6 +1242,9 @@ fn scan_body_for_calls(
                         to_id: components[idx].id.clone(),
                         label: Some("dispatches".to_string()),
                         payload_type: payload_type.clone(),
+                        evidence: body_evidence("background_dispatch", source, body, cap.get(0).map_or(0, |m| m.start())),
+                        confidence: None,
+                        relations: Vec::new(),
                     });
                 }
             }
@@ -452,7 +1252,7 @@ fn scan_body_for_calls(
     }
 
     // 3. Awaited calls: await function()
-    for cap in AWAIT_CALL_PATTERN.captures_iter(body) {
+    for cap in AWAIT_CALL_PATTERN.captures_iter(code_body) {
         let func_name = &cap[1];
 
         // Skip common awaited non-component calls
@@ -468,6 +1268,9 @@ fn scan_body_for_calls(
                         to_id: components[idx].id.clone(),
                         label: Some("calls".to_string()),
                         payload_type: payload_type.clone(),
+                        evidence: body_evidence("awaited_call", source, body, cap.get(0).map_or(0, |m| m.start())),
+                        confidence: None,
+                        relations: Vec::new(),
                     });
                 }
             }
@@ -479,15 +1282,18 @@ fn scan_body_for_calls(
 
 /// Infer call edges: transport/service → other component function calls and dispatch edges.
 /// Scans handler/service bodies for module.function() calls, background task dispatches,
-/// and awaited function calls.
+/// and awaited function calls. Qualified calls into packages `modules` resolves
+/// bind to that package's components. Imports are read from `file_contents`;
+/// calls only count where they appear in `code_contents`.
 pub fn infer_call_edges(
     components: &[DetectedComponent],
     file_contents: &HashMap<String, String>,
+    code_contents: &HashMap<String, String>,
+    modules: &ModuleIndex,
 ) -> Vec<DetectedEdge> {
     use crate::output::schema::ComponentKind;
 
     let mut edges = Vec::new();
-    let name_index = build_name_index(components);
 
     // Build file→components index
     let mut by_file: HashMap<&str, Vec<&DetectedComponent>> = HashMap::new();
@@ -515,7 +1321,14 @@ pub fn infer_call_edges(
         }
     }
 
-    for (file, comps) in &by_file {
+    let index = CallIndex {
+        components,
+        name_index: build_name_index(components),
+        stem_to_file,
+        by_file,
+    };
+
+    for (file, comps) in &index.by_file {
         let transports: Vec<&&DetectedComponent> = comps.iter()
             .filter(|c| c.kind == ComponentKind::Transport)
             .collect();
@@ -535,45 +1348,42 @@ pub fn infer_call_edges(
             None => continue,
         };
 
+        let code = code_contents.get(*file).unwrap_or(content);
         let import_index = build_import_index(content);
+        let packages = modules.go_packages(file, content);
         let lines: Vec<&str> = content.lines().collect();
+        let code_lines: Vec<&str> = code.lines().collect();
 
-        // Scan transport bodies (80-line window)
+        // Scan transport bodies (exact span, else 80-line window)
         for tp in &transports {
-            let start_line = tp.source.line_start.unwrap_or(1) as usize;
-            let end_line = (start_line + 80).min(lines.len());
-            let start_idx = if start_line > 0 { start_line - 1 } else { 0 };
-            let body = lines[start_idx..end_line].join("\n");
+            let body = body_text(tp, &lines, 80);
+            let code_body = body_text(tp, &code_lines, 80);
 
             edges.extend(scan_body_for_calls(
-                tp, &body, transport_payload(tp),
-                &import_index, &name_index, components, &stem_to_file, &by_file,
+                tp, &body, &code_body, transport_payload(tp),
+                &import_index, &packages, &index,
             ));
         }
 
-        // Scan service bodies (150-line window)
+        // Scan service bodies (exact span, else 150-line window)
         for svc in &services {
-            let start_line = svc.source.line_start.unwrap_or(1) as usize;
-            let end_line = (start_line + 150).min(lines.len());
-            let start_idx = if start_line > 0 { start_line - 1 } else { 0 };
-            let body = lines[start_idx..end_line].join("\n");
+            let body = body_text(svc, &lines, 150);
+            let code_body = body_text(svc, &code_lines, 150);
 
             edges.extend(scan_body_for_calls(
-                svc, &body, None,
-                &import_index, &name_index, components, &stem_to_file, &by_file,
+                svc, &body, &code_body, None,
+                &import_index, &packages, &index,
             ));
         }
 
-        // Scan prompt bodies (80-line window)
+        // Scan prompt bodies (exact span, else 80-line window)
         for pr in &prompts {
-            let start_line = pr.source.line_start.unwrap_or(1) as usize;
-            let end_line = (start_line + 80).min(lines.len());
-            let start_idx = if start_line > 0 { start_line - 1 } else { 0 };
-            let body = lines[start_idx..end_line].join("\n");
+            let body = body_text(pr, &lines, 80);
+            let code_body = body_text(pr, &code_lines, 80);
 
             edges.extend(scan_body_for_calls(
-                pr, &body, None,
-                &import_index, &name_index, components, &stem_to_file, &by_file,
+                pr, &body, &code_body, None,
+                &import_index, &packages, &index,
             ));
         }
     }
@@ -585,6 +1395,125 @@ pub fn infer_call_edges(
     edges
 }
 
+/// Resolve a function call target to a component ID and the strategy that
+/// found it: the module the alias names, else a unique component name
+fn resolve_call_target(
+    func_name: &str,
+    resolved_module: Option<&str>,
+    module_alias: &str,
+    index: &CallIndex,
+) -> Option<(String, &'static str)> {
+    // Strategy 1: If we have a resolved module path, find components in files matching that module
+    let module_key = resolved_module.unwrap_or(module_alias);
+    // Get the last segment of dotted path (e.g., "app.crud" -> "crud")
+    let module_stem = module_key.rsplit('.').next().unwrap_or(module_key);
+
+    if let Some(files) = index.stem_to_file.get(module_stem) {
+        for file in files {
+            if let Some(comps) = index.by_file.get(file.as_str()) {
+                for comp in comps {
+                    if comp.name == func_name {
+                        return Some((comp.id.clone(), "module_call"));
+                    }
+                }
+            }
+        }
+    }
+
+    // Strategy 2: Fall back to name-only matching
+    if let Some(targets) = index.name_index.get(func_name)
+        && targets.len() == 1
+    {
+        return Some((index.components[targets[0]].id.clone(), "name_only_call"));
+    }
+
+    None
+}
+
+/// Primary label of a merged edge: the first of these among its relations.
+/// Flow outranks model structure, which outranks imports and references.
+const LABEL_PRIORITY: &[&str] = &[
+    "handles",
+    "calls",
+    "dispatches",
+    "http_call",
+    "grpc_call",
+    "publishes",
+    "invokes_prompt",
+    "produces",
+    "consumes",
+    "persists",
+    "writes",
+    "reads",
+    "transforms",
+    "foreign_key",
+    "extends",
+    "contains_many",
+    "contains",
+    "maps_to",
+    "imports",
+    "references",
+];
+
+fn label_rank(label: &str) -> usize {
+    LABEL_PRIORITY.iter().position(|l| *l == label).unwrap_or(LABEL_PRIORITY.len())
+}
+
+/// Collapse edges into one per `(from_id, to_id)` pair, in first-seen order.
+/// Each distinct label and payload type becomes a relation (the more confident
+/// of two duplicates wins). A pair with one relation stays a plain edge; with
+/// several, the edge lists them in `relations` and keeps only the
+/// highest-ranked label as its own. Merged edges can be merged again.
+pub fn merge_relations(edges: Vec<DetectedEdge>) -> Vec<DetectedEdge> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    let mut relations: HashMap<(String, String), Vec<EdgeRelation>> = HashMap::new();
+    for edge in edges {
+        let key = (edge.from_id.clone(), edge.to_id.clone());
+        let list = relations.entry(key.clone()).or_insert_with(|| {
+            pairs.push(key);
+            Vec::new()
+        });
+        for relation in edge.relations() {
+            match list
+                .iter_mut()
+                .find(|r| r.label == relation.label && r.payload_type == relation.payload_type)
+            {
+                Some(existing) if relation.confidence > existing.confidence => *existing = relation,
+                Some(_) => {}
+                None => list.push(relation),
+            }
+        }
+    }
+
+    pairs
+        .into_iter()
+        .map(|(from_id, to_id)| {
+            let mut list = relations.remove(&(from_id.clone(), to_id.clone())).unwrap_or_default();
+            list.sort_by_key(|r| label_rank(&r.label));
+            if let [relation] = list.as_slice() {
+                return DetectedEdge {
+                    from_id,
+                    to_id,
+                    label: Some(relation.label.clone()),
+                    payload_type: relation.payload_type.clone(),
+                    evidence: relation.evidence.clone(),
+                    confidence: relation.confidence,
+                    relations: Vec::new(),
+                };
+            }
+            DetectedEdge {
+                from_id,
+                to_id,
+                label: list.first().map(|r| r.label.clone()),
+                payload_type: None,
+                evidence: None,
+                confidence: None,
+                relations: list,
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,15 +1526,387 @@ mod tests {
             kind,
             language: "python".to_string(),
             source: SourceLocation { file: file.to_string(), line_start: Some(line), line_end: None },
-            metadata: std::collections::HashMap::new(),
-            transport_protocol: None,
-            http_method: None,
-            http_path: None,
-            model_fields: None,
-            prompt_subtype: None,
-            consumes: None,
-            produces: None,
+            ..Default::default()
+        }
+    }
+
+    fn typed_field(name: &str, ty: &str) -> crate::output::schema::ModelField {
+        crate::output::schema::ModelField {
+            name: name.to_string(),
+            type_name: Some(ty.to_string()),
+            optional: false,
+            default: None,
+            tags: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn model_edges_from_field_types_and_bases() {
+        let mut order = make_comp("order", "Order", ComponentKind::Model, "src/order.rs", 1);
+        order.model_fields = Some(vec![
+            typed_field("items", "Vec<LineItem>"),
+            typed_field("first", "Option<LineItem>"),
+            typed_field("customer", "crate::models::Customer"),
+            typed_field("parent", "Option<Box<Order>>"),
+        ]);
+        let line_item = make_comp("item", "LineItem", ComponentKind::Model, "src/order.rs", 10);
+        let customer = make_comp("cust", "Customer", ComponentKind::Model, "src/customer.rs", 1);
+        let mut admin = make_comp("admin", "Admin", ComponentKind::Model, "src/customer.rs", 20);
+        admin.metadata.insert("extends".to_string(), "Customer,Serializable".to_string());
+        // Same name in another language never matches
+        let mut ts_customer = make_comp("ts_cust", "Customer", ComponentKind::Model, "web/types.ts", 1);
+        ts_customer.language = "typescript".to_string();
+
+        let edges = infer_model_edges(&[order, line_item, customer, admin, ts_customer]);
+        let summary: Vec<(&str, &str, &str)> = edges
+            .iter()
+            .map(|e| (e.from_id.as_str(), e.to_id.as_str(), e.label.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("admin", "cust", "extends"),
+                ("order", "cust", "contains"),
+                ("order", "item", "contains_many"),
+            ]
+        );
+    }
+
+    #[test]
+    fn foreign_key_edges_resolve_tables_then_models() {
+        let orm = |id: &str, name: &str, table: &str, keys: Option<&str>| {
+            let mut comp = make_comp(id, name, ComponentKind::Model, "app/models.py", 1);
+            comp.metadata.insert("orm".to_string(), "sqlalchemy".to_string());
+            comp.metadata.insert("table".to_string(), table.to_string());
+            if let Some(keys) = keys {
+                comp.metadata.insert("foreign_keys".to_string(), keys.to_string());
+            }
+            comp
+        };
+        let user = orm("user", "User", "users", None);
+        let topic = orm("topic", "Topic", "topics", None);
+        let post = orm("post", "Post", "posts", Some("author_id->users.id,topic_id->topics.id,editor_id->users.id"));
+        let comment = orm("comment", "Comment", "comments", Some("post_id->Post.id,ghost_id->ghosts.id"));
+
+        let edges = infer_foreign_key_edges(&[user, topic, post, comment]);
+        let summary: Vec<(&str, &str, &str)> = edges
+            .iter()
+            .map(|e| (e.from_id.as_str(), e.to_id.as_str(), e.label.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("comment", "post", "foreign_key"),
+                ("post", "topic", "foreign_key"),
+                ("post", "user", "foreign_key"),
+            ]
+        );
+        // The first key to a table is the one quoted
+        assert_eq!(edges[2].evidence.as_ref().unwrap().snippet.as_deref(), Some("author_id->users.id"));
+    }
+
+    #[test]
+    fn table_mapping_edges_by_orm_table_then_name() {
+        let table = |id: &str, name: &str| {
+            let mut comp = make_comp(id, name, ComponentKind::Model, "db/V1__init.sql", 1);
+            comp.metadata.insert("migrations".to_string(), "db/V1__init.sql".to_string());
+            comp
+        };
+        let mut orm_model = make_comp("account", "Account", ComponentKind::Model, "app/models.py", 1);
+        orm_model.metadata.insert("table".to_string(), "tbl_accounts".to_string());
+        let order_item = make_comp("item", "OrderItem", ComponentKind::Model, "src/order.rs", 1);
+        let unmatched = make_comp("other", "Widget", ComponentKind::Model, "src/widget.rs", 1);
+
+        let edges = infer_table_mapping_edges(&[
+            table("t_accounts", "tbl_accounts"),
+            table("t_items", "order_items"),
+            orm_model,
+            order_item,
+            unmatched,
+        ]);
+        let summary: Vec<(&str, &str)> = edges.iter().map(|e| (e.from_id.as_str(), e.to_id.as_str())).collect();
+        assert_eq!(summary, vec![("account", "t_accounts"), ("item", "t_items")]);
+        assert!(edges.iter().all(|e| e.label.as_deref() == Some("maps_to")));
+    }
+
+    #[test]
+    fn datastore_edges_follow_client_variables() {
+        use crate::scanner::lexer;
+
+        let mut cache = make_comp("redis", "redis", ComponentKind::Datastore, "app/db.py", 1);
+        cache.metadata.insert("clients".to_string(), "app/db.py#cache".to_string());
+        let mut pg = make_comp("pg", "postgres", ComponentKind::Datastore, "app/db.py", 2);
+        pg.metadata.insert("clients".to_string(), "app/db.py#conn".to_string());
+        let mut handler = make_comp("h", "get_user", ComponentKind::Service, "app/users.py", 3);
+        handler.source.line_end = Some(7);
+        let mut unrelated = make_comp("u", "other", ComponentKind::Service, "app/other.py", 1);
+        unrelated.source.line_end = Some(2);
+
+        let users = "from app.db import cache, conn\n\ndef get_user(id):\n    hit = cache.get(id)\n    conn.execute(\"SELECT * FROM users WHERE id = %s\", id)\n    cache.setex(id, 60, hit)\n    return hit\n";
+        let other = "def other():\n    return cache.get(1)\n";
+        let mut file_contents = HashMap::new();
+        let mut code_contents = HashMap::new();
+        for (file, src) in [("app/users.py", users), ("app/other.py", other)] {
+            let masked = lexer::mask(src, "python");
+            file_contents.insert(file.to_string(), masked.text);
+            code_contents.insert(file.to_string(), masked.code);
         }
+
+        let edges = infer_datastore_edges(&[cache, pg, handler, unrelated], &file_contents, &code_contents);
+        let summary: Vec<(&str, &str, &str)> = edges
+            .iter()
+            .map(|e| (e.from_id.as_str(), e.to_id.as_str(), e.label.as_deref().unwrap()))
+            .collect();
+        assert_eq!(summary, vec![("h", "pg", "reads"), ("h", "redis", "reads"), ("h", "redis", "writes")]);
+    }
+
+    #[test]
+    fn http_calls_link_to_matching_transports() {
+        let mut route = make_comp("r", "get_user", ComponentKind::Transport, "api/users.py", 1);
+        route.http_method = Some("GET".to_string());
+        route.http_path = Some("/api/users/{user_id}".to_string());
+        route.transport_protocol = Some(TransportProtocol::Http);
+        let mut client = make_comp("c", "usersApi", ComponentKind::Service, "web/api/users.ts", 1);
+        client.source.line_end = Some(4);
+
+        let web = "export async function usersApi(id: string) {\n  const user = await fetch(`/api/users/${id}`);\n  await axios.post(\"https://hooks.example.com/notify\", user);\n}\n";
+        let file_contents = HashMap::from([("web/api/users.ts".to_string(), web.to_string())]);
+
+        let (endpoints, edges) = infer_outbound_edges(&[route, client], &file_contents, &HashMap::new());
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].name, "POST hooks.example.com/notify");
+        assert_eq!(endpoints[0].metadata.get("direction").map(String::as_str), Some("outbound"));
+        let mut targets: Vec<&str> = edges.iter().map(|e| e.to_id.as_str()).collect();
+        targets.sort();
+        let mut expected = vec!["r", endpoints[0].id.as_str()];
+        expected.sort();
+        assert_eq!(targets, expected);
+        assert!(edges.iter().all(|e| e.from_id == "c" && e.label.as_deref() == Some("http_call")));
+    }
+
+    #[test]
+    fn calls_outside_components_add_no_endpoint() {
+        let mut route = make_comp("r", "list_orders", ComponentKind::Transport, "api/orders.py", 1);
+        route.http_method = Some("GET".to_string());
+        route.http_path = Some("/api/orders".to_string());
+        route.transport_protocol = Some(TransportProtocol::Http);
+
+        // Module level, and a plain function that is not a component
+        let web = "await fetch(\"/api/orders\", { method: \"POST\" });\n\nfunction loadOrders() {\n  return fetch(\"/api/orders\");\n}\n";
+        let file_contents = HashMap::from([("web/orders.ts".to_string(), web.to_string())]);
+
+        let (endpoints, edges) = infer_outbound_edges(&[route], &file_contents, &HashMap::new());
+        assert!(endpoints.is_empty());
+        assert!(edges.is_empty());
+    }
+
+    #[test]
+    fn imports_resolve_by_package_before_stem() {
+        let in_package = |id: &str, file: &str, package: &str| {
+            let mut comp = make_comp(id, id, ComponentKind::Service, file, 1);
+            comp.package = Some(package.to_string());
+            comp
+        };
+        let components = vec![
+            in_package("ui_button", "packages/ui/src/button/index.ts", "@acme/ui"),
+            in_package("ui_index", "packages/ui/src/index.ts", "@acme/ui"),
+            in_package("api_index", "packages/api/src/index.ts", "@acme/api"),
+            in_package("api_routes", "packages/api/src/routes.ts", "@acme/api"),
+            in_package("core_db", "crates/core/src/db.rs", "acme-core"),
+            in_package("app", "apps/web/src/app.ts", "web"),
+            in_package("server", "crates/server/src/main.rs", "server"),
+        ];
+        let file_contents = HashMap::from([
+            (
+                "apps/web/src/app.ts".to_string(),
+                "import { Button } from \"@acme/ui/button\";\nimport { api } from \"@acme/api\";\n".to_string(),
+            ),
+            ("packages/api/src/routes.ts".to_string(), "import { x } from \"./index\";\n".to_string()),
+            ("crates/server/src/main.rs".to_string(), "use acme_core::db::Pool;\n".to_string()),
+        ]);
+        let edges = infer_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
+        let mut imports: Vec<(&str, &str)> = edges
+            .iter()
+            .filter(|e| e.label.as_deref() == Some("imports"))
+            .map(|e| (e.from_id.as_str(), e.to_id.as_str()))
+            .collect();
+        imports.sort();
+        assert_eq!(
+            imports,
+            vec![("api_routes", "api_index"), ("app", "api_index"), ("app", "ui_button"), ("server", "core_db")]
+        );
+    }
+
+    #[test]
+    fn named_imports_bind_to_the_imported_component() {
+        let components = vec![
+            make_comp("user", "User", ComponentKind::Model, "src/models/user.ts", 1),
+            make_comp("order", "Order", ComponentKind::Model, "src/models/order.ts", 1),
+            make_comp("user_svc", "UserService", ComponentKind::Service, "src/a/utils.ts", 1),
+            make_comp("other_svc", "OtherService", ComponentKind::Service, "src/a/utils.ts", 5),
+            make_comp("b_utils", "formatDate", ComponentKind::Transform, "src/b/utils.ts", 1),
+            make_comp("handler", "handler", ComponentKind::Service, "src/b/handler.ts", 1),
+        ];
+        let file_contents = HashMap::from([
+            ("src/models/index.ts".to_string(), "export * from \"./user\";\nexport * from \"./order\";\n".to_string()),
+            ("src/models/user.ts".to_string(), String::new()),
+            ("src/models/order.ts".to_string(), String::new()),
+            ("src/a/utils.ts".to_string(), String::new()),
+            ("src/b/utils.ts".to_string(), String::new()),
+            (
+                "src/b/handler.ts".to_string(),
+                "import { User } from \"../models\";\nimport { UserService } from \"../a/utils\";\n".to_string(),
+            ),
+        ]);
+        let modules = ModuleIndex::build(&[], &file_contents);
+        let edges = infer_edges(&components, &file_contents, &HashMap::new(), &modules);
+        let mut imports: Vec<&str> = edges
+            .iter()
+            .filter(|e| e.label.as_deref() == Some("imports"))
+            .map(|e| e.to_id.as_str())
+            .collect();
+        imports.sort();
+        // Not `Order` (same barrel), `OtherService` or the `b/utils` sibling stem
+        assert_eq!(imports, vec!["user", "user_svc"]);
+    }
+
+    #[test]
+    fn go_imports_resolve_to_package_directories() {
+        let dir = std::env::temp_dir().join(format!("sysvista-go-modules-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("go.mod"), "module github.com/acme/svc\n\ngo 1.22\n").unwrap();
+        let walked = vec![crate::scanner::file_walker::WalkedFile {
+            path: dir.join("go.mod"),
+            relative_path: "go.mod".to_string(),
+        }];
+
+        let handler_src = "package handlers\n\nimport (\n\t\"net/http\"\n\t\"github.com/acme/svc/internal/store\"\n)\n\nfunc CreateUser(w http.ResponseWriter, r *http.Request) {\n\tstore.SaveUser(r.Context(), nil)\n}\n";
+        let components = vec![
+            make_comp("handler", "CreateUser", ComponentKind::Service, "handlers/users.go", 8),
+            make_comp("save", "SaveUser", ComponentKind::Transform, "internal/store/users.go", 3),
+            make_comp("user", "User", ComponentKind::Model, "internal/store/models.go", 3),
+            make_comp("legacy_save", "SaveUser", ComponentKind::Transform, "legacy/store/users.go", 3),
+        ];
+        let file_contents = HashMap::from([
+            ("handlers/users.go".to_string(), handler_src.to_string()),
+            ("internal/store/users.go".to_string(), "package store\n".to_string()),
+            ("internal/store/models.go".to_string(), "package store\n".to_string()),
+            ("legacy/store/users.go".to_string(), "package store\n".to_string()),
+        ]);
+        let modules = ModuleIndex::build(&walked, &file_contents);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let imports = infer_edges(&components, &file_contents, &HashMap::new(), &modules);
+        let mut targets: Vec<&str> = imports
+            .iter()
+            .filter(|e| e.from_id == "handler" && e.label.as_deref() == Some("imports"))
+            .map(|e| e.to_id.as_str())
+            .collect();
+        targets.sort();
+        assert_eq!(targets, vec!["save", "user"]);
+
+        // Two `SaveUser`s: only the package qualifier tells them apart
+        let calls = infer_call_edges(&components, &file_contents, &HashMap::new(), &modules);
+        let called: Vec<&str> = calls.iter().filter(|e| e.from_id == "handler").map(|e| e.to_id.as_str()).collect();
+        assert_eq!(called, vec!["save"]);
+    }
+
+    #[test]
+    fn references_ignore_comments_and_strings() {
+        use crate::scanner::lexer;
+
+        let model = make_comp("m1", "Invoice", ComponentKind::Model, "src/models.py", 1);
+        let service = make_comp("svc1", "billing", ComponentKind::Service, "src/billing.py", 1);
+        let components = vec![model, service];
+
+        let mention_only = "def billing():\n    \"\"\"Builds an Invoice.\"\"\"\n    # Invoice totals\n    return 'Invoice'\n";
+        let masked = lexer::mask(mention_only, "python");
+        let file_contents = HashMap::from([("src/billing.py".to_string(), masked.text)]);
+        let code_contents = HashMap::from([("src/billing.py".to_string(), masked.code)]);
+        assert!(infer_edges(&components, &file_contents, &code_contents, &ModuleIndex::default()).is_empty());
+
+        let real_use = "def billing():\n    return Invoice(total=1)\n";
+        let masked = lexer::mask(real_use, "python");
+        let file_contents = HashMap::from([("src/billing.py".to_string(), masked.text)]);
+        let code_contents = HashMap::from([("src/billing.py".to_string(), masked.code)]);
+        let edges = infer_edges(&components, &file_contents, &code_contents, &ModuleIndex::default());
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].label.as_deref(), Some("references"));
+    }
+
+    #[test]
+    fn datastores_and_endpoints_are_not_import_sources() {
+        let model = make_comp("m1", "Invoice", ComponentKind::Model, "src/models.py", 1);
+        let service = make_comp("svc1", "billing", ComponentKind::Service, "src/billing.py", 1);
+        let store = make_comp("db", "postgres", ComponentKind::Datastore, "src/billing.py", 1);
+        let mut endpoint = make_comp("out", "GET api.example.com/rates", ComponentKind::Transport, "src/billing.py", 3);
+        endpoint.metadata.insert("direction".to_string(), "outbound".to_string());
+
+        let content = "from .models import Invoice\n\ndef billing():\n    return Invoice(total=1)\n";
+        let file_contents = HashMap::from([("src/billing.py".to_string(), content.to_string())]);
+        let edges = infer_edges(&[model, service, store, endpoint], &file_contents, &HashMap::new(), &ModuleIndex::default());
+        assert!(!edges.is_empty());
+        assert!(edges.iter().all(|e| e.from_id == "svc1"));
+    }
+
+    #[test]
+    fn merges_every_relation_of_a_pair_into_one_edge() {
+        let model = make_comp("m1", "Invoice", ComponentKind::Model, "src/models.py", 1);
+        let service = make_comp("svc1", "billing", ComponentKind::Service, "src/billing.py", 2);
+        let components = vec![model, service];
+
+        let content = "from models import Invoice\ndef billing():\n    return Invoice(total=1)\n";
+        let file_contents = HashMap::from([("src/billing.py".to_string(), content.to_string())]);
+        let mut edges = infer_edges(&components, &file_contents, &file_contents, &ModuleIndex::default());
+        let labels: Vec<&str> = edges.iter().filter_map(|e| e.label.as_deref()).collect();
+        assert_eq!(labels, vec!["imports", "references"]);
+
+        edges.push(DetectedEdge {
+            from_id: "svc1".to_string(),
+            to_id: "m1".to_string(),
+            label: Some("persists".to_string()),
+            payload_type: Some("Invoice".to_string()),
+            evidence: None,
+            confidence: None,
+            relations: Vec::new(),
+        });
+        // A pair with a single relation stays a plain edge
+        let single = merge_relations(edges[..1].to_vec());
+        assert_eq!(single[0].label.as_deref(), Some("imports"));
+        assert!(single[0].evidence.is_some());
+        assert!(single[0].relations.is_empty());
+
+        let merged = merge_relations(merge_relations(edges));
+        assert_eq!(merged.len(), 1);
+        let edge = &merged[0];
+        // Only the primary label is repeated outside the relations
+        assert_eq!(edge.label.as_deref(), Some("persists"));
+        assert_eq!((edge.payload_type.as_deref(), edge.evidence.is_none()), (None, true));
+        assert_eq!(edge.relations[0].payload_type.as_deref(), Some("Invoice"));
+        assert_eq!(edge.labels(), vec!["persists", "imports", "references"]);
+        let import = &edge.relations[1];
+        assert_eq!(import.evidence.as_ref().map(|e| e.heuristic.as_str()), Some("import_stem"));
+        assert_eq!(import.payload_type, None);
+    }
+
+    #[test]
+    fn ignores_calls_named_only_in_strings() {
+        use crate::scanner::lexer;
+
+        let transport = make_comp("tp1", "remove_user_route", ComponentKind::Transport, "src/routes/users.py", 3);
+        let service = make_comp("svc1", "delete_user", ComponentKind::Service, "src/crud/users.py", 1);
+
+        let file_content = "from . import crud\n\n@router.delete(\"/users/{user_id}\")\nasync def remove_user_route(user_id: int):\n    log.info(\"replay with crud.delete_user(db, 1)\")\n";
+        let masked = lexer::mask(file_content, "python");
+        let file_contents = HashMap::from([
+            ("src/routes/users.py".to_string(), masked.text),
+            ("src/crud/users.py".to_string(), String::new()),
+        ]);
+        let code_contents = HashMap::from([("src/routes/users.py".to_string(), masked.code)]);
+
+        let edges = infer_call_edges(&[transport, service], &file_contents, &code_contents, &ModuleIndex::default());
+        assert!(edges.is_empty());
     }
 
     #[test]
@@ -625,13 +1926,19 @@ async def create_msg_route(body: MessageCreate):
         file_contents.insert("src/crud/messages.py".to_string(), String::new());
 
         let components = vec![transport, service];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
 
         assert!(!edges.is_empty());
         let calls: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("calls")).collect();
         assert_eq!(calls.len(), 1);
         assert_eq!(calls[0].from_id, "tp1");
         assert_eq!(calls[0].to_id, "svc1");
+
+        let evidence = calls[0].evidence.as_ref().unwrap();
+        assert_eq!(evidence.heuristic, "module_call");
+        assert_eq!(evidence.source.file, "src/routes/messages.py");
+        assert_eq!(evidence.source.line_start, Some(5));
+        assert_eq!(evidence.snippet.as_deref(), Some("crud.create_message("));
     }
 
     #[test]
@@ -649,7 +1956,7 @@ async def create_route(body: ItemCreate, background_tasks: BackgroundTasks):
         file_contents.insert("src/routes/api.py".to_string(), file_content.to_string());
 
         let components = vec![transport, worker];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
 
         let dispatches: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("dispatches")).collect();
         assert_eq!(dispatches.len(), 1);
@@ -672,7 +1979,7 @@ async def get_route():
         file_contents.insert("src/routes/api.py".to_string(), file_content.to_string());
 
         let components = vec![transport, service];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
 
         let calls: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("calls")).collect();
         assert_eq!(calls.len(), 1);
@@ -694,7 +2001,7 @@ async def route():
         file_contents.insert("src/routes/api.py".to_string(), file_content.to_string());
 
         let components = vec![transport];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
         assert!(edges.is_empty());
     }
 
@@ -713,7 +2020,7 @@ async def route():
         file_contents.insert("src/routes/api.py".to_string(), file_content.to_string());
 
         let components = vec![transport];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
         assert!(edges.is_empty());
     }
 
@@ -732,7 +2039,7 @@ async def route():
         file_contents.insert("src/routes/api.py".to_string(), file_content.to_string());
 
         let components = vec![transport, service];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
 
         let calls: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("calls")).collect();
         assert_eq!(calls.len(), 1);
@@ -763,13 +2070,28 @@ async def create_user_route(body: UserCreate):
         file_contents.insert("src/routes.py".to_string(), file_content.to_string());
 
         let components = vec![transport, model];
-        let edges = infer_flow_edges(&components, &file_contents);
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
 
         let persists: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("persists")).collect();
         assert_eq!(persists.len(), 1);
         assert_eq!(persists[0].payload_type, Some("User".to_string()));
     }
 
+    #[test]
+    fn persists_ignores_models_named_only_in_strings() {
+        use crate::scanner::lexer;
+
+        let transport = make_comp("tp1", "create_user_route", ComponentKind::Transport, "src/routes.py", 1);
+        let model = make_comp("m1", "User", ComponentKind::Model, "src/models.py", 1);
+
+        let masked = lexer::mask("@router.post(\"/users\")\nasync def create_user_route(body):\n    log(\"User created\")\n", "python");
+        let file_contents = HashMap::from([("src/routes.py".to_string(), masked.text)]);
+        let code_contents = HashMap::from([("src/routes.py".to_string(), masked.code)]);
+
+        let edges = infer_flow_edges(&[transport, model], &file_contents, &code_contents);
+        assert!(edges.iter().all(|e| e.label.as_deref() != Some("persists")));
+    }
+
     #[test]
     fn handles_edge_gets_transport_payload_type() {
         let service = make_comp("svc1", "UserService", ComponentKind::Service, "src/users.py", 1);
@@ -780,7 +2102,7 @@ async def create_user_route(body: UserCreate):
 
         let file_contents = HashMap::new();
         let components = vec![service, transport];
-        let edges = infer_flow_edges(&components, &file_contents);
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
 
         let handles: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("handles")).collect();
         assert_eq!(handles.len(), 1);
@@ -807,7 +2129,7 @@ async def create_msg_route(body: MessageCreate):
         file_contents.insert("src/crud/messages.py".to_string(), String::new());
 
         let components = vec![transport, service];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
 
         let calls: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("calls")).collect();
         assert_eq!(calls.len(), 1);
@@ -831,7 +2153,7 @@ class RelevanceFilter:
         file_contents.insert("src/services/generator.py".to_string(), String::new());
 
         let components = vec![svc1, svc2];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
 
         let calls: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("calls")).collect();
         assert_eq!(calls.len(), 1);
@@ -857,7 +2179,7 @@ class Scout:
         file_contents.insert("src/filter.py".to_string(), String::new());
 
         let components = vec![svc1, svc2];
-        let edges = infer_call_edges(&components, &file_contents);
+        let edges = infer_call_edges(&components, &file_contents, &HashMap::new(), &ModuleIndex::default());
 
         let calls: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("calls")).collect();
         assert_eq!(calls.len(), 1);
@@ -880,7 +2202,7 @@ class StateManager:
         file_contents.insert("src/services/state.py".to_string(), file_content.to_string());
 
         let components = vec![svc, model];
-        let edges = infer_flow_edges(&components, &file_contents);
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
 
         let persists: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("persists")).collect();
         assert_eq!(persists.len(), 1);
@@ -906,7 +2228,7 @@ class RelevanceFilter:
         file_contents.insert("src/services/filter.py".to_string(), file_content.to_string());
 
         let components = vec![svc, model1, model2];
-        let edges = infer_flow_edges(&components, &file_contents);
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
 
         let persists: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("persists")).collect();
         assert_eq!(persists.len(), 2);
@@ -915,6 +2237,49 @@ class RelevanceFilter:
         assert!(target_ids.contains(&"m2"));
     }
 
+    #[test]
+    fn body_span_excludes_neighbouring_handlers() {
+        let mut first = make_comp("tp1", "list_users", ComponentKind::Transport, "src/routes.py", 2);
+        first.source.line_end = Some(4);
+        let mut second = make_comp("tp2", "create_order", ComponentKind::Transport, "src/routes.py", 6);
+        second.source.line_end = Some(8);
+        let model = make_comp("m1", "Order", ComponentKind::Model, "src/models.py", 1);
+
+        let file_content = r#"
+@router.get("/users")
+async def list_users():
+    return []
+
+@router.post("/orders")
+async def create_order():
+    return Order()
+"#;
+        let mut file_contents = HashMap::new();
+        file_contents.insert("src/routes.py".to_string(), file_content.to_string());
+
+        let components = vec![first, second, model];
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
+
+        let persists: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("persists")).collect();
+        assert_eq!(persists.len(), 1);
+        assert_eq!(persists[0].from_id, "tp2");
+    }
+
+    #[test]
+    fn service_span_bounds_invokes_prompt() {
+        let mut svc = make_comp("svc1", "Generator", ComponentKind::Service, "src/gen.py", 10);
+        svc.source.line_end = Some(20);
+        let inside = make_comp("pr1", "generate", ComponentKind::Prompt, "src/gen.py", 15);
+        let outside = make_comp("pr2", "helper", ComponentKind::Prompt, "src/gen.py", 30);
+
+        let components = vec![svc, inside, outside];
+        let edges = infer_flow_edges(&components, &HashMap::new(), &HashMap::new());
+
+        let invokes: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("invokes_prompt")).collect();
+        assert_eq!(invokes.len(), 1);
+        assert_eq!(invokes[0].to_id, "pr1");
+    }
+
     #[test]
     fn import_index_bare_dot_relative() {
         let content = "from . import crud\n";
@@ -951,6 +2316,20 @@ class RelevanceFilter:
         assert_eq!(index.get("crud").map(|s| s.as_str()), Some("crud"));
     }
 
+    #[test]
+    fn import_bindings_cover_every_name_in_a_list() {
+        let content = "from .routers import users, orders as o  # noqa\nfrom app.models import (\n    User,\n    Order,  # kept\n)\nimport os.path, json\n";
+        let bindings = build_import_bindings(content);
+        let binding = |alias: &str| bindings.get(alias).map(|b| (b.module.as_str(), b.name.as_str()));
+        assert_eq!(binding("users"), Some((".routers", "users")));
+        assert_eq!(binding("o"), Some((".routers", "orders")));
+        assert_eq!(binding("User"), Some(("app.models", "User")));
+        assert_eq!(binding("Order"), Some(("app.models", "Order")));
+        assert_eq!(binding("os"), Some(("", "os")));
+        assert_eq!(binding("json"), Some(("", "json")));
+        assert_eq!(bindings.len(), 6);
+    }
+
     #[test]
     fn service_invokes_prompt_same_file() {
         let svc = make_comp("svc1", "CommentGenerator", ComponentKind::Service, "src/generator.py", 10);
@@ -958,7 +2337,7 @@ class RelevanceFilter:
 
         let file_contents = HashMap::new();
         let components = vec![svc, prompt];
-        let edges = infer_flow_edges(&components, &file_contents);
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
 
         let invokes: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("invokes_prompt")).collect();
         assert_eq!(invokes.len(), 1);
@@ -974,7 +2353,7 @@ class RelevanceFilter:
 
         let file_contents = HashMap::new();
         let components = vec![svc, pr1, pr2];
-        let edges = infer_flow_edges(&components, &file_contents);
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
 
         let invokes: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("invokes_prompt")).collect();
         assert_eq!(invokes.len(), 2);
@@ -990,7 +2369,7 @@ class RelevanceFilter:
 
         let file_contents = HashMap::new();
         let components = vec![svc, prompt];
-        let edges = infer_flow_edges(&components, &file_contents);
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
 
         let invokes: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("invokes_prompt")).collect();
         assert!(invokes.is_empty());
@@ -1003,46 +2382,9 @@ class RelevanceFilter:
 
         let file_contents = HashMap::new();
         let components = vec![svc, prompt];
-        let edges = infer_flow_edges(&components, &file_contents);
+        let edges = infer_flow_edges(&components, &file_contents, &HashMap::new());
 
         let invokes: Vec<_> = edges.iter().filter(|e| e.label.as_deref() == Some("invokes_prompt")).collect();
         assert!(invokes.is_empty());
     }
 }
-
-/// Resolve a function call target to a component ID
-fn resolve_call_target(
-    func_name: &str,
-    resolved_module: Option<&str>,
-    module_alias: &str,
-    name_index: &HashMap<String, Vec<usize>>,
-    components: &[DetectedComponent],
-    stem_to_file: &HashMap<String, Vec<String>>,
-    by_file: &HashMap<&str, Vec<&DetectedComponent>>,
-) -> Option<String> {
-    // Strategy 1: If we have a resolved module path, find components in files matching that module
-    let module_key = resolved_module.unwrap_or(module_alias);
-    // Get the last segment of dotted path (e.g., "app.crud" -> "crud")
-    let module_stem = module_key.rsplit('.').next().unwrap_or(module_key);
-
-    if let Some(files) = stem_to_file.get(module_stem) {
-        for file in files {
-            if let Some(comps) = by_file.get(file.as_str()) {
-                for comp in comps {
-                    if comp.name == func_name {
-                        return Some(comp.id.clone());
-                    }
-                }
-            }
-        }
-    }
-
-    // Strategy 2: Fall back to name-only matching
-    if let Some(targets) = name_index.get(func_name) {
-        if targets.len() == 1 {
-            return Some(components[targets[0]].id.clone());
-        }
-    }
-
-    None
-}
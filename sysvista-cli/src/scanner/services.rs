@@ -4,7 +4,10 @@ use std::sync::LazyLock;
 
 use crate::output::schema::{ComponentKind, DetectedComponent, SourceLocation};
 
+use super::lexer::is_code;
 use super::make_id;
+use super::spans::definition_end;
+use super::syntax::{Definition, DefinitionKind, SyntaxIndex};
 
 struct ServicePattern {
     regex: Regex,
@@ -51,6 +54,7 @@ fn is_service_dir(file: &str) -> bool {
 
 pub fn detect_services(
     content: &str,
+    code: &str,
     language: &str,
     file: &str,
 ) -> Vec<DetectedComponent> {
@@ -61,6 +65,9 @@ pub fn detect_services(
         for cap in pattern.regex.captures_iter(content) {
             let name = cap[pattern.name_group].to_string();
             let match_start = cap.get(0).unwrap().start();
+            if !is_code(code, match_start) {
+                continue;
+            }
             let line_num = content[..match_start].lines().count() as u32 + 1;
 
             components.push(DetectedComponent {
@@ -71,16 +78,10 @@ pub fn detect_services(
                 source: SourceLocation {
                     file: file.to_string(),
                     line_start: Some(line_num),
-                    line_end: None,
+                    line_end: definition_end(content, language, line_num),
                 },
                 metadata: HashMap::from([("detection".to_string(), "decorator".to_string())]),
-                transport_protocol: None,
-                http_method: None,
-                http_path: None,
-                model_fields: None,
-                prompt_subtype: None,
-                consumes: None,
-                produces: None,
+                ..Default::default()
             });
         }
     }
@@ -91,6 +92,9 @@ pub fn detect_services(
         for cap in CLASS_PATTERN.captures_iter(content) {
             let name = cap[1].to_string();
             let match_start = cap.get(0).unwrap().start();
+            if !is_code(code, match_start) {
+                continue;
+            }
             let line_num = content[..match_start].lines().count() as u32 + 1;
 
             components.push(DetectedComponent {
@@ -101,19 +105,13 @@ pub fn detect_services(
                 source: SourceLocation {
                     file: file.to_string(),
                     line_start: Some(line_num),
-                    line_end: None,
+                    line_end: definition_end(content, language, line_num),
                 },
                 metadata: HashMap::from([(
                     "detection".to_string(),
                     "directory_convention".to_string(),
                 )]),
-                transport_protocol: None,
-                http_method: None,
-                http_path: None,
-                model_fields: None,
-                prompt_subtype: None,
-                consumes: None,
-                produces: None,
+                ..Default::default()
             });
         }
     }
@@ -124,6 +122,9 @@ pub fn detect_services(
         for cap in func_re.captures_iter(content) {
             let name = cap[1].to_string();
             let match_start = cap.get(0).unwrap().start();
+            if !is_code(code, match_start) {
+                continue;
+            }
             let line_num = content[..match_start].lines().count() as u32 + 1;
 
             components.push(DetectedComponent {
@@ -134,19 +135,13 @@ pub fn detect_services(
                 source: SourceLocation {
                     file: file.to_string(),
                     line_start: Some(line_num),
-                    line_end: None,
+                    line_end: definition_end(content, language, line_num),
                 },
                 metadata: HashMap::from([(
                     "detection".to_string(),
                     "directory_convention".to_string(),
                 )]),
-                transport_protocol: None,
-                http_method: None,
-                http_path: None,
-                model_fields: None,
-                prompt_subtype: None,
-                consumes: None,
-                produces: None,
+                ..Default::default()
             });
         }
     }
@@ -161,6 +156,9 @@ pub fn detect_services(
                 continue;
             }
             let match_start = cap.get(0).unwrap().start();
+            if !is_code(code, match_start) {
+                continue;
+            }
             let line_num = content[..match_start].lines().count() as u32 + 1;
 
             components.push(DetectedComponent {
@@ -171,19 +169,13 @@ pub fn detect_services(
                 source: SourceLocation {
                     file: file.to_string(),
                     line_start: Some(line_num),
-                    line_end: None,
+                    line_end: definition_end(content, language, line_num),
                 },
                 metadata: HashMap::from([(
                     "detection".to_string(),
                     "directory_convention".to_string(),
                 )]),
-                transport_protocol: None,
-                http_method: None,
-                http_path: None,
-                model_fields: None,
-                prompt_subtype: None,
-                consumes: None,
-                produces: None,
+                ..Default::default()
             });
         }
     }
@@ -199,13 +191,16 @@ pub fn detect_services(
                 continue;
             }
             let match_start = cap.get(0).unwrap().start();
+            if !is_code(code, match_start) {
+                continue;
+            }
 
             // Skip if preceded by @dataclass (those are models)
             let before = &content[..match_start];
-            if let Some(last_line) = before.lines().last() {
-                if last_line.trim().starts_with("@dataclass") {
-                    continue;
-                }
+            if let Some(last_line) = before.lines().last()
+                && last_line.trim().starts_with("@dataclass")
+            {
+                continue;
             }
 
             // Skip if inheriting from model base classes
@@ -228,19 +223,13 @@ pub fn detect_services(
                 source: SourceLocation {
                     file: file.to_string(),
                     line_start: Some(line_num),
-                    line_end: None,
+                    line_end: definition_end(content, language, line_num),
                 },
                 metadata: HashMap::from([(
                     "detection".to_string(),
                     "class_heuristic".to_string(),
                 )]),
-                transport_protocol: None,
-                http_method: None,
-                http_path: None,
-                model_fields: None,
-                prompt_subtype: None,
-                consumes: None,
-                produces: None,
+                ..Default::default()
             });
         }
     }
@@ -248,6 +237,89 @@ pub fn detect_services(
     components
 }
 
+/// Decorators that mark a class as a service (NestJS / Spring)
+const SERVICE_DECORATORS: &[&str] = &["Controller", "RestController", "Injectable", "Service"];
+
+/// Base classes that mark a Python class as a Flask/Django resource or view
+const VIEW_BASES: &[&str] = &["Resource", "View", "ViewSet", "APIView"];
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+fn service_from_definition(def: &Definition, language: &str, file: &str, detection: &str) -> DetectedComponent {
+    DetectedComponent {
+        id: make_id("service", &def.name, file),
+        name: def.name.clone(),
+        kind: ComponentKind::Service,
+        language: language.to_string(),
+        source: SourceLocation {
+            file: file.to_string(),
+            line_start: Some(def.line_start),
+            line_end: Some(def.line_end),
+        },
+        metadata: HashMap::from([("detection".to_string(), detection.to_string())]),
+        ..Default::default()
+    }
+}
+
+/// Detect services from parsed definitions, with the same precedence as
+/// `detect_services`: decorated classes, then the directory convention, then
+/// (Python only) the plain-class heuristic. Decorators and bases are read from
+/// the parse rather than the preceding line, and spans are exact.
+pub fn detect_services_from_syntax(language: &str, file: &str, syntax: &SyntaxIndex) -> Vec<DetectedComponent> {
+    let service = |def: &Definition, detection: &str| service_from_definition(def, language, file, detection);
+
+    let decorated: Vec<DetectedComponent> = syntax
+        .definitions
+        .iter()
+        .filter(|d| d.kind == DefinitionKind::Class)
+        .filter(|d| {
+            d.decorators.iter().any(|dec| SERVICE_DECORATORS.contains(&last_segment(&dec.name)))
+                || (language == "python"
+                    && d.bases.iter().any(|b| VIEW_BASES.iter().any(|v| last_segment(b).ends_with(v))))
+        })
+        .map(|d| service(d, "decorator"))
+        .collect();
+    if !decorated.is_empty() {
+        return decorated;
+    }
+
+    let top_level: Vec<&Definition> = syntax.top_level().collect();
+    let of_kind = |kind: DefinitionKind| top_level.iter().copied().filter(move |d| d.kind == kind);
+
+    if is_service_dir(file) {
+        // Classes, else exported functions, else public Python functions
+        let classes: Vec<DetectedComponent> =
+            of_kind(DefinitionKind::Class).map(|d| service(d, "directory_convention")).collect();
+        if !classes.is_empty() {
+            return classes;
+        }
+        let exported: Vec<DetectedComponent> = of_kind(DefinitionKind::Function)
+            .filter(|d| d.exported)
+            .map(|d| service(d, "directory_convention"))
+            .collect();
+        if !exported.is_empty() || language != "python" {
+            return exported;
+        }
+        return of_kind(DefinitionKind::Function)
+            .filter(|d| !d.name.starts_with('_'))
+            .map(|d| service(d, "directory_convention"))
+            .collect();
+    }
+
+    if language != "python" {
+        return Vec::new();
+    }
+    // Plain classes, except private and test classes and those that are models
+    of_kind(DefinitionKind::Class)
+        .filter(|d| !d.name.starts_with('_') && !d.name.starts_with("Test"))
+        .filter(|d| !d.decorators.iter().any(|dec| last_segment(&dec.name) == "dataclass"))
+        .filter(|d| !d.bases.iter().any(|b| MODEL_BASES.iter().any(|m| b.contains(m))))
+        .map(|d| service(d, "class_heuristic"))
+        .collect()
+}
+
 static EXPORT_FUNC_PATTERN: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?m)^export\s+(?:async\s+)?function\s+(\w+)").unwrap());
 
@@ -266,6 +338,11 @@ const MODEL_BASES: &[&str] = &["BaseModel", "Schema", "TypedDict"];
 mod tests {
     use super::*;
 
+    fn regex_services(content: &str, language: &str, file: &str) -> Vec<DetectedComponent> {
+        let masked = crate::scanner::lexer::mask(content, language);
+        detect_services(&masked.text, &masked.code, language, file)
+    }
+
     #[test]
     fn detects_python_bare_functions_in_crud_dir() {
         let content = r#"
@@ -278,7 +355,7 @@ async def get_messages(db, app_id):
 def delete_message(db, msg_id):
     pass
 "#;
-        let comps = detect_services(content, "python", "src/crud/messages.py");
+        let comps = regex_services(content, "python", "src/crud/messages.py");
         let names: Vec<&str> = comps.iter().map(|c| c.name.as_str()).collect();
         assert_eq!(names, vec!["create_message", "get_messages", "delete_message"]);
         assert!(comps.iter().all(|c| c.kind == ComponentKind::Service));
@@ -297,7 +374,7 @@ def _helper(x):
 def public_func(x):
     pass
 "#;
-        let comps = detect_services(content, "python", "src/crud/utils.py");
+        let comps = regex_services(content, "python", "src/crud/utils.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "public_func");
     }
@@ -305,7 +382,7 @@ def public_func(x):
     #[test]
     fn no_detection_outside_service_dirs() {
         let content = "async def some_func(x):\n    pass\n";
-        let comps = detect_services(content, "python", "src/utils/helpers.py");
+        let comps = regex_services(content, "python", "src/utils/helpers.py");
         assert!(comps.is_empty());
     }
 
@@ -319,7 +396,7 @@ def public_func(x):
     #[test]
     fn no_python_detection_for_non_python_language() {
         let content = "def some_func(x):\n    pass\n";
-        let comps = detect_services(content, "javascript", "src/crud/helpers.js");
+        let comps = regex_services(content, "javascript", "src/crud/helpers.js");
         assert!(comps.is_empty());
     }
 
@@ -328,7 +405,7 @@ def public_func(x):
     #[test]
     fn detects_plain_python_class_as_service() {
         let content = "class DiscordScanner:\n    pass\n";
-        let comps = detect_services(content, "python", "discord_client.py");
+        let comps = regex_services(content, "python", "discord_client.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "DiscordScanner");
         assert_eq!(comps[0].kind, ComponentKind::Service);
@@ -338,7 +415,7 @@ def public_func(x):
     #[test]
     fn detects_python_class_with_non_model_base() {
         let content = "class FarcasterScanner(SomeBase):\n    pass\n";
-        let comps = detect_services(content, "python", "farcaster.py");
+        let comps = regex_services(content, "python", "farcaster.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "FarcasterScanner");
     }
@@ -346,42 +423,42 @@ def public_func(x):
     #[test]
     fn skips_dataclass_decorated_class() {
         let content = "@dataclass(frozen=True)\nclass Message:\n    text: str\n";
-        let comps = detect_services(content, "python", "config.py");
+        let comps = regex_services(content, "python", "config.py");
         assert!(comps.is_empty());
     }
 
     #[test]
     fn skips_typed_dict_class() {
         let content = "class Config(TypedDict):\n    name: str\n";
-        let comps = detect_services(content, "python", "config.py");
+        let comps = regex_services(content, "python", "config.py");
         assert!(comps.is_empty());
     }
 
     #[test]
     fn skips_typed_dict_with_extra_args() {
         let content = "class EvalResponse(TypedDict, total=False):\n    score: float\n";
-        let comps = detect_services(content, "python", "filter.py");
+        let comps = regex_services(content, "python", "filter.py");
         assert!(comps.is_empty());
     }
 
     #[test]
     fn skips_base_model_class() {
         let content = "class User(BaseModel):\n    name: str\n";
-        let comps = detect_services(content, "python", "schemas.py");
+        let comps = regex_services(content, "python", "schemas.py");
         assert!(comps.is_empty());
     }
 
     #[test]
     fn skips_test_classes() {
         let content = "class TestScanner:\n    def test_it(self): pass\n";
-        let comps = detect_services(content, "python", "test_scanner.py");
+        let comps = regex_services(content, "python", "test_scanner.py");
         assert!(comps.is_empty());
     }
 
     #[test]
     fn skips_private_classes() {
         let content = "class _Internal:\n    pass\n";
-        let comps = detect_services(content, "python", "utils.py");
+        let comps = regex_services(content, "python", "utils.py");
         assert!(comps.is_empty());
     }
 
@@ -394,7 +471,7 @@ class RelevanceFilter:
 class CommentGenerator:
     pass
 "#;
-        let comps = detect_services(content, "python", "pipeline.py");
+        let comps = regex_services(content, "python", "pipeline.py");
         let names: Vec<&str> = comps.iter().map(|c| c.name.as_str()).collect();
         assert_eq!(names, vec!["RelevanceFilter", "CommentGenerator"]);
     }
@@ -412,15 +489,76 @@ class Config(TypedDict):
 class Scanner:
     pass
 "#;
-        let comps = detect_services(content, "python", "mixed.py");
+        let comps = regex_services(content, "python", "mixed.py");
         assert_eq!(comps.len(), 1);
         assert_eq!(comps[0].name, "Scanner");
     }
 
+    #[test]
+    fn skips_decorators_inside_string_literals() {
+        let content = "const hint = \"missing @Injectable(\";\nlogger.warn(hint)\nclass Cache {}\n";
+        assert!(regex_services(content, "typescript", "src/cache.ts").is_empty());
+    }
+
+    fn syntax_services(content: &str, language: &str, file: &str) -> Vec<DetectedComponent> {
+        let index = crate::scanner::syntax::parse(content, language, file).unwrap();
+        detect_services_from_syntax(language, file, &index)
+    }
+
+    #[test]
+    fn syntax_detects_decorated_classes_with_exact_spans() {
+        let content = "import { Injectable } from \"@nestjs/common\";\n\n@Injectable()\nexport class UserService {\n  find() {\n    return 1;\n  }\n}\n";
+        let comps = syntax_services(content, "typescript", "src/users.ts");
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].name, "UserService");
+        assert_eq!((comps[0].source.line_start, comps[0].source.line_end), (Some(3), Some(8)));
+        assert_eq!(comps[0].metadata.get("detection").unwrap(), "decorator");
+
+        let content = "class UserList(views.MethodView):\n    def get(self):\n        pass\n";
+        let comps = syntax_services(content, "python", "app/views.py");
+        assert_eq!(comps[0].metadata.get("detection").unwrap(), "decorator");
+    }
+
+    #[test]
+    fn syntax_applies_the_directory_convention_to_top_level_definitions() {
+        let content = "async def create_message(db, data):\n    def _inner():\n        pass\n    return _inner\n\ndef _helper():\n    pass\n";
+        let comps = syntax_services(content, "python", "src/crud/messages.py");
+        let names: Vec<&str> = comps.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["create_message"]);
+
+        let content = "function local() {}\nexport async function listUsers() {}\n";
+        let comps = syntax_services(content, "typescript", "src/services/users.ts");
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].name, "listUsers");
+    }
+
+    #[test]
+    fn syntax_class_heuristic_skips_models_tests_and_private_classes() {
+        let content = r#"
+@dataclasses.dataclass
+class Message:
+    text: str
+
+class Config(TypedDict, total=False):
+    name: str
+
+class TestScanner:
+    pass
+
+if ENABLED:
+    class Scanner:
+        pass
+"#;
+        let comps = syntax_services(content, "python", "mixed.py");
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].name, "Scanner");
+        assert_eq!(comps[0].metadata.get("detection").unwrap(), "class_heuristic");
+    }
+
     #[test]
     fn class_heuristic_does_not_fire_for_non_python() {
         let content = "class Scanner:\n    pass\n";
-        let comps = detect_services(content, "rust", "scanner.rs");
+        let comps = regex_services(content, "rust", "scanner.rs");
         assert!(comps.is_empty());
     }
 }
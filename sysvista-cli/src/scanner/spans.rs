@@ -0,0 +1,344 @@
+use super::lexer::{self, Region};
+
+// ---------------------------------------------------------------------------
+// Heuristic definition spans — refined by `syntax` where a grammar exists
+// ---------------------------------------------------------------------------
+
+/// The code view of a file, split into lines, with the lines that open inside a
+/// multi-line string or block comment marked.
+struct CodeLines<'a> {
+    lines: Vec<&'a str>,
+    continued: Vec<bool>,
+}
+
+impl CodeLines<'_> {
+    /// Whether line `idx` starts inside a string or comment opened above it.
+    fn continued(&self, idx: usize) -> bool {
+        self.continued.get(idx).copied().unwrap_or(false)
+    }
+}
+
+/// Mark the lines that start inside one of `regions`, including the line that
+/// holds only a block string's closing delimiter.
+fn continued_lines(content: &str, regions: &[Region]) -> Vec<bool> {
+    let mut continued = vec![false; content.lines().count() + 1];
+    let (mut line, mut pos) = (0, 0);
+    for region in regions {
+        line += content[pos..region.range.start].matches('\n').count();
+        let inside = content[region.range.clone()].matches('\n').count();
+        for flag in continued.iter_mut().skip(line + 1).take(inside) {
+            *flag = true;
+        }
+        line += inside;
+        pos = region.range.end;
+    }
+    continued
+}
+
+/// Find the last line of the definition or statement that starts at `line_start`
+/// (1-based). C-like languages are matched on brackets, Python on indentation,
+/// Ruby on the `end` aligned with the opening line. Comments and strings are
+/// masked by the [`lexer`] first. Returns `None` when the construct never
+/// closes (truncated or malformed source).
+pub fn definition_end(content: &str, language: &str, line_start: u32) -> Option<u32> {
+    let regions = lexer::lex(content, language);
+    let code = lexer::mask_regions(content, &regions).code;
+    let source = CodeLines {
+        lines: code.lines().collect(),
+        continued: continued_lines(content, &regions),
+    };
+    let start = (line_start as usize).checked_sub(1)?;
+    if start >= source.lines.len() {
+        return None;
+    }
+    let end = match language {
+        "python" => python_end(&source, start),
+        "ruby" => ruby_end(&source.lines, start),
+        _ => bracket_end(&source, start),
+    }?;
+    Some(end as u32 + 1)
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+// ---------------------------------------------------------------------------
+// C-like languages: brace matching
+// ---------------------------------------------------------------------------
+
+/// Tokens that, at the end of a line, mean the statement continues on the next.
+const CONTINUES_AFTER: &[char] = &[',', '=', '(', '.', '+', '|', '&', ':', '>', '?'];
+
+/// Tokens that, at the start of the next line, continue the current header.
+const CONTINUES_WITH: &[&str] = &["{", ".", "->", "=>", ":", "?", "|", "&", "+", "=", "where", "extends", "implements"];
+
+fn header_continues(lines: &[&str], idx: usize) -> bool {
+    let current = lines[idx].trim();
+    // Annotations, decorators and attributes belong to the definition below them
+    if current.starts_with('@') || current.starts_with("#[") || current.starts_with('[') {
+        return true;
+    }
+    if current.ends_with(CONTINUES_AFTER) || current.starts_with("where") {
+        return true;
+    }
+    match lines[idx + 1..].iter().map(|l| l.trim()).find(|l| !l.is_empty()) {
+        Some(next) => CONTINUES_WITH.iter().any(|t| next.starts_with(t)),
+        None => false,
+    }
+}
+
+/// Scan from `start` until the first `{` block closes, or — for bodiless
+/// statements — until a top-level `;` or a line that does not continue.
+fn bracket_end(source: &CodeLines, start: usize) -> Option<usize> {
+    let mut depth: i32 = 0;
+    let mut saw_brace = false;
+
+    for (idx, line) in source.lines.iter().enumerate().skip(start) {
+        for c in line.chars() {
+            match c {
+                '(' | '[' => depth += 1,
+                '{' => {
+                    depth += 1;
+                    saw_brace = true;
+                }
+                ')' | ']' | '}' => {
+                    depth -= 1;
+                    if depth <= 0 && saw_brace {
+                        return Some(idx);
+                    }
+                    depth = depth.max(0);
+                }
+                ';' if depth == 0 => return Some(idx),
+                _ => {}
+            }
+        }
+        if depth == 0 && !saw_brace && !source.continued(idx + 1) && !header_continues(&source.lines, idx) {
+            return Some(idx);
+        }
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// Python: indentation blocks
+// ---------------------------------------------------------------------------
+
+/// Track bracket depth over one line of code; returns true if a `:` appeared at
+/// depth 0.
+fn scan_line(depth: &mut i32, line: &str) -> bool {
+    let mut top_colon = false;
+    for c in line.chars() {
+        match c {
+            '(' | '[' | '{' => *depth += 1,
+            ')' | ']' | '}' => *depth = (*depth - 1).max(0),
+            ':' if *depth == 0 => top_colon = true,
+            _ => {}
+        }
+    }
+    top_colon
+}
+
+fn is_block_header(trimmed: &str) -> bool {
+    ["def ", "async def ", "class "].iter().any(|k| trimmed.starts_with(k))
+}
+
+fn python_end(source: &CodeLines, start: usize) -> Option<usize> {
+    let lines = &source.lines;
+    let base_indent = indent_of(lines[start]);
+    let mut depth = 0;
+    let mut idx = start;
+    // An open bracket or string carries the statement onto the next line
+    let in_statement = |depth: i32, idx: usize| depth > 0 || source.continued(idx + 1);
+
+    // Decorators (and their multi-line arguments) precede the definition
+    while lines[idx].trim_start().starts_with('@') {
+        scan_line(&mut depth, lines[idx]);
+        while in_statement(depth, idx) {
+            idx += 1;
+            scan_line(&mut depth, lines.get(idx)?);
+        }
+        idx += 1;
+        if idx >= lines.len() {
+            return None;
+        }
+    }
+
+    if !is_block_header(lines[idx].trim_start()) {
+        // A plain statement ends where its brackets and strings close
+        loop {
+            scan_line(&mut depth, lines[idx]);
+            if !in_statement(depth, idx) && !lines[idx].trim_end().ends_with('\\') {
+                return Some(idx);
+            }
+            idx += 1;
+            if idx >= lines.len() {
+                return None;
+            }
+        }
+    }
+
+    // Header runs until a top-level `:`
+    while !scan_line(&mut depth, lines[idx]) || in_statement(depth, idx) {
+        idx += 1;
+        if idx >= lines.len() {
+            return None;
+        }
+    }
+
+    let mut end = idx;
+    for (i, line) in lines.iter().enumerate().skip(idx + 1) {
+        if depth == 0 && !source.continued(i) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if indent_of(line) <= base_indent {
+                break;
+            }
+        }
+        scan_line(&mut depth, line);
+        end = i;
+    }
+    Some(end)
+}
+
+// ---------------------------------------------------------------------------
+// Ruby: `end` aligned with the opening keyword
+// ---------------------------------------------------------------------------
+
+fn ruby_end(lines: &[&str], start: usize) -> Option<usize> {
+    let base_indent = indent_of(lines[start]);
+    let opener = lines[start].trim();
+    if opener.ends_with(" end") || opener == "end" {
+        return Some(start);
+    }
+    lines
+        .iter()
+        .enumerate()
+        .skip(start + 1)
+        .find(|(_, l)| {
+            let t = l.trim();
+            indent_of(l) == base_indent && (t == "end" || t.starts_with("end ") || t.starts_with("end."))
+        })
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brace_block_skips_strings_and_comments() {
+        let src = "\
+export class UserService {
+  // closing } in a comment
+  greet() {
+    return \"}\" + `${'}'}`;
+  }
+}
+const next = 1;
+";
+        assert_eq!(definition_end(src, "typescript", 1), Some(6));
+        assert_eq!(definition_end(src, "typescript", 3), Some(5));
+        assert_eq!(definition_end(src, "typescript", 7), Some(7));
+    }
+
+    #[test]
+    fn express_handler_and_annotations() {
+        let src = "\
+router.post('/users', validate({ strict: true }), async (req, res) => {
+  res.json(await create(req.body));
+});
+
+@Get(':id')
+async findOne(id: string) {
+  return id;
+}
+";
+        assert_eq!(definition_end(src, "javascript", 1), Some(3));
+        assert_eq!(definition_end(src, "typescript", 5), Some(8));
+    }
+
+    #[test]
+    fn rust_lifetimes_and_where_clauses() {
+        let src = "\
+pub fn first<'a, T>(items: &'a [T]) -> &'a T
+where
+    T: Clone,
+{
+    let _c = '{';
+    &items[0]
+}
+pub struct Unit;
+";
+        assert_eq!(definition_end(src, "rust", 1), Some(7));
+        assert_eq!(definition_end(src, "rust", 8), Some(8));
+    }
+
+    #[test]
+    fn allman_braces_on_next_line() {
+        let src = "\
+public class OrderService : IOrderService
+{
+    public void Place() { }
+}
+";
+        assert_eq!(definition_end(src, "csharp", 1), Some(4));
+    }
+
+    #[test]
+    fn bodiless_definition_on_the_last_line() {
+        assert_eq!(definition_end("data class Foo(val x: Int)", "kotlin", 1), Some(1));
+        assert_eq!(definition_end("package app\n\ndata class Foo(val x: Int)\n", "kotlin", 3), Some(3));
+        assert_eq!(definition_end("data class Foo(\n    val x: Int,", "kotlin", 1), None);
+    }
+
+    #[test]
+    fn python_indentation_blocks() {
+        let src = "\
+@router.get(
+    \"/items\",
+)
+async def list_items(
+    limit: int = 10,
+) -> list[Item]:
+    \"\"\"Docstring
+with an unindented line.
+\"\"\"
+    items = fetch(
+limit)
+
+    return items
+
+class Other:
+    pass
+";
+        assert_eq!(definition_end(src, "python", 1), Some(13));
+        assert_eq!(definition_end(src, "python", 15), Some(16));
+    }
+
+    #[test]
+    fn python_statement_span() {
+        let src = "\
+PROMPT = ChatPromptTemplate.from_messages([
+    (\"system\", \"Be brief: {style}\"),
+])
+x = 1
+";
+        assert_eq!(definition_end(src, "python", 1), Some(3));
+        assert_eq!(definition_end(src, "python", 4), Some(4));
+    }
+
+    #[test]
+    fn ruby_aligned_end() {
+        let src = "\
+class UsersController < ApplicationController
+  def index
+    render json: User.all
+  end
+end
+";
+        assert_eq!(definition_end(src, "ruby", 1), Some(5));
+        assert_eq!(definition_end(src, "ruby", 2), Some(4));
+    }
+}
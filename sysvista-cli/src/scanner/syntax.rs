@@ -0,0 +1,604 @@
+use std::ops::Range;
+
+use tree_sitter::{Language, Node, Parser};
+
+use crate::output::schema::DetectedComponent;
+
+// ---------------------------------------------------------------------------
+// Syntax index — definitions extracted from a tree-sitter parse
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Interface,
+    Enum,
+    TypeAlias,
+    Trait,
+    Impl,
+    Module,
+}
+
+/// A decorator (Python/TypeScript) or attribute (Rust) attached to a definition.
+/// `@router.get("/x", tags=["a"])` → name `router.get`, arguments `"/x", tags=["a"]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decorator {
+    pub name: String,
+    /// Text between the call's parentheses; None when the decorator isn't called
+    pub arguments: Option<String>,
+    pub line: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub name: String,
+    pub kind: DefinitionKind,
+    /// First line of the definition, including decorators (1-based)
+    pub line_start: u32,
+    /// Line holding the definition's name
+    pub name_line: u32,
+    /// Last line of the definition (1-based, inclusive)
+    pub line_end: u32,
+    /// Byte range of the definition keyword through the end of the definition
+    pub byte_range: Range<usize>,
+    pub decorators: Vec<Decorator>,
+    /// Declared base classes / extended interfaces, as written
+    pub bases: Vec<String>,
+    /// Declared with `export` (TypeScript/JavaScript)
+    pub exported: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct SyntaxIndex {
+    pub definitions: Vec<Definition>,
+}
+
+impl SyntaxIndex {
+    /// The innermost definition whose header (decorators through name line) covers `line`.
+    pub fn definition_at(&self, line: u32) -> Option<&Definition> {
+        self.definitions
+            .iter()
+            .filter(|d| d.line_start <= line && line <= d.name_line)
+            .min_by_key(|d| d.line_end - d.line_start)
+    }
+
+    /// Definitions not nested inside another definition, in source order.
+    pub fn top_level(&self) -> impl Iterator<Item = &Definition> {
+        self.definitions.iter().filter(|d| {
+            !self.definitions.iter().any(|outer| {
+                outer.byte_range != d.byte_range
+                    && outer.byte_range.start <= d.byte_range.start
+                    && d.byte_range.end <= outer.byte_range.end
+            })
+        })
+    }
+}
+
+fn grammar(language: &str, file: &str) -> Option<Language> {
+    match language {
+        // The TypeScript grammar rejects JSX, and TSX rejects `<T>value` casts
+        "typescript" if file.ends_with(".tsx") => Some(tree_sitter_typescript::LANGUAGE_TSX.into()),
+        "typescript" => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+        // TSX is a superset of JavaScript (including JSX), so it parses .js/.jsx as well
+        "javascript" => Some(tree_sitter_typescript::LANGUAGE_TSX.into()),
+        "python" => Some(tree_sitter_python::LANGUAGE.into()),
+        "rust" => Some(tree_sitter_rust::LANGUAGE.into()),
+        "go" => Some(tree_sitter_go::LANGUAGE.into()),
+        _ => None,
+    }
+}
+
+/// Parse a file and index its definitions. Returns None for languages without a
+/// grammar, so callers can fall back to the regex detectors.
+pub fn parse(content: &str, language: &str, file: &str) -> Option<SyntaxIndex> {
+    let mut parser = Parser::new();
+    parser.set_language(&grammar(language, file)?).ok()?;
+    let tree = parser.parse(content, None)?;
+
+    let mut index = SyntaxIndex::default();
+    collect_definitions(tree.root_node(), content, language, &mut index.definitions);
+    Some(index)
+}
+
+fn line_of(row: usize) -> u32 {
+    row as u32 + 1
+}
+
+fn text<'a>(node: Node, content: &'a str) -> &'a str {
+    node.utf8_text(content.as_bytes()).unwrap_or("")
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+fn collect_definitions(node: Node, content: &str, language: &str, out: &mut Vec<Definition>) {
+    let def = match language {
+        "python" => python_definition(node, content),
+        "typescript" | "javascript" => ts_definition(node, content),
+        "rust" => rust_definition(node, content),
+        "go" => go_definition(node, content),
+        _ => None,
+    };
+    if let Some(def) = def {
+        out.push(def);
+    }
+    for child in named_children(node) {
+        collect_definitions(child, content, language, out);
+    }
+}
+
+/// Build a definition from a node with a `name` field
+fn definition(
+    node: Node,
+    kind: DefinitionKind,
+    name: String,
+    name_node: Node,
+    decorators: Vec<Decorator>,
+    bases: Vec<String>,
+) -> Definition {
+    let first_decorator = decorators.iter().map(|d| d.line).min();
+    let line_start = line_of(node.start_position().row);
+    Definition {
+        name,
+        kind,
+        line_start: first_decorator.map_or(line_start, |l| l.min(line_start)),
+        name_line: line_of(name_node.start_position().row),
+        line_end: line_of(node.end_position().row),
+        byte_range: node.start_byte()..node.end_byte(),
+        decorators,
+        bases: bases.into_iter().filter(|b| !b.is_empty()).collect(),
+        exported: node.parent().is_some_and(|p| p.kind() == "export_statement"),
+    }
+}
+
+/// Split a decorator expression into its callee name and argument text
+fn decorator_from_expr(expr: Node, content: &str, line: u32) -> Decorator {
+    let is_call = expr.kind() == "call" || expr.kind() == "call_expression";
+    let callee = if is_call { expr.child_by_field_name("function") } else { Some(expr) };
+    let arguments = is_call
+        .then(|| expr.child_by_field_name("arguments"))
+        .flatten()
+        .map(|args| {
+            let list = text(args, content);
+            list.strip_prefix('(').and_then(|l| l.strip_suffix(')')).unwrap_or(list).to_string()
+        });
+    Decorator {
+        name: callee.map(|c| text(c, content).to_string()).unwrap_or_default(),
+        arguments,
+        line,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Python
+// ---------------------------------------------------------------------------
+
+fn python_definition(node: Node, content: &str) -> Option<Definition> {
+    let kind = match node.kind() {
+        "function_definition" => {
+            let in_class = node
+                .parent()
+                .and_then(|p| if p.kind() == "decorated_definition" { p.parent() } else { Some(p) })
+                .is_some_and(|p| p.kind() == "block" && p.parent().is_some_and(|c| c.kind() == "class_definition"));
+            if in_class {
+                DefinitionKind::Method
+            } else {
+                DefinitionKind::Function
+            }
+        }
+        "class_definition" => DefinitionKind::Class,
+        _ => return None,
+    };
+    let name_node = node.child_by_field_name("name")?;
+
+    let decorators = node
+        .parent()
+        .filter(|p| p.kind() == "decorated_definition")
+        .map(|p| {
+            named_children(p)
+                .into_iter()
+                .filter(|c| c.kind() == "decorator")
+                .filter_map(|d| {
+                    let expr = d.named_child(0)?;
+                    Some(decorator_from_expr(expr, content, line_of(d.start_position().row)))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let bases = node
+        .child_by_field_name("superclasses")
+        .map(|args| {
+            named_children(args)
+                .into_iter()
+                .filter(|a| a.kind() != "keyword_argument")
+                .map(|a| text(a, content).to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(definition(
+        node,
+        kind,
+        text(name_node, content).to_string(),
+        name_node,
+        decorators,
+        bases,
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// TypeScript / JavaScript
+// ---------------------------------------------------------------------------
+
+/// Decorators directly on a node plus those on a wrapping `export` statement
+/// (`@Controller() export class X`).
+fn ts_decorators(node: Node, content: &str) -> Vec<Decorator> {
+    let mut owners = vec![node];
+    if let Some(parent) = node.parent().filter(|p| p.kind() == "export_statement") {
+        owners.push(parent);
+    }
+    let mut decorators: Vec<Decorator> = owners
+        .into_iter()
+        .flat_map(named_children)
+        .filter(|c| c.kind() == "decorator")
+        .filter_map(|d| {
+            let expr = d.named_child(0)?;
+            Some(decorator_from_expr(expr, content, line_of(d.start_position().row)))
+        })
+        .collect();
+
+    // Method decorators are siblings preceding the method in the class body
+    if node.kind() == "method_definition" {
+        let mut prev = node.prev_named_sibling();
+        while let Some(p) = prev.filter(|p| p.kind() == "decorator") {
+            if let Some(expr) = p.named_child(0) {
+                decorators.insert(0, decorator_from_expr(expr, content, line_of(p.start_position().row)));
+            }
+            prev = p.prev_named_sibling();
+        }
+    }
+    decorators
+}
+
+fn ts_heritage(node: Node, content: &str) -> Vec<String> {
+    let mut bases = Vec::new();
+    for child in named_children(node) {
+        match child.kind() {
+            "class_heritage" => {
+                for clause in named_children(child) {
+                    for value in named_children(clause) {
+                        bases.push(text(value, content).to_string());
+                    }
+                }
+            }
+            "extends_type_clause" => {
+                for value in named_children(child) {
+                    bases.push(text(value, content).to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    bases
+}
+
+fn ts_definition(node: Node, content: &str) -> Option<Definition> {
+    let kind = match node.kind() {
+        "function_declaration" | "generator_function_declaration" => DefinitionKind::Function,
+        "method_definition" => DefinitionKind::Method,
+        "class_declaration" | "abstract_class_declaration" => DefinitionKind::Class,
+        "interface_declaration" => DefinitionKind::Interface,
+        "type_alias_declaration" => DefinitionKind::TypeAlias,
+        "enum_declaration" => DefinitionKind::Enum,
+        "internal_module" | "module" => DefinitionKind::Module,
+        // const handler = async (req) => { ... }
+        "variable_declarator" => {
+            let value = node.child_by_field_name("value")?;
+            if !matches!(value.kind(), "arrow_function" | "function_expression" | "function") {
+                return None;
+            }
+            let name_node = node.child_by_field_name("name")?;
+            // Span the whole `const x = ...;` statement
+            let stmt = node
+                .parent()
+                .filter(|p| matches!(p.kind(), "lexical_declaration" | "variable_declaration"))
+                .unwrap_or(node);
+            return Some(definition(
+                stmt,
+                DefinitionKind::Function,
+                text(name_node, content).to_string(),
+                name_node,
+                Vec::new(),
+                Vec::new(),
+            ));
+        }
+        _ => return None,
+    };
+    let name_node = node.child_by_field_name("name")?;
+    Some(definition(
+        node,
+        kind,
+        text(name_node, content).to_string(),
+        name_node,
+        ts_decorators(node, content),
+        ts_heritage(node, content),
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Rust
+// ---------------------------------------------------------------------------
+
+/// `#[...]` attributes preceding an item, as decorators
+fn rust_attributes(node: Node, content: &str) -> Vec<Decorator> {
+    let mut attrs = Vec::new();
+    let mut prev = node.prev_named_sibling();
+    while let Some(p) = prev.filter(|p| p.kind() == "attribute_item") {
+        if let Some(attr) = p.named_child(0) {
+            let path = attr.named_child(0).map(|n| text(n, content).to_string()).unwrap_or_default();
+            attrs.insert(
+                0,
+                Decorator {
+                    name: path,
+                    arguments: None,
+                    line: line_of(p.start_position().row),
+                },
+            );
+        }
+        prev = p.prev_named_sibling();
+    }
+    attrs
+}
+
+fn rust_definition(node: Node, content: &str) -> Option<Definition> {
+    let kind = match node.kind() {
+        "function_item" => {
+            let in_impl = node
+                .parent()
+                .and_then(|p| p.parent())
+                .is_some_and(|p| matches!(p.kind(), "impl_item" | "trait_item"));
+            if in_impl {
+                DefinitionKind::Method
+            } else {
+                DefinitionKind::Function
+            }
+        }
+        "struct_item" => DefinitionKind::Struct,
+        "enum_item" => DefinitionKind::Enum,
+        "trait_item" => DefinitionKind::Trait,
+        "type_item" => DefinitionKind::TypeAlias,
+        "mod_item" => DefinitionKind::Module,
+        "impl_item" => {
+            // impl [Trait for] Type — named after the full header
+            let ty = node.child_by_field_name("type")?;
+            let name = match node.child_by_field_name("trait") {
+                Some(tr) => format!("{} for {}", text(tr, content), text(ty, content)),
+                None => text(ty, content).to_string(),
+            };
+            return Some(definition(
+                node,
+                DefinitionKind::Impl,
+                name,
+                ty,
+                rust_attributes(node, content),
+                Vec::new(),
+            ));
+        }
+        _ => return None,
+    };
+    let name_node = node.child_by_field_name("name")?;
+    Some(definition(
+        node,
+        kind,
+        text(name_node, content).to_string(),
+        name_node,
+        rust_attributes(node, content),
+        Vec::new(),
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Go
+// ---------------------------------------------------------------------------
+
+fn go_definition(node: Node, content: &str) -> Option<Definition> {
+    let kind = match node.kind() {
+        "function_declaration" => DefinitionKind::Function,
+        "method_declaration" => DefinitionKind::Method,
+        "type_spec" => match node.child_by_field_name("type")?.kind() {
+            "struct_type" => DefinitionKind::Struct,
+            "interface_type" => DefinitionKind::Interface,
+            _ => DefinitionKind::TypeAlias,
+        },
+        _ => return None,
+    };
+    let name_node = node.child_by_field_name("name")?;
+    Some(definition(
+        node,
+        kind,
+        text(name_node, content).to_string(),
+        name_node,
+        Vec::new(),
+        Vec::new(),
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Component annotation
+// ---------------------------------------------------------------------------
+
+/// Replace the heuristic `line_end` of regex-detected components with the exact
+/// end of the definition they sit on. Transports resolve to the handler their
+/// route decorator is attached to; components that are not on a definition
+/// header (e.g. SDK calls inside a function body) keep the heuristic span.
+pub fn annotate_spans(components: &mut [DetectedComponent], index: &SyntaxIndex) {
+    for comp in components.iter_mut() {
+        let Some(line) = comp.source.line_start else {
+            continue;
+        };
+        if let Some(def) = index.definition_at(line) {
+            comp.source.line_end = Some(def.line_end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(index: &'a SyntaxIndex, name: &str) -> &'a Definition {
+        index
+            .definitions
+            .iter()
+            .find(|d| d.name == name)
+            .unwrap_or_else(|| panic!("no definition named {name}"))
+    }
+
+    #[test]
+    fn python_definitions_with_decorators_and_bases() {
+        let content = r#"
+@dataclass(frozen=True)
+class Message:
+    text: str
+
+class User(BaseModel, table=True):
+    name: str
+
+    def greet(self):
+        return "hi"
+
+@router.get("/users/{id}")
+async def get_user(id: str):
+    user = User(name=id)
+    return user
+"#;
+        let index = parse(content, "python", "app.py").unwrap();
+
+        let message = find(&index, "Message");
+        assert_eq!(message.kind, DefinitionKind::Class);
+        assert_eq!((message.line_start, message.name_line, message.line_end), (2, 3, 4));
+        assert_eq!(message.decorators[0].name, "dataclass");
+
+        let user = find(&index, "User");
+        assert_eq!(user.bases, vec!["BaseModel"]);
+        assert_eq!(find(&index, "greet").kind, DefinitionKind::Method);
+
+        let handler = find(&index, "get_user");
+        assert_eq!((handler.line_start, handler.line_end), (12, 15));
+        assert_eq!(handler.decorators[0].name, "router.get");
+        assert_eq!(handler.decorators[0].arguments.as_deref(), Some("\"/users/{id}\""));
+        assert_eq!(message.decorators[0].arguments.as_deref(), Some("frozen=True"));
+    }
+
+    #[test]
+    fn python_indented_definitions_are_found() {
+        let content = "if TYPE_CHECKING:\n    class Hidden(BaseModel):\n        x: int\n";
+        let index = parse(content, "python", "app.py").unwrap();
+        let hidden = find(&index, "Hidden");
+        assert_eq!((hidden.line_start, hidden.line_end), (2, 3));
+    }
+
+    #[test]
+    fn typescript_definitions_and_export_decorators() {
+        let content = r#"
+export interface User extends Base {
+  id: string;
+}
+
+@Controller("users")
+export class UserController {
+  @Get(":id")
+  findOne(id: string) {
+    return id;
+  }
+}
+
+export const toDto = async (user: User) => {
+  return { id: user.id };
+};
+"#;
+        let index = parse(content, "typescript", "app.ts").unwrap();
+
+        let user = find(&index, "User");
+        assert_eq!(user.kind, DefinitionKind::Interface);
+        assert_eq!((user.line_start, user.line_end), (2, 4));
+        assert_eq!(user.bases, vec!["Base"]);
+
+        let controller = find(&index, "UserController");
+        assert_eq!(controller.decorators[0].name, "Controller");
+        assert_eq!(controller.line_start, 6);
+        assert_eq!(controller.line_end, 12);
+
+        let method = find(&index, "findOne");
+        assert_eq!(method.decorators[0].name, "Get");
+        assert_eq!((method.line_start, method.line_end), (8, 11));
+
+        let arrow = find(&index, "toDto");
+        assert_eq!(arrow.kind, DefinitionKind::Function);
+        assert_eq!((arrow.line_start, arrow.line_end), (14, 16));
+    }
+
+    #[test]
+    fn tsx_files_parse_jsx() {
+        let content = "export const List = ({ items }: Props) => (\n  <ul>\n    {items.map((i) => <li key={i}>{i}</li>)}\n  </ul>\n);\n\nexport function After() {}\n";
+        let index = parse(content, "typescript", "src/List.tsx").unwrap();
+        let list = find(&index, "List");
+        assert_eq!((list.line_start, list.line_end), (1, 5));
+        assert_eq!(find(&index, "After").line_start, 7);
+
+        // Plain .ts keeps the grammar that reads `<T>value` as a cast
+        let index = parse("const id = <string>raw;\nexport function f() {}\n", "typescript", "src/cast.ts").unwrap();
+        assert_eq!(find(&index, "f").line_start, 2);
+    }
+
+    #[test]
+    fn rust_items_with_attributes() {
+        let content = r#"
+#[derive(Debug, Clone)]
+pub(crate) struct Config {
+    name: String,
+}
+
+impl From<Config> for String {
+    fn from(c: Config) -> Self {
+        c.name
+    }
+}
+"#;
+        let index = parse(content, "rust", "lib.rs").unwrap();
+
+        let config = find(&index, "Config");
+        assert_eq!(config.kind, DefinitionKind::Struct);
+        assert_eq!((config.line_start, config.name_line, config.line_end), (2, 3, 5));
+        assert_eq!(config.decorators[0].name, "derive");
+
+        let imp = find(&index, "From<Config> for String");
+        assert_eq!(imp.kind, DefinitionKind::Impl);
+        assert_eq!(find(&index, "from").kind, DefinitionKind::Method);
+    }
+
+    #[test]
+    fn go_grouped_type_specs() {
+        let content = "package store\n\ntype (\n\tUser struct {\n\t\tID string\n\t}\n\tStore interface {\n\t\tSave(u User) error\n\t}\n)\n\nfunc (s *PG) Save(u User) error {\n\treturn nil\n}\n";
+        let index = parse(content, "go", "store.go").unwrap();
+        let user = find(&index, "User");
+        assert_eq!(user.kind, DefinitionKind::Struct);
+        assert_eq!((user.line_start, user.line_end), (4, 6));
+        assert_eq!(find(&index, "Store").kind, DefinitionKind::Interface);
+        let save = find(&index, "Save");
+        assert_eq!(save.kind, DefinitionKind::Method);
+        assert_eq!((save.line_start, save.line_end), (12, 14));
+    }
+
+    #[test]
+    fn unsupported_language_has_no_index() {
+        assert!(parse("message Foo {}", "protobuf", "foo.proto").is_none());
+        assert!(parse("class Foo; end", "ruby", "foo.rb").is_none());
+    }
+}
@@ -4,7 +4,11 @@ use std::sync::LazyLock;
 
 use crate::output::schema::{ComponentKind, DetectedComponent, SourceLocation};
 
+use super::fields::delimited;
+use super::lexer::is_code;
 use super::make_id;
+use super::spans::definition_end;
+use super::syntax::{Definition, DefinitionKind, SyntaxIndex};
 
 // Functions named to_*, from_*, convert*, transform*
 static FUNC_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
@@ -26,8 +30,31 @@ static FUNC_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
     ]
 });
 
+/// How a transform was recognised, for its `detection` metadata: `name_only`
+/// unless the parameter list after the name is followed by a declared return
+/// type (`-> Dto`, `): Dto`, Go's `() Dto`), which makes it `typed_signature`.
+fn signature_detection(after_name: &str, language: &str) -> &'static str {
+    let typed = after_name.find('(').is_some_and(|open| {
+        delimited(after_name, open, b'(', b')').is_some_and(|params| {
+            let rest = after_name[open + params.len() + 2..].trim_start();
+            match language {
+                "rust" | "python" => rest.starts_with("->"),
+                "typescript" => rest.starts_with(':'),
+                "go" => !rest.is_empty() && !rest.starts_with('{'),
+                _ => false,
+            }
+        })
+    });
+    if typed { "typed_signature" } else { "name_only" }
+}
+
+fn detection_metadata(detection: &str) -> HashMap<String, String> {
+    HashMap::from([("detection".to_string(), detection.to_string())])
+}
+
 pub fn detect_transforms(
     content: &str,
+    code: &str,
     language: &str,
     file: &str,
 ) -> Vec<DetectedComponent> {
@@ -36,13 +63,16 @@ pub fn detect_transforms(
     for pattern in FUNC_PATTERNS.iter() {
         for cap in pattern.captures_iter(content) {
             // For Rust `impl From<A> for B`, build a special name
-            let name = if cap.get(2).is_some() {
-                format!("From<{}> for {}", &cap[1], &cap[2])
+            let (name, detection) = if cap.get(2).is_some() {
+                (format!("From<{}> for {}", &cap[1], &cap[2]), "trait_impl")
             } else {
-                cap[1].to_string()
+                (cap[1].to_string(), signature_detection(&content[cap.get(1).unwrap().end()..], language))
             };
 
             let match_start = cap.get(0).unwrap().start();
+            if !is_code(code, match_start) {
+                continue;
+            }
             let line_num = content[..match_start].lines().count() as u32 + 1;
 
             components.push(DetectedComponent {
@@ -53,19 +83,141 @@ pub fn detect_transforms(
                 source: SourceLocation {
                     file: file.to_string(),
                     line_start: Some(line_num),
-                    line_end: None,
+                    line_end: definition_end(content, language, line_num),
                 },
-                metadata: HashMap::new(),
-                transport_protocol: None,
-                http_method: None,
-                http_path: None,
-                model_fields: None,
-                prompt_subtype: None,
-                consumes: None,
-                produces: None,
+                metadata: detection_metadata(detection),
+                ..Default::default()
             });
         }
     }
 
     components
 }
+
+/// `to*`, `from*`, `convert*` and `transform*` names, spelled `to_`/`from_` in
+/// Rust and Python and exported (`To`, `From`) in Go
+fn is_transform_name(name: &str, language: &str) -> bool {
+    let prefixes: &[&str] = match language {
+        "rust" | "python" => &["to_", "from_", "convert", "transform"],
+        "go" => &["To", "From", "Convert", "Transform"],
+        _ => &["to", "from", "convert", "transform"],
+    };
+    prefixes.iter().any(|p| name.len() > p.len() && name.starts_with(p))
+}
+
+fn is_transform(def: &Definition, language: &str) -> bool {
+    match def.kind {
+        DefinitionKind::Function => is_transform_name(&def.name, language),
+        // Go methods are declared at the top level like functions
+        DefinitionKind::Method => language == "go" && is_transform_name(&def.name, language),
+        // impl From<A> for B
+        DefinitionKind::Impl => def.name.starts_with("From<"),
+        _ => false,
+    }
+}
+
+/// Detect transforms from the top-level definitions of a parsed file, spanning
+/// the whole function or `impl From` block.
+pub fn detect_transforms_from_syntax(
+    content: &str,
+    language: &str,
+    file: &str,
+    syntax: &SyntaxIndex,
+) -> Vec<DetectedComponent> {
+    syntax
+        .top_level()
+        .filter(|def| is_transform(def, language))
+        .map(|def| {
+            let detection = if def.kind == DefinitionKind::Impl {
+                "trait_impl"
+            } else {
+                let decl = content.get(def.byte_range.clone()).unwrap_or("");
+                let after_name = decl.find(def.name.as_str()).map_or("", |i| &decl[i + def.name.len()..]);
+                signature_detection(after_name, language)
+            };
+            DetectedComponent {
+                id: make_id("transform", &def.name, file),
+                name: def.name.clone(),
+                kind: ComponentKind::Transform,
+                language: language.to_string(),
+                source: SourceLocation {
+                    file: file.to_string(),
+                    line_start: Some(def.line_start),
+                    line_end: Some(def.line_end),
+                },
+                metadata: detection_metadata(detection),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_definitions_inside_string_literals() {
+        let content = "const example = \"const toJson = (value) => JSON.stringify(value)\";\nconst toQuery = (params) => new URLSearchParams(params);\n";
+        let masked = crate::scanner::lexer::mask(content, "javascript");
+        let names: Vec<String> = detect_transforms(&masked.text, &masked.code, "javascript", "src/convert.js")
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["toQuery"]);
+    }
+
+    fn syntax_transforms(content: &str, language: &str) -> Vec<(String, Option<u32>, Option<u32>)> {
+        let index = crate::scanner::syntax::parse(content, language, "src/convert").unwrap();
+        detect_transforms_from_syntax(content, language, "src/convert", &index)
+            .into_iter()
+            .map(|c| (c.name, c.source.line_start, c.source.line_end))
+            .collect()
+    }
+
+    #[test]
+    fn syntax_detects_top_level_transforms_with_exact_spans() {
+        let rust = "impl From<Row> for User {\n    fn from(row: Row) -> Self {\n        User { id: row.id }\n    }\n}\n\npub fn to_dto(user: &User) -> Dto {\n    Dto::new(user)\n}\n";
+        assert_eq!(
+            syntax_transforms(rust, "rust"),
+            vec![
+                ("From<Row> for User".to_string(), Some(1), Some(5)),
+                ("to_dto".to_string(), Some(7), Some(9)),
+            ]
+        );
+
+        let go = "package user\n\nfunc (u User) ToDTO() DTO {\n\treturn DTO{}\n}\n\nfunc toInternal() {}\n";
+        assert_eq!(syntax_transforms(go, "go"), vec![("ToDTO".to_string(), Some(3), Some(5))]);
+
+        let python = "class Mapper:\n    def to_dict(self):\n        pass\n\nasync def transform_event(event):\n    pass\n";
+        assert_eq!(syntax_transforms(python, "python"), vec![("transform_event".to_string(), Some(5), Some(6))]);
+    }
+
+    #[test]
+    fn detection_follows_the_signature() {
+        let detections = |content: &str, language: &str| -> Vec<(String, String)> {
+            let index = crate::scanner::syntax::parse(content, language, "src/convert").unwrap();
+            detect_transforms_from_syntax(content, language, "src/convert", &index)
+                .into_iter()
+                .map(|c| (c.name, c.metadata["detection"].clone()))
+                .collect()
+        };
+        let pair = |name: &str, detection: &str| (name.to_string(), detection.to_string());
+
+        let rust = "impl From<Row> for User {\n    fn from(row: Row) -> Self { User }\n}\n\nfn to_dto(user: &User) -> Dto {\n    Dto\n}\n\nfn to_log(user: &User) {\n}\n";
+        assert_eq!(
+            detections(rust, "rust"),
+            vec![pair("From<Row> for User", "trait_impl"), pair("to_dto", "typed_signature"), pair("to_log", "name_only")]
+        );
+        let python = "def to_dict(user: User) -> dict:\n    pass\n\ndef from_row(row):\n    pass\n";
+        assert_eq!(detections(python, "python"), vec![pair("to_dict", "typed_signature"), pair("from_row", "name_only")]);
+
+        let ts = "export function toDto(user: User): UserDto {\n  return user;\n}\nconst toQuery = (params) => params;\n";
+        let masked = crate::scanner::lexer::mask(ts, "typescript");
+        let regex_detections: Vec<(String, String)> = detect_transforms(&masked.text, &masked.code, "typescript", "src/convert.ts")
+            .into_iter()
+            .map(|c| (c.name, c.metadata["detection"].clone()))
+            .collect();
+        assert_eq!(regex_detections, vec![pair("toDto", "typed_signature"), pair("toQuery", "name_only")]);
+    }
+}
@@ -1,17 +1,24 @@
 use regex::Regex;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::sync::LazyLock;
 
 use crate::output::schema::{
     ComponentKind, DetectedComponent, SourceLocation, TransportProtocol,
 };
 
+use super::lexer::is_code;
 use super::make_id;
+use super::spans::definition_end;
+use super::relationships::{build_import_bindings, ImportBinding};
+use super::syntax::{DefinitionKind, SyntaxIndex};
 
 struct RoutePattern {
     regex: Regex,
     method_group: usize,
     path_group: usize,
+    /// Capture group holding the router/app object the route is declared on, if any
+    router_group: Option<usize>,
     protocol: TransportProtocol,
 }
 
@@ -25,6 +32,7 @@ static HTTP_PATTERNS: LazyLock<Vec<RoutePattern>> = LazyLock::new(|| {
             .unwrap(),
             method_group: 1,
             path_group: 2,
+            router_group: None,
             protocol: TransportProtocol::Http,
         },
         // NestJS decorators: @Get("/path"), @Post("/path")
@@ -35,16 +43,19 @@ static HTTP_PATTERNS: LazyLock<Vec<RoutePattern>> = LazyLock::new(|| {
             .unwrap(),
             method_group: 1,
             path_group: 2,
+            router_group: None,
             protocol: TransportProtocol::Http,
         },
-        // Python Flask/FastAPI: @app.get("/path") or @router.post("/path")
+        // Python Flask/FastAPI: @app.get("/path") or @router.post("/path"). Only
+        // used when the file didn't parse; see `detect_python_routes`.
         RoutePattern {
             regex: Regex::new(
-                r#"(?m)@(?:app|router|api)\.(get|post|put|patch|delete)\s*\(\s*['"]([^'"]+)['"]"#,
+                r#"(?m)@(\w+)\.(get|post|put|patch|delete)\s*\(\s*['"]([^'"]+)['"]"#,
             )
             .unwrap(),
-            method_group: 1,
-            path_group: 2,
+            method_group: 2,
+            path_group: 3,
+            router_group: Some(1),
             protocol: TransportProtocol::Http,
         },
         // Java Spring: @GetMapping("/path"), @PostMapping("/path")
@@ -55,6 +66,7 @@ static HTTP_PATTERNS: LazyLock<Vec<RoutePattern>> = LazyLock::new(|| {
             .unwrap(),
             method_group: 1,
             path_group: 2,
+            router_group: None,
             protocol: TransportProtocol::Http,
         },
     ]
@@ -155,10 +167,10 @@ fn extract_payload_types(content: &str, match_start: usize) -> (Option<Vec<Strin
     }
 
     // Return type annotation fallback → produces
-    if produces.is_empty() {
-        if let Some(cap) = RETURN_TYPE_RE.captures(&snippet) {
-            produces.extend(normalize_types(&cap[1]));
-        }
+    if produces.is_empty()
+        && let Some(cap) = RETURN_TYPE_RE.captures(&snippet)
+    {
+        produces.extend(normalize_types(&cap[1]));
     }
 
     consumes.sort();
@@ -183,43 +195,355 @@ static WEBSOCKET_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
     ]
 });
 
+// ---------------------------------------------------------------------------
+// FastAPI routers
+// ---------------------------------------------------------------------------
+
+/// Objects routes may be declared on even when the file doesn't construct them
+const DEFAULT_ROUTER_OBJECTS: &[&str] = &["app", "router", "api"];
+
+/// `router = APIRouter(` / `app = FastAPI(` — group 1 = variable, group 2 = constructor
+static ROUTER_DECL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*(\w+)\s*(?::\s*\w+\s*)?=\s*(?:fastapi\.)?(APIRouter|FastAPI)\s*\(").unwrap()
+});
+
+/// `parent.include_router(` — group 1 = parent router/app variable
+static INCLUDE_ROUTER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\w+)\.include_router\s*\(").unwrap());
+
+static PREFIX_KWARG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bprefix\s*=\s*['"]([^'"]*)['"]"#).unwrap());
+
+static TAGS_KWARG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\btags\s*=\s*\[([^\]]*)\]").unwrap());
+
+static DEPENDENCIES_KWARG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bdependencies\s*=\s*\[").unwrap());
+
+static QUOTED_STRING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"['"]([^'"]*)['"]"#).unwrap());
+
+static DEPENDS_CALL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"Depends\(\s*([\w.]+)").unwrap());
+
+static FIRST_POSITIONAL_ARG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*([A-Za-z_][\w.]*)\s*(?:,|$)").unwrap());
+
+/// Router-level settings passed to `APIRouter(...)` or `include_router(...)`
+#[derive(Debug, Default, Clone)]
+struct RouterArgs {
+    prefix: String,
+    tags: Vec<String>,
+    dependencies: Vec<String>,
+}
+
+/// A FastAPI router or app constructed in a file
+#[derive(Debug)]
+struct RouterDecl {
+    var: String,
+    args: RouterArgs,
+}
+
+/// The byte range between the bracket at `open` and its matching close bracket,
+/// found in `code`, the lexer's code view, so brackets inside strings and
+/// comments don't affect nesting.
+fn balanced_inner(code: &str, open: usize) -> Range<usize> {
+    let mut depth = 0usize;
+    for (i, b) in code.bytes().enumerate().skip(open) {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + 1..i;
+                }
+            }
+            _ => {}
+        }
+    }
+    open + 1..code.len()
+}
+
+/// Parse router arguments from `args` and `code`, the text and code views of
+/// the same span.
+fn parse_router_args(args: &str, code: &str) -> RouterArgs {
+    let prefix = PREFIX_KWARG
+        .captures(args)
+        .map(|cap| cap[1].to_string())
+        .unwrap_or_default();
+
+    let tags = TAGS_KWARG
+        .captures(args)
+        .map(|cap| {
+            QUOTED_STRING
+                .captures_iter(&cap[1])
+                .map(|t| t[1].to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let dependencies = DEPENDENCIES_KWARG
+        .find(args)
+        .map(|m| {
+            let list = &args[balanced_inner(code, m.end() - 1)];
+            let deps: Vec<String> = DEPENDS_CALL
+                .captures_iter(list)
+                .map(|d| d[1].to_string())
+                .collect();
+            if deps.is_empty() && !list.trim().is_empty() {
+                vec![list.trim().to_string()]
+            } else {
+                deps
+            }
+        })
+        .unwrap_or_default();
+
+    RouterArgs {
+        prefix,
+        tags,
+        dependencies,
+    }
+}
+
+fn find_router_decls(content: &str, code: &str) -> Vec<RouterDecl> {
+    ROUTER_DECL
+        .captures_iter(content)
+        .map(|cap| {
+            let inner = balanced_inner(code, cap.get(0).unwrap().end() - 1);
+            RouterDecl {
+                var: cap[1].to_string(),
+                args: parse_router_args(&content[inner.clone()], &code[inner]),
+            }
+        })
+        .collect()
+}
+
+/// Record router tags/dependencies on a transport, merging with any already present
+fn merge_router_metadata(metadata: &mut HashMap<String, String>, args: &RouterArgs) {
+    for (key, values) in [("tags", &args.tags), ("dependencies", &args.dependencies)] {
+        if values.is_empty() {
+            continue;
+        }
+        let mut merged: Vec<String> = metadata
+            .get(key)
+            .map(|v| v.split(", ").map(String::from).collect())
+            .unwrap_or_default();
+        for value in values {
+            if !merged.contains(value) {
+                merged.push(value.clone());
+            }
+        }
+        metadata.insert(key.to_string(), merged.join(", "));
+    }
+}
+
+/// The route's path with its `APIRouter` prefix, and the router's metadata, for
+/// a route declared on `router_var`. None when the object isn't a known router.
+fn route_on_router(
+    router_var: &str,
+    path: &str,
+    routers: &[RouterDecl],
+) -> Option<(String, HashMap<String, String>)> {
+    let decl = routers.iter().find(|r| r.var == router_var);
+    if decl.is_none() && !DEFAULT_ROUTER_OBJECTS.contains(&router_var) {
+        return None;
+    }
+
+    let mut metadata = HashMap::new();
+    let mut path = path.to_string();
+    if let Some(decl) = decl {
+        if !decl.args.prefix.is_empty() {
+            metadata.insert("router_prefix".to_string(), decl.args.prefix.clone());
+            path = format!("{}{path}", decl.args.prefix);
+        }
+        merge_router_metadata(&mut metadata, &decl.args);
+    }
+    metadata.insert("router".to_string(), router_var.to_string());
+    Some((path, metadata))
+}
+
+/// An HTTP route found in a file
+struct Route {
+    method: String,
+    path: String,
+    metadata: HashMap<String, String>,
+    protocol: TransportProtocol,
+    /// Where the route's declaration starts; payload types are read from here
+    offset: usize,
+    line_start: u32,
+    line_end: Option<u32>,
+}
+
+fn http_transport(route: Route, content: &str, language: &str, file: &str) -> DetectedComponent {
+    let Route { method, path, metadata, protocol, offset, line_start, line_end } = route;
+    let display_name = format!("{method} {path}");
+    let (consumes, produces) = extract_payload_types(content, offset);
+
+    DetectedComponent {
+        id: make_id("transport", &display_name, file),
+        name: display_name,
+        kind: ComponentKind::Transport,
+        language: language.to_string(),
+        source: SourceLocation {
+            file: file.to_string(),
+            line_start: Some(line_start),
+            line_end,
+        },
+        metadata,
+        transport_protocol: Some(protocol),
+        http_method: Some(method),
+        http_path: Some(path),
+        consumes,
+        produces,
+        ..Default::default()
+    }
+}
+
+/// Route methods a decorator object accepts: FastAPI/Flask `get` … `delete`, and
+/// Flask's `route`, whose methods come from its `methods=[...]` argument
+const PYTHON_ROUTE_METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "route"];
+
+/// First positional argument, when it's a string: the route path
+static PATH_ARG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*[rbuRBU]?['"]([^'"]+)['"]"#).unwrap());
+
+static METHODS_KWARG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bmethods\s*=\s*[\[(]([^\])]*)[\])]").unwrap());
+
+fn line_offset(content: &str, line: u32) -> usize {
+    content.split_inclusive('\n').take(line as usize - 1).map(str::len).sum()
+}
+
+/// Detect FastAPI and Flask routes from the decorators of parsed Python
+/// functions, so routes on nested and indented handlers are found and span the
+/// handler they decorate.
+fn detect_python_routes(
+    content: &str,
+    language: &str,
+    file: &str,
+    syntax: &SyntaxIndex,
+    routers: &[RouterDecl],
+) -> Vec<DetectedComponent> {
+    let mut components = Vec::new();
+
+    let handlers = syntax
+        .definitions
+        .iter()
+        .filter(|d| matches!(d.kind, DefinitionKind::Function | DefinitionKind::Method));
+    for def in handlers {
+        for decorator in &def.decorators {
+            let Some((router_var, verb)) = decorator.name.rsplit_once('.') else {
+                continue;
+            };
+            if !PYTHON_ROUTE_METHODS.contains(&verb) || !router_var.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                continue;
+            }
+            let arguments = decorator.arguments.as_deref().unwrap_or("");
+            let Some(path) = PATH_ARG.captures(arguments) else {
+                continue;
+            };
+            let Some((path, metadata)) = route_on_router(router_var, &path[1], routers) else {
+                continue;
+            };
+
+            let methods = if verb == "route" {
+                let listed: Vec<String> = METHODS_KWARG
+                    .captures(arguments)
+                    .map(|cap| QUOTED_STRING.captures_iter(&cap[1]).map(|m| m[1].to_uppercase()).collect())
+                    .unwrap_or_default();
+                if listed.is_empty() {
+                    vec!["GET".to_string()]
+                } else {
+                    listed
+                }
+            } else {
+                vec![verb.to_uppercase()]
+            };
+
+            for method in methods {
+                let route = Route {
+                    method,
+                    path: path.clone(),
+                    metadata: metadata.clone(),
+                    protocol: TransportProtocol::Http,
+                    offset: line_offset(content, decorator.line),
+                    line_start: decorator.line,
+                    line_end: Some(def.line_end),
+                };
+                components.push(http_transport(route, content, language, file));
+            }
+        }
+    }
+
+    components
+}
+
+/// Detect transports in `content`, the masked text view of a file. Matches must
+/// start in `code`, its code-only view, so routes quoted inside strings are
+/// skipped; the path itself is still read from the string in `content`. Python
+/// route decorators are read from `syntax` when the file parsed.
 pub fn detect_transports(
     content: &str,
+    code: &str,
     language: &str,
     file: &str,
+    syntax: Option<&SyntaxIndex>,
 ) -> Vec<DetectedComponent> {
     let mut components = Vec::new();
 
+    let routers = if language == "python" {
+        find_router_decls(content, code)
+    } else {
+        Vec::new()
+    };
+
+    let python_syntax = syntax.filter(|_| language == "python");
+    if let Some(index) = python_syntax {
+        components.extend(detect_python_routes(content, language, file, index, &routers));
+    }
+
     // HTTP routes
     for pattern in HTTP_PATTERNS.iter() {
+        if pattern.router_group.is_some() && python_syntax.is_some() {
+            continue;
+        }
         for cap in pattern.regex.captures_iter(content) {
             let method = cap[pattern.method_group].to_uppercase();
-            let path = cap[pattern.path_group].to_string();
-            let display_name = format!("{method} {path}");
             let match_start = cap.get(0).unwrap().start();
-            let line_num = content[..match_start].lines().count() as u32 + 1;
+            if !is_code(code, match_start) {
+                continue;
+            }
 
-            let (consumes, produces) = extract_payload_types(content, match_start);
+            // A bare `router.get(` inside `@router.get(` / `@peers_router.get(` is a
+            // Python decorator, which its own pattern handles with router resolution.
+            if pattern.router_group.is_none()
+                && content[..match_start]
+                    .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+                    .ends_with('@')
+            {
+                continue;
+            }
 
-            components.push(DetectedComponent {
-                id: make_id("transport", &display_name, file),
-                name: display_name,
-                kind: ComponentKind::Transport,
-                language: language.to_string(),
-                source: SourceLocation {
-                    file: file.to_string(),
-                    line_start: Some(line_num),
-                    line_end: None,
+            let path = &cap[pattern.path_group];
+            let (path, metadata) = match pattern.router_group {
+                Some(group) => match route_on_router(&cap[group], path, &routers) {
+                    Some(routed) => routed,
+                    None => continue,
                 },
-                metadata: HashMap::new(),
-                transport_protocol: Some(pattern.protocol.clone()),
-                http_method: Some(method),
-                http_path: Some(path),
-                model_fields: None,
-                prompt_subtype: None,
-                consumes,
-                produces,
-            });
+                None => (path.to_string(), HashMap::new()),
+            };
+
+            let line_num = content[..match_start].lines().count() as u32 + 1;
+            let route = Route {
+                method,
+                path,
+                metadata,
+                protocol: pattern.protocol.clone(),
+                offset: match_start,
+                line_start: line_num,
+                line_end: definition_end(content, language, line_num),
+            };
+            components.push(http_transport(route, content, language, file));
         }
     }
 
@@ -227,6 +551,9 @@ pub fn detect_transports(
     for cap in GRPC_PATTERN.captures_iter(content) {
         let name = cap[1].to_string();
         let match_start = cap.get(0).unwrap().start();
+        if !is_code(code, match_start) {
+            continue;
+        }
         let line_num = content[..match_start].lines().count() as u32 + 1;
 
         components.push(DetectedComponent {
@@ -237,16 +564,10 @@ pub fn detect_transports(
             source: SourceLocation {
                 file: file.to_string(),
                 line_start: Some(line_num),
-                line_end: None,
+                line_end: definition_end(content, language, line_num),
             },
-            metadata: HashMap::new(),
             transport_protocol: Some(TransportProtocol::Grpc),
-            http_method: None,
-            http_path: None,
-            model_fields: None,
-            prompt_subtype: None,
-            consumes: None,
-            produces: None,
+            ..Default::default()
         });
     }
 
@@ -258,6 +579,9 @@ pub fn detect_transports(
                 .map(|m| m.as_str().to_string())
                 .unwrap_or_else(|| "WebSocket".to_string());
             let match_start = cap.get(0).unwrap().start();
+            if !is_code(code, match_start) {
+                continue;
+            }
             let line_num = content[..match_start].lines().count() as u32 + 1;
 
             components.push(DetectedComponent {
@@ -268,19 +592,536 @@ pub fn detect_transports(
                 source: SourceLocation {
                     file: file.to_string(),
                     line_start: Some(line_num),
-                    line_end: None,
+                    line_end: definition_end(content, language, line_num),
                 },
-                metadata: HashMap::new(),
                 transport_protocol: Some(TransportProtocol::Websocket),
-                http_method: None,
-                http_path: None,
-                model_fields: None,
-                prompt_subtype: None,
-                consumes: None,
-                produces: None,
+                ..Default::default()
             });
         }
     }
 
     components
 }
+
+/// A `parent.include_router(child, ...)` call: the mounting router and its arguments
+struct RouterMount {
+    parent: (String, String),
+    args: RouterArgs,
+}
+
+/// Join an import's module path and imported name into a dotted module path
+fn join_module(module: &str, name: &str) -> String {
+    if module.is_empty() {
+        name.to_string()
+    } else if module.ends_with('.') {
+        format!("{module}{name}")
+    } else {
+        format!("{module}.{name}")
+    }
+}
+
+/// Resolve a Python module path, as written in an import, to a scanned file.
+/// Relative paths are resolved against the importing file's package; absolute
+/// paths match any file whose path ends with the module's path.
+fn resolve_python_module(module: &str, importer: &str, files: &[&str]) -> Option<String> {
+    let dots = module.len() - module.trim_start_matches('.').len();
+    let rel = module[dots..].replace('.', "/");
+
+    let candidates: Vec<String> = if dots > 0 {
+        let mut base = std::path::Path::new(importer).parent()?.to_path_buf();
+        for _ in 1..dots {
+            base = base.parent()?.to_path_buf();
+        }
+        let base = base.to_string_lossy().to_string();
+        let join = |p: &str| if base.is_empty() { p.to_string() } else { format!("{base}/{p}") };
+        if rel.is_empty() {
+            vec![join("__init__.py")]
+        } else {
+            vec![join(&format!("{rel}.py")), join(&format!("{rel}/__init__.py"))]
+        }
+    } else {
+        vec![format!("{rel}.py"), format!("{rel}/__init__.py")]
+    };
+
+    for candidate in &candidates {
+        if let Some(file) = files
+            .iter()
+            .find(|f| **f == candidate || (dots == 0 && f.ends_with(&format!("/{candidate}"))))
+        {
+            return Some(file.to_string());
+        }
+    }
+    None
+}
+
+/// Resolve the router argument of `include_router` (e.g. `router`, `peers.router`)
+/// to the file and variable that define it.
+fn resolve_router_ref(
+    arg: &str,
+    file: &str,
+    bindings: &HashMap<String, ImportBinding>,
+    files: &[&str],
+) -> Option<(String, String)> {
+    let Some((object, var)) = arg.rsplit_once('.') else {
+        // Bare name: either imported from another module or defined locally
+        return match bindings.get(arg) {
+            Some(binding) if !binding.module.is_empty() => {
+                let target = resolve_python_module(&binding.module, file, files)?;
+                Some((target, binding.name.clone()))
+            }
+            Some(_) => None,
+            None => Some((file.to_string(), arg.to_string())),
+        };
+    };
+
+    // Dotted reference: the first segment is an imported module, any others are submodules
+    let (head, rest) = match object.split_once('.') {
+        Some((head, rest)) => (head, Some(rest)),
+        None => (object, None),
+    };
+    let binding = bindings.get(head)?;
+    let mut module = join_module(&binding.module, &binding.name);
+    if let Some(rest) = rest {
+        module = join_module(&module, rest);
+    }
+
+    resolve_python_module(&module, file, files)
+        .or_else(|| {
+            // `from pkg import router_module` where the module lives in pkg/__init__.py
+            (rest.is_none() && !binding.module.is_empty())
+                .then(|| resolve_python_module(&binding.module, file, files))
+                .flatten()
+        })
+        .map(|target| (target, var.to_string()))
+}
+
+/// Accumulate the prefix, tags and dependencies applied to a router by each
+/// chain of `include_router` calls that mount it, outermost first. A router
+/// mounted more than once gets one entry per mount, in path order. `path` holds
+/// the routers already on the chain, so a mount that closes an include cycle
+/// is dropped.
+fn mounted_args(
+    key: &(String, String),
+    mounts: &HashMap<(String, String), Vec<RouterMount>>,
+    own_args: &HashMap<(String, String), RouterArgs>,
+    path: &mut Vec<(String, String)>,
+) -> Vec<RouterArgs> {
+    // Unmounted routers contribute nothing
+    let Some(router_mounts) = mounts.get(key) else {
+        return vec![RouterArgs::default()];
+    };
+
+    path.push(key.clone());
+    let mut chains = Vec::new();
+    for mount in router_mounts {
+        if path.contains(&mount.parent) {
+            continue;
+        }
+        let parent_args = own_args.get(&mount.parent).cloned().unwrap_or_default();
+        for mut args in mounted_args(&mount.parent, mounts, own_args, path) {
+            for layer in [&parent_args, &mount.args] {
+                args.prefix.push_str(&layer.prefix);
+                args.tags.extend(layer.tags.iter().cloned());
+                args.dependencies.extend(layer.dependencies.iter().cloned());
+            }
+            chains.push(args);
+        }
+    }
+    path.pop();
+    chains
+}
+
+/// Set a FastAPI transport's mount prefix, tags and dependencies. A new prefix
+/// changes its path, and with it the name and ID.
+fn apply_mount(comp: &mut DetectedComponent, args: &RouterArgs) {
+    merge_router_metadata(&mut comp.metadata, args);
+    if args.prefix.is_empty() {
+        return;
+    }
+
+    let path = format!("{}{}", args.prefix, comp.http_path.as_deref().unwrap_or(""));
+    let method = comp.http_method.clone().unwrap_or_default();
+    comp.name = format!("{method} {path}");
+    comp.id = make_id("transport", &comp.name, &comp.source.file);
+    comp.http_path = Some(path);
+    comp.metadata.insert("mount_prefix".to_string(), args.prefix.clone());
+}
+
+/// Apply `include_router` prefixes across modules so each FastAPI transport's
+/// `http_path` is the externally visible path. Transports whose path changes
+/// get a new name and ID to match; a router mounted under several prefixes
+/// gets one transport per mounted path.
+pub fn resolve_router_mounts(
+    components: &mut Vec<DetectedComponent>,
+    file_contents: &HashMap<String, String>,
+    code_contents: &HashMap<String, String>,
+) {
+    let mut files: Vec<&str> = file_contents
+        .keys()
+        .filter(|f| f.ends_with(".py"))
+        .map(|f| f.as_str())
+        .collect();
+    // Mounts of the same router are kept in path order
+    files.sort_unstable();
+
+    let mut own_args: HashMap<(String, String), RouterArgs> = HashMap::new();
+    let mut mounts: HashMap<(String, String), Vec<RouterMount>> = HashMap::new();
+
+    for &file in &files {
+        let content = &file_contents[file];
+        if !content.contains("APIRouter") && !content.contains("include_router") {
+            continue;
+        }
+        let code = code_contents.get(file).map_or(content.as_str(), String::as_str);
+
+        for decl in find_router_decls(content, code) {
+            own_args.insert((file.to_string(), decl.var), decl.args);
+        }
+
+        let bindings = build_import_bindings(content);
+        for cap in INCLUDE_ROUTER.captures_iter(content) {
+            let inner = balanced_inner(code, cap.get(0).unwrap().end() - 1);
+            let args = &content[inner.clone()];
+            let Some(arg) = FIRST_POSITIONAL_ARG.captures(args) else {
+                continue;
+            };
+            let Some(child) = resolve_router_ref(&arg[1], file, &bindings, &files) else {
+                continue;
+            };
+            mounts.entry(child).or_default().push(RouterMount {
+                parent: (file.to_string(), cap[1].to_string()),
+                args: parse_router_args(args, &code[inner]),
+            });
+        }
+    }
+
+    if mounts.is_empty() {
+        return;
+    }
+
+    let mut extra_mounts = Vec::new();
+    for comp in components.iter_mut() {
+        if comp.kind != ComponentKind::Transport {
+            continue;
+        }
+        let Some(router) = comp.metadata.get("router") else {
+            continue;
+        };
+        let key = (comp.source.file.clone(), router.clone());
+        if !mounts.contains_key(&key) {
+            continue;
+        }
+
+        let mut chains = mounted_args(&key, &mounts, &own_args, &mut Vec::new()).into_iter();
+        let Some(first) = chains.next() else {
+            continue;
+        };
+        let unmounted = comp.clone();
+        apply_mount(comp, &first);
+
+        // Every further mount is the same handler served at another path
+        let mut prefixes = vec![first.prefix];
+        for args in chains {
+            if prefixes.contains(&args.prefix) {
+                continue;
+            }
+            let mut mounted = unmounted.clone();
+            apply_mount(&mut mounted, &args);
+            prefixes.push(args.prefix);
+            extra_mounts.push(mounted);
+        }
+    }
+    components.extend(extra_mounts);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(content: &str, language: &str, file: &str) -> Vec<DetectedComponent> {
+        let masked = crate::scanner::lexer::mask(content, language);
+        let syntax = crate::scanner::syntax::parse(content, language, file);
+        detect_transports(&masked.text, &masked.code, language, file, syntax.as_ref())
+    }
+
+    #[test]
+    fn applies_api_router_prefix_and_metadata() {
+        let content = r#"
+router = APIRouter(
+    prefix="/peers",
+    tags=["peers", "admin"],
+    dependencies=[Depends(get_db), Depends(auth.require_user)],
+)
+
+@router.get("/{peer_id}")
+async def get_peer(peer_id: str):
+    pass
+"#;
+        let comps = detect(content, "python", "src/routers/peers.py");
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].name, "GET /peers/{peer_id}");
+        assert_eq!(comps[0].http_path.as_deref(), Some("/peers/{peer_id}"));
+        assert_eq!(comps[0].metadata.get("router").unwrap(), "router");
+        assert_eq!(comps[0].metadata.get("router_prefix").unwrap(), "/peers");
+        assert_eq!(comps[0].metadata.get("tags").unwrap(), "peers, admin");
+        assert_eq!(
+            comps[0].metadata.get("dependencies").unwrap(),
+            "get_db, auth.require_user"
+        );
+    }
+
+    #[test]
+    fn detects_routes_on_custom_router_names() {
+        let content = r#"
+peers_router = APIRouter(prefix="/peers")
+
+@peers_router.post("/")
+async def create_peer():
+    pass
+
+@cache.get("/not-a-route")
+def cached():
+    pass
+"#;
+        let comps = detect(content, "python", "src/peers.py");
+        let names: Vec<&str> = comps.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["POST /peers/"]);
+    }
+
+    #[test]
+    fn python_decorator_is_not_double_counted_as_express_route() {
+        let content = "@router.get(\"/health\")\ndef health():\n    pass\n";
+        let comps = detect(content, "python", "src/health.py");
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].metadata.get("router").unwrap(), "router");
+    }
+
+    #[test]
+    fn flask_route_decorator_emits_one_transport_per_method() {
+        let content = "app = Flask(__name__)\n\n@app.route(\"/items\", methods=[\"GET\", \"POST\"])\ndef items():\n    pass\n\n@app.route(\"/health\")\ndef health():\n    pass\n";
+        let comps = detect(content, "python", "app.py");
+        let names: Vec<&str> = comps.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["GET /items", "POST /items", "GET /health"]);
+    }
+
+    #[test]
+    fn python_routes_come_from_decorators_of_nested_handlers() {
+        let content = r#"
+router = APIRouter(prefix="/admin")
+
+if settings.ADMIN_ENABLED:
+    @router.delete("/cache")
+    @requires_role("admin")
+    async def clear_cache():
+        cache.clear()
+        return None
+
+def build():
+    label = "@router.get('/not-a-route')"
+"#;
+        let comps = detect(content, "python", "app/admin.py");
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].name, "DELETE /admin/cache");
+        assert_eq!((comps[0].source.line_start, comps[0].source.line_end), (Some(5), Some(9)));
+    }
+
+    #[test]
+    fn commented_out_routes_are_ignored_after_masking() {
+        let content = "// router.get(\"/old\", legacy);\n/* app.post('/gone', h) */\nrouter.get(\"/users\", list);\n";
+        let comps = detect(content, "javascript", "src/routes.js");
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].http_path.as_deref(), Some("/users"));
+        assert_eq!(comps[0].source.line_start, Some(3));
+    }
+
+    #[test]
+    fn routes_quoted_inside_strings_are_ignored() {
+        let content = "const doc = \"app.post('/gone', h)\";\nconst msg = 'router.get(\"/old\")';\nrouter.get(\"/users\", list);\n";
+        let comps = detect(content, "javascript", "src/routes.js");
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].http_path.as_deref(), Some("/users"));
+    }
+
+    #[test]
+    fn resolves_python_modules_relative_and_absolute() {
+        let files = vec!["app/main.py", "app/routers/peers.py", "app/routers/__init__.py"];
+        assert_eq!(
+            resolve_python_module(".routers.peers", "app/main.py", &files).as_deref(),
+            Some("app/routers/peers.py")
+        );
+        assert_eq!(
+            resolve_python_module("..main", "app/routers/peers.py", &files).as_deref(),
+            Some("app/main.py")
+        );
+        assert_eq!(
+            resolve_python_module("app.routers", "app/main.py", &files).as_deref(),
+            Some("app/routers/__init__.py")
+        );
+        assert_eq!(resolve_python_module("missing", "app/main.py", &files), None);
+    }
+
+    fn scan_files(files: &[(&str, &str)]) -> Vec<DetectedComponent> {
+        let file_contents: HashMap<String, String> = files
+            .iter()
+            .map(|(f, c)| (f.to_string(), c.to_string()))
+            .collect();
+        let code_contents: HashMap<String, String> = files
+            .iter()
+            .map(|(f, c)| (f.to_string(), crate::scanner::lexer::mask(c, "python").code))
+            .collect();
+        let mut comps: Vec<DetectedComponent> = files
+            .iter()
+            .flat_map(|(f, c)| detect(c, "python", f))
+            .collect();
+        resolve_router_mounts(&mut comps, &file_contents, &code_contents);
+        comps
+    }
+
+    #[test]
+    fn include_router_prefix_resolved_across_modules() {
+        let comps = scan_files(&[
+            (
+                "app/main.py",
+                "from .routers import peers\n\napp = FastAPI()\napp.include_router(peers.router, prefix=\"/v1\", tags=[\"public\"])\n",
+            ),
+            (
+                "app/routers/peers.py",
+                "router = APIRouter(prefix=\"/peers\", tags=[\"peers\"])\n\n@router.get(\"/{peer_id}\")\ndef get_peer(peer_id):\n    pass\n",
+            ),
+        ]);
+        assert_eq!(comps.len(), 1);
+        let tp = &comps[0];
+        assert_eq!(tp.http_path.as_deref(), Some("/v1/peers/{peer_id}"));
+        assert_eq!(tp.name, "GET /v1/peers/{peer_id}");
+        assert_eq!(tp.id, make_id("transport", "GET /v1/peers/{peer_id}", "app/routers/peers.py"));
+        assert_eq!(tp.metadata.get("mount_prefix").unwrap(), "/v1");
+        assert_eq!(tp.metadata.get("tags").unwrap(), "peers, public");
+    }
+
+    #[test]
+    fn nested_include_router_chains_prefixes() {
+        let comps = scan_files(&[
+            (
+                "app/main.py",
+                "from app.api import api_router\n\napp = FastAPI()\napp.include_router(api_router, prefix=\"/api\")\n",
+            ),
+            (
+                "app/api.py",
+                "from app.routes.users import router as users_router\n\napi_router = APIRouter(prefix=\"/v2\")\napi_router.include_router(users_router)\n",
+            ),
+            (
+                "app/routes/users.py",
+                "router = APIRouter(prefix=\"/users\")\n\n@router.post(\"/\")\ndef create_user():\n    pass\n",
+            ),
+        ]);
+        assert_eq!(comps.len(), 1);
+        assert_eq!(comps[0].http_path.as_deref(), Some("/api/v2/users/"));
+        assert_eq!(comps[0].metadata.get("mount_prefix").unwrap(), "/api/v2");
+    }
+
+    #[test]
+    fn include_router_resolves_each_name_of_an_import_list() {
+        let comps = scan_files(&[
+            (
+                "app/main.py",
+                "from .routers import users, orders\n\napp = FastAPI()\napp.include_router(users.router)\napp.include_router(orders.router, prefix=\"/v1\")\n",
+            ),
+            (
+                "app/routers/users.py",
+                "router = APIRouter(prefix=\"/users\")\n\n@router.get(\"/\")\ndef list_users():\n    pass\n",
+            ),
+            (
+                "app/routers/orders.py",
+                "router = APIRouter(prefix=\"/orders\")\n\n@router.get(\"/\")\ndef list_orders():\n    pass\n",
+            ),
+        ]);
+        let mut paths: Vec<&str> = comps.iter().filter_map(|c| c.http_path.as_deref()).collect();
+        paths.sort_unstable();
+        assert_eq!(paths, vec!["/users/", "/v1/orders/"]);
+    }
+
+    #[test]
+    fn router_mounted_twice_gets_a_transport_per_mount_in_path_order() {
+        for _ in 0..8 {
+            let comps = scan_files(&[
+                ("app/b.py", "from app.peers import router\n\napp = FastAPI()\napp.include_router(router, prefix=\"/b\")\n"),
+                ("app/a.py", "from app.peers import router\n\napp = FastAPI()\napp.include_router(router, prefix=\"/a\")\n"),
+                ("app/peers.py", "router = APIRouter()\n\n@router.get(\"/peers\")\ndef list_peers():\n    pass\n"),
+            ]);
+            let paths: Vec<&str> = comps.iter().filter_map(|c| c.http_path.as_deref()).collect();
+            assert_eq!(paths, vec!["/a/peers", "/b/peers"]);
+        }
+    }
+
+    #[test]
+    fn router_included_under_two_prefixes_keeps_both_paths() {
+        let comps = scan_files(&[
+            (
+                "app/main.py",
+                "from .routers import peers\n\napp = FastAPI()\napp.include_router(peers.router, prefix=\"/v1\")\napp.include_router(peers.router, prefix=\"/v2\", tags=[\"beta\"])\n",
+            ),
+            (
+                "app/routers/peers.py",
+                "router = APIRouter(prefix=\"/peers\")\n\n@router.get(\"/{peer_id}\")\ndef get_peer(peer_id):\n    pass\n",
+            ),
+        ]);
+        let mounted: Vec<(&str, Option<&str>, Option<&str>)> = comps
+            .iter()
+            .map(|c| {
+                let meta = |key: &str| c.metadata.get(key).map(String::as_str);
+                (c.name.as_str(), meta("mount_prefix"), meta("tags"))
+            })
+            .collect();
+        assert_eq!(
+            mounted,
+            vec![
+                ("GET /v1/peers/{peer_id}", Some("/v1"), None),
+                ("GET /v2/peers/{peer_id}", Some("/v2"), Some("beta")),
+            ]
+        );
+        assert_ne!(comps[0].id, comps[1].id);
+        assert_eq!(comps[1].source.line_start, comps[0].source.line_start);
+    }
+
+    #[test]
+    fn unmounted_router_keeps_its_own_prefix() {
+        let comps = scan_files(&[(
+            "app/peers.py",
+            "router = APIRouter(prefix=\"/peers\")\n\n@router.get(\"/\")\ndef list_peers():\n    pass\n",
+        )]);
+        assert_eq!(comps[0].http_path.as_deref(), Some("/peers/"));
+        assert!(!comps[0].metadata.contains_key("mount_prefix"));
+    }
+
+    #[test]
+    fn include_cycles_add_no_prefix() {
+        let comps = scan_files(&[
+            (
+                "app/main.py",
+                "from app import peers
+
+app = FastAPI()
+app.include_router(peers.router, prefix=\"/v1\")
+",
+            ),
+            (
+                "app/peers.py",
+                "router = APIRouter(prefix=\"/peers\")
+admin = APIRouter(prefix=\"/admin\")
+
+\
+@router.get(\"/\")
+def list_peers():
+    pass
+
+\
+router.include_router(router, prefix=\"/again\")
+router.include_router(admin)
+admin.include_router(router)
+",
+            ),
+        ]);
+        let paths: Vec<&str> = comps.iter().filter_map(|c| c.http_path.as_deref()).collect();
+        assert_eq!(paths, vec!["/v1/peers/"]);
+    }
+}
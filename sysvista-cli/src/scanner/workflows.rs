@@ -6,12 +6,13 @@ use crate::output::schema::{
 };
 
 /// Infer workflows from components and edges.
-/// For each transport component, build a workflow by following edges:
+/// For each inbound transport component, build a workflow by following edges:
 /// 1. Transport is the entry point (Entry)
 /// 2. Follow `calls` edges → Call steps
-/// 3. From call targets, follow `persists`/`transforms` edges → Persist steps
+/// 3. From call targets, follow `persists`/`transforms`/`reads`/`writes` edges → Persist steps
 /// 4. Follow `dispatches` edges → Dispatch steps
 /// 5. Match transport's `produces` list to model components → Response steps
+///
 /// Skip workflows with only 1 step.
 pub fn infer_workflows(
     components: &[DetectedComponent],
@@ -20,11 +21,11 @@ pub fn infer_workflows(
     // Build adjacency by edge label: from_id → [(to_id, label)]
     let mut outgoing: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
     for edge in edges {
-        if let Some(label) = &edge.label {
+        for label in edge.labels() {
             outgoing
                 .entry(edge.from_id.as_str())
                 .or_default()
-                .push((edge.to_id.as_str(), label.as_str()));
+                .push((edge.to_id.as_str(), label));
         }
     }
 
@@ -38,7 +39,10 @@ pub fn infer_workflows(
     let mut workflows = Vec::new();
 
     for comp in components {
-        if comp.kind != ComponentKind::Transport {
+        // Outbound endpoints are called by the system, never an entry into it
+        if comp.kind != ComponentKind::Transport
+            || comp.metadata.get("direction").is_some_and(|d| d == "outbound")
+        {
             continue;
         }
 
@@ -92,11 +96,11 @@ pub fn infer_workflows(
             }
         }
 
-        // Collect persists/transforms from all visited components → Persist steps
+        // Collect persists/transforms/reads/writes from all visited components → Persist steps
         for source_id in &visited {
             if let Some(source_edges) = outgoing.get(*source_id) {
                 for (to_id, label) in source_edges {
-                    if (*label == "persists" || *label == "transforms")
+                    if matches!(*label, "persists" | "transforms" | "reads" | "writes")
                         && seen.insert(to_id.to_string())
                     {
                         steps.push(WorkflowStep {
@@ -127,15 +131,15 @@ pub fn infer_workflows(
         // Step 4: Match produces to model components → Response steps
         if let Some(ref produces) = comp.produces {
             for type_name in produces {
-                if let Some(&model_id) = model_name_to_id.get(type_name.as_str()) {
-                    if seen.insert(model_id.to_string()) {
-                        steps.push(WorkflowStep {
-                            component_id: model_id.to_string(),
-                            step_type: StepType::Response,
-                            order,
-                        });
-                        order += 1;
-                    }
+                if let Some(&model_id) = model_name_to_id.get(type_name.as_str())
+                    && seen.insert(model_id.to_string())
+                {
+                    steps.push(WorkflowStep {
+                        component_id: model_id.to_string(),
+                        step_type: StepType::Response,
+                        order,
+                    });
+                    order += 1;
                 }
             }
         }
@@ -169,7 +173,7 @@ pub fn infer_workflows(
     // --- Service-entry workflows (CLI tools, background pipelines) ---
     let called_ids: HashSet<&str> = edges
         .iter()
-        .filter(|e| e.label.as_deref() == Some("calls"))
+        .filter(|e| e.has_label("calls"))
         .map(|e| e.to_id.as_str())
         .collect();
 
@@ -239,11 +243,11 @@ pub fn infer_workflows(
             }
         }
 
-        // Collect persists/transforms from all visited components → Persist steps
+        // Collect persists/transforms/reads/writes from all visited components → Persist steps
         for source_id in &visited_services {
             if let Some(source_edges) = outgoing.get(*source_id) {
                 for (to_id, label) in source_edges {
-                    if (*label == "persists" || *label == "transforms")
+                    if matches!(*label, "persists" | "transforms" | "reads" | "writes")
                         && seen.insert(to_id.to_string())
                     {
                         steps.push(WorkflowStep {
@@ -278,7 +282,7 @@ pub fn infer_workflows(
     }
 
     // Sort by step count descending (most interesting workflows first)
-    workflows.sort_by(|a, b| b.steps.len().cmp(&a.steps.len()));
+    workflows.sort_by_key(|w| std::cmp::Reverse(w.steps.len()));
 
     workflows
 }
@@ -295,14 +299,10 @@ mod tests {
             kind,
             language: "python".to_string(),
             source: SourceLocation { file: "test.py".to_string(), line_start: Some(1), line_end: None },
-            metadata: std::collections::HashMap::new(),
-            transport_protocol: None,
             http_method: Some("POST".to_string()),
             http_path: Some("/messages".to_string()),
-            model_fields: None,
-            prompt_subtype: None,
-            consumes: None,
             produces,
+            ..Default::default()
         }
     }
 
@@ -312,6 +312,9 @@ mod tests {
             to_id: to.to_string(),
             label: Some(label.to_string()),
             payload_type: None,
+            evidence: None,
+            confidence: None,
+            relations: Vec::new(),
         }
     }
 
@@ -0,0 +1,358 @@
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::analysis;
+use crate::output::schema::{
+    Analysis, ComponentKind, DetectedComponent, DetectedEdge, EdgeEvidence, Package, Repository, ScanStats, SysVistaOutput,
+    TransportProtocol,
+};
+
+use super::relationships::{grpc_service, is_outbound, merge_relations, route_for, topic_consumers};
+
+// ---------------------------------------------------------------------------
+// Workspaces — several repositories scanned into one output
+// ---------------------------------------------------------------------------
+
+/// A repository to scan and the name its components are namespaced under.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepoSpec {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// A workspace manifest:
+///
+/// ```toml
+/// name = "platform"
+///
+/// [[repos]]
+/// name = "users"
+/// path = "../users-service"
+/// ```
+///
+/// Relative paths are resolved against the manifest's directory. Repository
+/// names must be unique.
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    pub name: Option<String>,
+    pub repos: Vec<RepoSpec>,
+}
+
+pub fn load_manifest(path: &Path) -> Result<Manifest, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("cannot read '{}': {e}", path.display()))?;
+    let mut manifest: Manifest = toml::from_str(&text).map_err(|e| format!("invalid manifest '{}': {e}", path.display()))?;
+    let base = path.parent().unwrap_or(Path::new("."));
+    let mut names: HashSet<&str> = HashSet::new();
+    if let Some(repo) = manifest.repos.iter().find(|r| !names.insert(r.name.as_str())) {
+        return Err(format!("invalid manifest '{}': repository '{}' is listed twice", path.display(), repo.name));
+    }
+    for repo in &mut manifest.repos {
+        if repo.path.is_relative() {
+            repo.path = base.join(&repo.path);
+        }
+    }
+    Ok(manifest)
+}
+
+/// Specs for bare repository roots, named after their directories. Names
+/// repeated among the roots or already used by `existing` repositories get a
+/// numeric suffix.
+pub fn repos_from_roots(roots: &[PathBuf], existing: &[RepoSpec]) -> Vec<RepoSpec> {
+    let mut taken: HashSet<String> = existing.iter().map(|r| r.name.clone()).collect();
+    roots
+        .iter()
+        .map(|root| {
+            let base = root
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("repo")
+                .to_string();
+            let mut name = base.clone();
+            let mut n = 2;
+            while !taken.insert(name.clone()) {
+                name = format!("{base}-{n}");
+                n += 1;
+            }
+            RepoSpec {
+                name,
+                path: root.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Scan each repository and merge the results. Component, edge, workflow and
+/// package IDs are prefixed with `repo:`, package and evidence paths with
+/// `repo/`, and outbound endpoints one repository calls are replaced by the
+/// transports another repository serves.
+pub fn scan_workspace(project_name: &str, root_dir: &Path, repos: &[RepoSpec]) -> SysVistaOutput {
+    let start = Instant::now();
+
+    let mut output = SysVistaOutput {
+        version: "1".to_string(),
+        scanned_at: chrono::Utc::now().to_rfc3339(),
+        root_dir: root_dir.to_string_lossy().to_string(),
+        project_name: project_name.to_string(),
+        detected_languages: Vec::new(),
+        components: Vec::new(),
+        edges: Vec::new(),
+        workflows: Vec::new(),
+        scan_stats: ScanStats {
+            files_scanned: 0,
+            files_skipped: 0,
+            scan_duration_ms: 0,
+        },
+        packages: Vec::new(),
+        repositories: Vec::new(),
+        analysis: Analysis::default(),
+    };
+
+    for repo in repos {
+        let result = super::scan(&repo.path);
+        let ns = |id: &str| format!("{}:{id}", repo.name);
+        let in_repo = |path: &str| {
+            if path.is_empty() {
+                repo.name.clone()
+            } else {
+                format!("{}/{path}", repo.name)
+            }
+        };
+        let qualify = |evidence: &mut Option<EdgeEvidence>| {
+            if let Some(evidence) = evidence {
+                evidence.source.file = in_repo(&evidence.source.file);
+            }
+        };
+
+        output.components.extend(result.components.into_iter().map(|mut c| {
+            c.id = ns(&c.id);
+            c.repo = Some(repo.name.clone());
+            c.package = c.package.map(|p| ns(&p));
+            c
+        }));
+        output.edges.extend(result.edges.into_iter().map(|mut e| {
+            e.from_id = ns(&e.from_id);
+            e.to_id = ns(&e.to_id);
+            qualify(&mut e.evidence);
+            for relation in &mut e.relations {
+                qualify(&mut relation.evidence);
+            }
+            e
+        }));
+        output.workflows.extend(result.workflows.into_iter().map(|mut w| {
+            w.id = ns(&w.id);
+            w.entry_point_id = ns(&w.entry_point_id);
+            for step in &mut w.steps {
+                step.component_id = ns(&step.component_id);
+            }
+            w
+        }));
+
+        output.packages.extend(result.packages.into_iter().map(|p| Package {
+            name: ns(&p.name),
+            path: in_repo(&p.path),
+            manager: p.manager,
+        }));
+        output.scan_stats.files_scanned += result.scan_stats.files_scanned;
+        output.scan_stats.files_skipped += result.scan_stats.files_skipped;
+        output.detected_languages.extend(result.detected_languages.iter().cloned());
+        output.repositories.push(Repository {
+            name: repo.name.clone(),
+            root_dir: result.root_dir,
+            detected_languages: result.detected_languages,
+            scan_stats: result.scan_stats,
+        });
+    }
+
+    output.detected_languages.sort();
+    output.detected_languages.dedup();
+    link_repositories(&mut output.components, &mut output.edges);
+    analysis::analyze(&mut output);
+    output.scan_stats.scan_duration_ms = start.elapsed().as_millis() as u64;
+    output
+}
+
+/// Replace each outbound endpoint with the transports of other repositories
+/// that serve it: HTTP routes by method and path (repositories named in the
+/// call's host first), queue consumers by topic, gRPC services by name. Edges
+/// into a replaced endpoint are redirected and the endpoint node dropped.
+pub fn link_repositories(components: &mut Vec<DetectedComponent>, edges: &mut Vec<DetectedEdge>) {
+    let mut redirects: HashMap<String, Vec<String>> = HashMap::new();
+    for endpoint in components.iter().filter(|c| c.kind == ComponentKind::Transport && is_outbound(c)) {
+        let others: Vec<&DetectedComponent> = components
+            .iter()
+            .filter(|c| c.kind == ComponentKind::Transport && !is_outbound(c) && c.repo != endpoint.repo)
+            .collect();
+        let targets: Vec<&DetectedComponent> = match endpoint.transport_protocol {
+            Some(TransportProtocol::Http) => {
+                let method = endpoint.http_method.as_deref().unwrap_or("GET");
+                let path = endpoint.http_path.as_deref().unwrap_or("/");
+                let host = endpoint.metadata.get("host").map(|h| h.to_lowercase());
+                let named: Vec<&DetectedComponent> = others
+                    .iter()
+                    .copied()
+                    .filter(|c| {
+                        let repo = c.repo.as_deref().unwrap_or("").to_lowercase();
+                        host.as_deref().is_some_and(|h| !repo.is_empty() && h.contains(&repo))
+                    })
+                    .collect();
+                route_for(&named, method, path)
+                    .or_else(|| route_for(&others, method, path))
+                    .into_iter()
+                    .collect()
+            }
+            Some(TransportProtocol::Mq) => match endpoint.metadata.get("topic") {
+                Some(topic) => topic_consumers(&others, topic),
+                None => Vec::new(),
+            },
+            Some(TransportProtocol::Grpc) => grpc_service(&others, &endpoint.name).into_iter().collect(),
+            _ => Vec::new(),
+        };
+        if !targets.is_empty() {
+            redirects.insert(endpoint.id.clone(), targets.iter().map(|t| t.id.clone()).collect());
+        }
+    }
+    if redirects.is_empty() {
+        return;
+    }
+
+    components.retain(|c| !redirects.contains_key(&c.id));
+    let mut linked = Vec::with_capacity(edges.len());
+    for edge in edges.drain(..) {
+        match redirects.get(&edge.to_id) {
+            Some(targets) => linked.extend(targets.iter().map(|to| DetectedEdge {
+                to_id: to.clone(),
+                ..edge.clone()
+            })),
+            None => linked.push(edge),
+        }
+    }
+    *edges = merge_relations(linked);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, file: &str, content: &str) {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn links_calls_across_repositories() {
+        let dir = std::env::temp_dir().join(format!("sysvista-workspace-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let users = dir.join("users");
+        let web = dir.join("web");
+        write(&users, "app/routes.py", "@app.get(\"/api/users/{user_id}\")\ndef get_user(user_id: int):\n    return {}\n");
+        write(
+            &web,
+            "src/services/users.ts",
+            "export async function loadUser(id: string) {\n  return fetch(`http://users:8000/api/users/${id}`);\n}\n\nexport async function ping() {\n  return fetch(\"https://status.example.com/ping\");\n}\n",
+        );
+        std::fs::write(
+            dir.join("workspace.toml"),
+            "name = \"platform\"\n\n[[repos]]\nname = \"users\"\npath = \"users\"\n\n[[repos]]\nname = \"web\"\npath = \"web\"\n",
+        )
+        .unwrap();
+
+        let manifest = load_manifest(&dir.join("workspace.toml")).unwrap();
+        let output = scan_workspace("platform", &dir, &manifest.repos);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(output.repositories.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), vec!["users", "web"]);
+        let route = output.components.iter().find(|c| c.name == "GET /api/users/{user_id}").unwrap();
+        assert_eq!(route.repo.as_deref(), Some("users"));
+        assert!(route.id.starts_with("users:"));
+
+        let caller = output.components.iter().find(|c| c.name == "loadUser").unwrap();
+        assert!(output.edges.iter().any(|e| {
+            e.from_id == caller.id && e.to_id == route.id && e.has_label("http_call")
+        }));
+        // Calls nothing in the workspace serves keep their endpoint node
+        let outbound: Vec<&str> = output.components.iter().filter(|c| is_outbound(c)).map(|c| c.name.as_str()).collect();
+        assert_eq!(outbound, vec!["GET status.example.com/ping"]);
+    }
+
+    #[test]
+    fn links_only_publishes_made_in_code() {
+        let dir = std::env::temp_dir().join(format!("sysvista-workspace-mq-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        write(&dir.join("billing"), "app/consumer.py", "consumer = KafkaConsumer('orders.created')\n");
+        write(
+            &dir.join("orders"),
+            "src/services/orders.py",
+            "def place_order(order):\n    producer.send('orders.created', value=order)\n    log.info(\"replay with producer.send('orders.replayed')\")\n",
+        );
+        let output = scan_workspace("platform", &dir, &repos_from_roots(&[dir.join("billing"), dir.join("orders")], &[]));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let consumer = output.components.iter().find(|c| c.name == "orders.created" && !is_outbound(c)).unwrap();
+        assert_eq!(consumer.repo.as_deref(), Some("billing"));
+        let publisher = output.components.iter().find(|c| c.name == "place_order").unwrap();
+        assert!(output.edges.iter().any(|e| {
+            e.from_id == publisher.id && e.to_id == consumer.id && e.has_label("publishes")
+        }));
+        assert!(output.components.iter().all(|c| !is_outbound(c)));
+    }
+
+    #[test]
+    fn roots_are_named_after_directories() {
+        let specs = repos_from_roots(&[PathBuf::from("/src/api"), PathBuf::from("/other/api"), PathBuf::from("/src/web")], &[]);
+        let names: Vec<&str> = specs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "api-2", "web"]);
+
+        // Manifest repositories keep their names; roots make way
+        let manifest = [RepoSpec { name: "web".to_string(), path: PathBuf::from("/apps/web") }];
+        let specs = repos_from_roots(&[PathBuf::from("/src/web")], &manifest);
+        assert_eq!(specs[0].name, "web-2");
+    }
+
+    #[test]
+    fn packages_and_evidence_are_namespaced_by_repository() {
+        let dir = std::env::temp_dir().join(format!("sysvista-workspace-pkg-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for repo in ["shop", "admin"] {
+            let root = dir.join(repo);
+            write(&root, "package.json", "{\"name\": \"root\", \"workspaces\": [\"packages/*\"]}\n");
+            write(&root, "packages/api/package.json", "{\"name\": \"api\"}\n");
+            write(
+                &root,
+                "packages/api/src/users.ts",
+                "import { User } from \"./models\";\n\nexport class UserService {\n  get(): User {\n    return load();\n  }\n}\n",
+            );
+            write(&root, "packages/api/src/models.ts", "export interface User {\n  id: string;\n}\n");
+        }
+        let output = scan_workspace("platform", &dir, &repos_from_roots(&[dir.join("shop"), dir.join("admin")], &[]));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let packages: Vec<(&str, &str)> =
+            output.packages.iter().filter(|p| p.name.ends_with(":api")).map(|p| (p.name.as_str(), p.path.as_str())).collect();
+        assert_eq!(packages, vec![("shop:api", "shop/packages/api"), ("admin:api", "admin/packages/api")]);
+
+        for repo in ["shop", "admin"] {
+            let service = output.components.iter().find(|c| c.name == "UserService" && c.repo.as_deref() == Some(repo)).unwrap();
+            assert_eq!(service.package.as_deref(), Some(format!("{repo}:api").as_str()));
+            let evidence = output
+                .edges
+                .iter()
+                .filter(|e| e.from_id == service.id)
+                .find_map(|e| e.relations().into_iter().find_map(|r| r.evidence))
+                .unwrap();
+            assert_eq!(evidence.source.file, format!("{repo}/packages/api/src/users.ts"));
+        }
+    }
+
+    #[test]
+    fn manifest_rejects_repeated_repository_names() {
+        let dir = std::env::temp_dir().join(format!("sysvista-workspace-dup-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        write(&dir, "workspace.toml", "[[repos]]\nname = \"api\"\npath = \"a\"\n\n[[repos]]\nname = \"api\"\npath = \"b\"\n");
+        let err = load_manifest(&dir.join("workspace.toml")).unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(err.contains("repository 'api' is listed twice"), "{err}");
+    }
+}
@@ -23,6 +23,7 @@ function AppInner() {
     searchQuery,
     searchResults,
     connectedComponents,
+    connectedEdges,
     highlightedNodeIds,
     highlightedFlowNodeIds,
     workflows,
@@ -211,6 +212,7 @@ function AppInner() {
           <DetailPanel
             component={selectedNode}
             connectedComponents={connectedComponents}
+            connectedEdges={connectedEdges}
             onClose={() => setSelectedNode(null)}
             onNavigate={handleNavigate}
           />
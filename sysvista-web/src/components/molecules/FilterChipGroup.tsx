@@ -1,6 +1,6 @@
 import type { ComponentKind } from "../../types/schema";
 
-const KINDS: ComponentKind[] = ["model", "service", "transport", "transform", "prompt"];
+const KINDS: ComponentKind[] = ["model", "service", "transport", "transform", "prompt", "datastore"];
 
 /** Solid bg classes for active chips (full saturation, not the /20 variants) */
 const KIND_SOLID_BG: Record<ComponentKind, string> = {
@@ -9,6 +9,7 @@ const KIND_SOLID_BG: Record<ComponentKind, string> = {
   transport: "bg-orange-500",
   transform: "bg-purple-500",
   prompt: "bg-cyan-500",
+  datastore: "bg-rose-500",
 };
 
 interface FilterChipGroupProps {
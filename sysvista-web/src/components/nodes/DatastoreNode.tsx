@@ -0,0 +1,42 @@
+import { Handle, Position, type NodeProps } from "@xyflow/react";
+import { HardDrive } from "lucide-react";
+import type { GraphNode } from "../../lib/graph-adapter";
+
+export function DatastoreNode({ data }: NodeProps) {
+  const { component, hubTier, degree, direction } = data as unknown as GraphNode;
+  const isHighHub = hubTier === "high";
+  const isMediumHub = hubTier === "medium";
+  const targetPos = direction === "LR" ? Position.Left : Position.Top;
+  const sourcePos = direction === "LR" ? Position.Right : Position.Bottom;
+
+  return (
+    <div
+      className={`rounded-xl border-2 border-rose-500/50 bg-rose-950/80 px-4 py-2 shadow-lg min-w-[160px] ${
+        isHighHub
+          ? "shadow-rose-400/40 ring-2 ring-rose-400/60"
+          : isMediumHub
+            ? "shadow-rose-400/20 ring-1 ring-rose-400/30"
+            : "shadow-rose-500/10"
+      }`}
+    >
+      <Handle type="target" position={targetPos} className="!bg-rose-400" />
+      <div className="flex items-center gap-2">
+        <HardDrive className="h-4 w-4 text-rose-400 shrink-0" />
+        <div className="truncate">
+          <div className="text-sm font-semibold text-rose-100 truncate">
+            {component.name}
+          </div>
+          <div className="text-xs text-rose-400/70">
+            {component.metadata.host ?? component.metadata.engine ?? component.language}
+          </div>
+        </div>
+        {isHighHub && (
+          <span className="ml-auto text-[10px] font-bold text-rose-300 bg-rose-800/60 rounded-full px-1.5 py-0.5 shrink-0">
+            {degree}
+          </span>
+        )}
+      </div>
+      <Handle type="source" position={sourcePos} className="!bg-rose-400" />
+    </div>
+  );
+}
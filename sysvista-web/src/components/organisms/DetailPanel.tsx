@@ -1,14 +1,16 @@
-import { FileCode, MapPin, Tag, Link } from "lucide-react";
-import type { DetectedComponent } from "../../types/schema";
+import { FileCode, MapPin, Tag, Link, Search, Activity } from "lucide-react";
+import type { DetectedComponent, DetectedEdge } from "../../types/schema";
 import { PanelShell } from "../molecules/PanelShell";
 import { FieldGroup } from "../molecules/FieldGroup";
 import { ListItem } from "../molecules/ListItem";
 import { Badge } from "../atoms/Badge";
 import { SectionHeader } from "../atoms/SectionHeader";
+import { edgeRelations } from "../../lib/graph-adapter";
 
 interface DetailPanelProps {
   component: DetectedComponent;
   connectedComponents: DetectedComponent[];
+  connectedEdges: DetectedEdge[];
   onClose: () => void;
   onNavigate: (component: DetectedComponent) => void;
 }
@@ -16,9 +18,23 @@ interface DetailPanelProps {
 export function DetailPanel({
   component,
   connectedComponents,
+  connectedEdges,
   onClose,
   onNavigate,
 }: DetailPanelProps) {
+  const names = new Map(connectedComponents.map((c) => [c.id, c.name]));
+  names.set(component.id, component.name);
+  const labelsTo = (id: string) =>
+    connectedEdges
+      .filter((e) => e.from_id === id || e.to_id === id)
+      .flatMap((e) => edgeRelations(e).map((r) => r.label))
+      .join(", ");
+  const evidenced = connectedEdges.flatMap((edge) =>
+    edgeRelations(edge)
+      .filter((relation) => relation.evidence)
+      .map((relation) => ({ edge, relation, evidence: relation.evidence! })),
+  );
+
   return (
     <PanelShell side="right" title="" onClose={onClose}>
       {/* Header */}
@@ -38,6 +54,9 @@ export function DetailPanel({
           {component.source.line_start && (
             <span className="text-gray-500">
               :{component.source.line_start}
+              {component.source.line_end &&
+                component.source.line_end !== component.source.line_start &&
+                `-${component.source.line_end}`}
             </span>
           )}
         </div>
@@ -48,6 +67,32 @@ export function DetailPanel({
         <div className="text-sm text-gray-300">{component.language}</div>
       </FieldGroup>
 
+      {/* Package (monorepos) */}
+      {component.package && (
+        <FieldGroup label="Package" icon={Tag}>
+          <div className="text-sm text-gray-300">{component.package}</div>
+        </FieldGroup>
+      )}
+
+      {/* Repository (workspace scans) */}
+      {component.repo && (
+        <FieldGroup label="Repository" icon={Tag}>
+          <div className="text-sm text-gray-300">{component.repo}</div>
+        </FieldGroup>
+      )}
+
+      {/* Coupling metrics (from the CLI) */}
+      {component.metrics && (
+        <FieldGroup label="Metrics" icon={Activity}>
+          <div className="text-sm text-gray-300">
+            fan-in {component.metrics.fan_in}, fan-out {component.metrics.fan_out}
+          </div>
+          <div className="text-xs text-gray-500">
+            betweenness {component.metrics.betweenness.toFixed(3)}, hub {component.metrics.hub_score.toFixed(2)}
+          </div>
+        </FieldGroup>
+      )}
+
       {/* Prompt subtype */}
       {component.prompt_subtype && (
         <FieldGroup label="Prompt Type" icon={Tag}>
@@ -119,9 +164,16 @@ export function DetailPanel({
           <div className="text-xs font-medium text-gray-400 mb-1">
             Fields
           </div>
-          <div className="flex flex-wrap gap-1">
+          <div className="space-y-0.5 font-mono text-xs">
             {component.model_fields.map((field) => (
-              <Badge key={field} label={field} variant="field" />
+              <div key={field.name} className="text-gray-300">
+                {field.name}
+                {field.optional && <span className="text-gray-500">?</span>}
+                {field.type && <span className="text-cyan-300">: {field.type}</span>}
+                {field.default && (
+                  <span className="text-gray-500"> = {field.default}</span>
+                )}
+              </div>
             ))}
           </div>
         </div>
@@ -151,12 +203,40 @@ export function DetailPanel({
                 key={conn.id}
                 kind={conn.kind}
                 label={conn.name}
+                sublabel={labelsTo(conn.id) || undefined}
                 onClick={() => onNavigate(conn)}
               />
             ))}
           </div>
         </div>
       )}
+
+      {/* Why each edge was inferred */}
+      {evidenced.length > 0 && (
+        <div className="mt-4">
+          <SectionHeader icon={Search} label="Evidence" count={evidenced.length} />
+          <div className="space-y-2 mt-1">
+            {evidenced.map(({ edge, relation, evidence }, i) => (
+              <div key={`${edge.from_id}-${edge.to_id}-${relation.label}-${i}`} className="text-xs">
+                <div className="text-gray-300">
+                  {names.get(edge.from_id) ?? edge.from_id}
+                  <span className="text-gray-500"> {relation.label} </span>
+                  {names.get(edge.to_id) ?? edge.to_id}
+                </div>
+                <div className="text-gray-500">
+                  {evidence.heuristic} · {evidence.source.file}
+                  {evidence.source.line_start && `:${evidence.source.line_start}`}
+                </div>
+                {evidence.snippet && (
+                  <div className="font-mono text-gray-400 bg-gray-800 rounded px-2 py-0.5 mt-0.5 truncate">
+                    {evidence.snippet}
+                  </div>
+                )}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
     </PanelShell>
   );
 }
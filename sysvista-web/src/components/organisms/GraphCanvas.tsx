@@ -18,6 +18,7 @@ import { ServiceNode } from "../nodes/ServiceNode";
 import { TransportNode } from "../nodes/TransportNode";
 import { TransformNode } from "../nodes/TransformNode";
 import { PromptNode } from "../nodes/PromptNode";
+import { DatastoreNode } from "../nodes/DatastoreNode";
 import { GroupLabelNode } from "../nodes/GroupLabelNode";
 import { ClusterLabelNode } from "../nodes/ClusterLabelNode";
 
@@ -27,6 +28,7 @@ const nodeTypes: NodeTypes = {
   transport: TransportNode,
   transform: TransformNode,
   prompt: PromptNode,
+  datastore: DatastoreNode,
   groupLabel: GroupLabelNode,
   clusterLabel: ClusterLabelNode,
 };
@@ -1,4 +1,4 @@
-import { Database, Server, Globe, ArrowRightLeft, MessageSquare } from "lucide-react";
+import { Database, Server, Globe, ArrowRightLeft, MessageSquare, HardDrive } from "lucide-react";
 import { KIND_COLORS } from "../../lib/design-tokens";
 import type { ComponentKind } from "../../types/schema";
 
@@ -8,6 +8,7 @@ const items: { kind: ComponentKind; label: string; Icon: typeof Database }[] = [
   { kind: "transport", label: "Transport", Icon: Globe },
   { kind: "transform", label: "Transform", Icon: ArrowRightLeft },
   { kind: "prompt", label: "Prompt", Icon: MessageSquare },
+  { kind: "datastore", label: "Datastore", Icon: HardDrive },
 ];
 
 const edgeItems = [
@@ -6,10 +6,10 @@ import type {
   ComponentKind,
   Workflow,
 } from "../types/schema";
-import { buildGraph, buildFlowGraph, FLOW_LABELS } from "../lib/graph-adapter";
+import { buildGraph, buildFlowGraph, isFlowEdge } from "../lib/graph-adapter";
 import { initSearch, search } from "../lib/search";
 
-const ALL_KINDS: ComponentKind[] = ["model", "service", "transport", "transform", "prompt"];
+const ALL_KINDS: ComponentKind[] = ["model", "service", "transport", "transform", "prompt", "datastore"];
 
 export type ViewMode = "graph" | "flow";
 
@@ -92,6 +92,13 @@ export function useGraphData() {
     return schema.components.filter((c) => connectedIds.has(c.id));
   }, [schema, selectedNode]);
 
+  const connectedEdges = useMemo(() => {
+    if (!schema || !selectedNode) return [];
+    return schema.edges.filter(
+      (edge) => edge.from_id === selectedNode.id || edge.to_id === selectedNode.id,
+    );
+  }, [schema, selectedNode]);
+
   /**
    * Trace workflow from a component by following flow edges (handles, persists, transforms).
    * BFS in both directions to find the full flow chain.
@@ -101,9 +108,7 @@ export function useGraphData() {
       if (!schema) return { nodeIds: new Set(), edgeIds: new Set() };
 
       // Build adjacency from flow edges only
-      const flowEdges = schema.edges.filter(
-        (e) => e.label && FLOW_LABELS.has(e.label),
-      );
+      const flowEdges = schema.edges.filter(isFlowEdge);
 
       const nodeIds = new Set<string>();
       const edgeIds = new Set<string>();
@@ -174,6 +179,7 @@ export function useGraphData() {
     searchQuery,
     searchResults,
     connectedComponents,
+    connectedEdges,
     highlightedNodeIds,
     highlightedFlowNodeIds,
     workflows,
@@ -155,6 +155,25 @@ describe("detectHubs", () => {
     expect(hubs.get("b")!.degree).toBe(2);
   });
 
+  it("uses CLI metrics when every component has them", () => {
+    const metrics = (fan_in: number, hub_score: number) => ({
+      fan_in,
+      fan_out: 0,
+      betweenness: 0,
+      hub_score,
+    });
+    const comps = [
+      makeComponent("a", "A", "model", "src/a.py", { metrics: metrics(9, 2.5) }),
+      makeComponent("b", "B", "model", "src/b.py", { metrics: metrics(4, 1.2) }),
+      makeComponent("c", "C", "service", "src/c.py", { metrics: metrics(1, -0.4) }),
+    ];
+    // The edges passed in are ignored in favour of the scan-wide metrics
+    const hubs = detectHubs(comps, [{ from_id: "c", to_id: "a" }]);
+    expect(hubs.get("a")).toEqual({ tier: "high", degree: 9 });
+    expect(hubs.get("b")).toEqual({ tier: "medium", degree: 4 });
+    expect(hubs.get("c")).toEqual({ tier: "normal", degree: 1 });
+  });
+
   it("includes zero-degree nodes", () => {
     const comps = [
       makeComponent("a", "A", "service"),
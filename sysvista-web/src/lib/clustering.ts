@@ -58,11 +58,24 @@ export interface HubInfo {
  * - high: degree > mean + 2*stddev
  * - medium: degree > mean + 1*stddev
  * - normal: everything else
+ *
+ * Components scanned with metrics use the CLI's hub score over the whole
+ * graph; otherwise degrees are counted over the given edges.
  */
 export function detectHubs(
   components: DetectedComponent[],
   edges: DetectedEdge[],
 ): Map<string, HubInfo> {
+  if (components.length > 0 && components.every((c) => c.metrics)) {
+    return new Map(
+      components.map((c) => {
+        const { fan_in, fan_out, hub_score } = c.metrics!;
+        const tier: HubInfo["tier"] = hub_score > 2 ? "high" : hub_score > 1 ? "medium" : "normal";
+        return [c.id, { tier, degree: fan_in + fan_out }];
+      }),
+    );
+  }
+
   const baseDegrees = new Map(components.map((c) => [c.id, 0]));
 
   const degreeMap = edges.reduce((acc, e) => {
@@ -42,6 +42,13 @@ export const KIND_COLORS: Record<ComponentKind, {
     dot: "bg-cyan-400",
     hex: "#06b6d4",
   },
+  datastore: {
+    bg: "bg-rose-500/20",
+    text: "text-rose-400",
+    border: "border-rose-500/30",
+    dot: "bg-rose-400",
+    hex: "#f43f5e",
+  },
 };
 
 export const STEP_TYPE_COLORS: Record<StepType, { text: string; bg: string; label: string }> = {
@@ -60,4 +67,5 @@ export const KIND_NODE_SIZE: Record<ComponentKind, { width: number; height: numb
   transport: { width: 200, height: 60 },
   transform: { width: 180, height: 60 },
   prompt:    { width: 200, height: 60 },
+  datastore: { width: 180, height: 60 },
 };
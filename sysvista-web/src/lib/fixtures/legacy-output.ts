@@ -0,0 +1,33 @@
+// A scan written before structured model fields and workflows: `model_fields`
+// holds bare names and there is no `workflows` key.
+export const legacyOutput: unknown = {
+  version: "1",
+  scanned_at: "2024-01-01T00:00:00Z",
+  root_dir: "/projects/shop",
+  project_name: "shop",
+  detected_languages: ["python"],
+  components: [
+    {
+      id: "m1",
+      name: "Order",
+      kind: "model",
+      language: "python",
+      source: { file: "app/models.py", line_start: 4, line_end: 9 },
+      metadata: {},
+      model_fields: ["id", "total", "status"],
+    },
+    {
+      id: "t1",
+      name: "POST /orders",
+      kind: "transport",
+      language: "python",
+      source: { file: "app/routes.py", line_start: 12 },
+      metadata: {},
+      transport_protocol: "http",
+      http_method: "POST",
+      http_path: "/orders",
+    },
+  ],
+  edges: [{ from_id: "m1", to_id: "t1", label: "consumes" }],
+  scan_stats: { files_scanned: 2, files_skipped: 0, scan_duration_ms: 5 },
+};
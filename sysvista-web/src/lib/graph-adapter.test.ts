@@ -139,6 +139,26 @@ describe("buildGraph filtering", () => {
     expect(edges[0].label).toContain("imports");
     expect(edges[0].label).toContain("references");
   });
+
+  it("labels merged edges with every relation", () => {
+    const data = makeScan({
+      components: [
+        makeComponent("tp1", "route", "transport"),
+        makeComponent("svc1", "handler", "service"),
+      ],
+      edges: [{
+        from_id: "tp1",
+        to_id: "svc1",
+        label: "calls",
+        relations: [{ label: "calls" }, { label: "imports" }],
+      }],
+    });
+
+    const allKinds = new Set<ComponentKind>(["model", "service", "transport", "transform"]);
+    expect(buildGraph(data, allKinds).edges[0].label).toBe("calls, imports");
+    // The flow view only shows flow relations
+    expect(buildFlowGraph(data, allKinds).edges[0].label).toBe("calls");
+  });
 });
 
 describe("buildFlowGraph", () => {
@@ -1,6 +1,6 @@
 import dagre from "@dagrejs/dagre";
 import type { Node, Edge } from "@xyflow/react";
-import type { SysVistaOutput, DetectedComponent, DetectedEdge, ComponentKind } from "../types/schema";
+import type { SysVistaOutput, DetectedComponent, DetectedEdge, EdgeRelation, ComponentKind } from "../types/schema";
 import { classifyComponents, detectHubs, type HubInfo } from "./clustering";
 import { KIND_NODE_SIZE } from "./design-tokens";
 import type { ClusterLabelData } from "../components/nodes/ClusterLabelNode";
@@ -22,7 +22,7 @@ export interface GroupLabelNode extends Record<string, unknown> {
 
 const KIND_CONFIG = KIND_NODE_SIZE;
 
-export const FLOW_LABELS = new Set(["handles", "persists", "transforms", "consumes", "produces", "calls", "dispatches"]);
+export const FLOW_LABELS = new Set(["handles", "persists", "transforms", "consumes", "produces", "calls", "dispatches", "reads", "writes", "http_call", "publishes", "grpc_call"]);
 const PAYLOAD_LABELS = new Set(["consumes", "produces"]);
 
 // Dagre can't handle dense graphs — fall back to cluster grid layout above this threshold
@@ -30,6 +30,16 @@ const MAX_DAGRE_EDGES = 2000;
 
 // --- Shared helpers ---
 
+/** Relations of an edge; an edge with one relation carries it as its own `label`. */
+export const edgeRelations = (e: DetectedEdge): EdgeRelation[] =>
+  e.relations?.length
+    ? e.relations
+    : e.label
+      ? [{ label: e.label, payload_type: e.payload_type, evidence: e.evidence, confidence: e.confidence }]
+      : [];
+
+export const isFlowEdge = (e: DetectedEdge) => edgeRelations(e).some((r) => FLOW_LABELS.has(r.label));
+
 interface MergedEdge {
   from_id: string;
   to_id: string;
@@ -41,17 +51,12 @@ const deduplicateEdges = (edges: DetectedEdge[]): MergedEdge[] =>
   [...edges.reduce((acc, e) => {
     const key = `${e.from_id}->${e.to_id}`;
     const existing = acc.get(key);
-    if (existing) {
-      if (e.label && !existing.labels.includes(e.label)) existing.labels.push(e.label);
-      if (e.payload_type && !existing.payload_types.includes(e.payload_type)) existing.payload_types.push(e.payload_type);
-    } else {
-      acc.set(key, {
-        from_id: e.from_id,
-        to_id: e.to_id,
-        labels: e.label ? [e.label] : [],
-        payload_types: e.payload_type ? [e.payload_type] : [],
-      });
+    const merged = existing ?? { from_id: e.from_id, to_id: e.to_id, labels: [], payload_types: [] };
+    for (const r of edgeRelations(e)) {
+      if (!merged.labels.includes(r.label)) merged.labels.push(r.label);
+      if (r.payload_type && !merged.payload_types.includes(r.payload_type)) merged.payload_types.push(r.payload_type);
     }
+    acc.set(key, merged);
     return acc;
   }, new Map<string, MergedEdge>()).values()];
 
@@ -80,8 +85,8 @@ const toComponentNode = (
 const classifyEdge = (labels: string[]) => ({
   isPayload: labels.some((l) => PAYLOAD_LABELS.has(l)),
   isFlow: labels.some((l) => FLOW_LABELS.has(l)),
-  isCalls: labels.includes("calls"),
-  isDispatches: labels.includes("dispatches"),
+  isCalls: labels.some((l) => l === "calls" || l === "http_call" || l === "grpc_call"),
+  isDispatches: labels.some((l) => l === "dispatches" || l === "publishes"),
 });
 
 const edgeStroke = ({ isPayload, isCalls, isDispatches, isFlow }: ReturnType<typeof classifyEdge>) =>
@@ -254,7 +259,11 @@ export function buildFlowGraph(
   data: SysVistaOutput,
   activeKinds: Set<ComponentKind>,
 ): { nodes: Node[]; edges: Edge[] } {
-  const flowEdges = data.edges.filter((e) => e.label && FLOW_LABELS.has(e.label));
+  // Flow edges keep only their flow relations
+  const flowEdges = data.edges.filter(isFlowEdge).map((e) => ({
+    ...e,
+    relations: edgeRelations(e).filter((r) => FLOW_LABELS.has(r.label)),
+  }));
 
   const flowNodeIds = flowEdges.reduce((acc, e) => {
     acc.add(e.from_id);
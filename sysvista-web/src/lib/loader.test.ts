@@ -1,24 +1,6 @@
 import { describe, it, expect } from "vitest";
-import type { SysVistaOutput } from "../types/schema";
-
-// Re-implement validate logic for testing since it's not exported
-function validate(data: unknown): SysVistaOutput {
-  const obj = data as Record<string, unknown>;
-  if (
-    !obj ||
-    typeof obj !== "object" ||
-    !Array.isArray(obj.components) ||
-    !Array.isArray(obj.edges)
-  ) {
-    throw new Error(
-      "Invalid SysVista JSON: missing required fields (components, edges)",
-    );
-  }
-  if (!Array.isArray(obj.workflows)) {
-    obj.workflows = [];
-  }
-  return obj as unknown as SysVistaOutput;
-}
+import { validate } from "./loader";
+import { legacyOutput } from "./fixtures/legacy-output";
 
 describe("loader validate", () => {
   it("accepts valid data with workflows", () => {
@@ -53,6 +35,39 @@ describe("loader validate", () => {
     expect(result.workflows).toEqual([]);
   });
 
+  it("upgrades model fields listed as bare names", () => {
+    const data = {
+      components: [
+        {
+          id: "c1",
+          name: "User",
+          kind: "model",
+          language: "python",
+          source: { file: "models.py" },
+          metadata: {},
+          model_fields: ["id", { name: "email", type: "str", optional: true }],
+        },
+      ],
+      edges: [],
+    };
+    const result = validate(data);
+    expect(result.components[0].model_fields).toEqual([
+      { name: "id", optional: false },
+      { name: "email", type: "str", optional: true },
+    ]);
+  });
+
+  it("upgrades a legacy output fixture", () => {
+    const result = validate(legacyOutput);
+    expect(result.workflows).toEqual([]);
+    expect(result.components[0].model_fields).toEqual([
+      { name: "id", optional: false },
+      { name: "total", optional: false },
+      { name: "status", optional: false },
+    ]);
+    expect(result.components[1].model_fields).toBeUndefined();
+  });
+
   it("rejects data missing components", () => {
     expect(() => validate({ edges: [] })).toThrow("missing required fields");
   });
@@ -1,6 +1,6 @@
 import type { SysVistaOutput } from "../types/schema";
 
-function validate(data: unknown): SysVistaOutput {
+export function validate(data: unknown): SysVistaOutput {
   const obj = data as Record<string, unknown>;
   if (
     !obj ||
@@ -15,6 +15,14 @@ function validate(data: unknown): SysVistaOutput {
   if (!Array.isArray(obj.workflows)) {
     obj.workflows = [];
   }
+  // Older outputs list model fields as bare names
+  for (const component of obj.components as Record<string, unknown>[]) {
+    if (Array.isArray(component.model_fields)) {
+      component.model_fields = component.model_fields.map((field: unknown) =>
+        typeof field === "string" ? { name: field, optional: false } : field,
+      );
+    }
+  }
   return obj as unknown as SysVistaOutput;
 }
 
@@ -1,4 +1,4 @@
-export type ComponentKind = "model" | "service" | "transport" | "transform" | "prompt";
+export type ComponentKind = "model" | "service" | "transport" | "transform" | "prompt" | "datastore";
 export type TransportProtocol = "http" | "grpc" | "websocket" | "mq" | "graphql" | "unknown";
 export type PromptSubtype = "router" | "classifier" | "generator" | "extractor" | "summarizer" | "validator";
 
@@ -8,6 +8,14 @@ export interface SourceLocation {
   line_end?: number;
 }
 
+export interface ModelField {
+  name: string;
+  type?: string;
+  optional: boolean;
+  default?: string;
+  tags?: Record<string, string>;
+}
+
 export interface DetectedComponent {
   id: string;
   name: string;
@@ -19,9 +27,42 @@ export interface DetectedComponent {
   http_method?: string;
   http_path?: string;
   prompt_subtype?: PromptSubtype;
-  model_fields?: string[];
+  model_fields?: ModelField[];
   consumes?: string[];
   produces?: string[];
+  repo?: string;
+  origin?: string;
+  package?: string;
+  confidence?: number;
+  metrics?: ComponentMetrics;
+  history?: ComponentHistory;
+}
+
+export interface ComponentHistory {
+  commits: number;
+  authors: number;
+  lines_changed: number;
+  last_modified?: string;
+}
+
+export interface ComponentMetrics {
+  fan_in: number;
+  fan_out: number;
+  betweenness: number;
+  hub_score: number;
+}
+
+export interface EdgeEvidence {
+  heuristic: string;
+  source: SourceLocation;
+  snippet?: string;
+}
+
+export interface EdgeRelation {
+  label: string;
+  payload_type?: string;
+  evidence?: EdgeEvidence;
+  confidence?: number;
 }
 
 export interface DetectedEdge {
@@ -29,6 +70,9 @@ export interface DetectedEdge {
   to_id: string;
   label?: string;
   payload_type?: string;
+  evidence?: EdgeEvidence;
+  confidence?: number;
+  relations?: EdgeRelation[];
 }
 
 export type StepType = "entry" | "call" | "persist" | "dispatch" | "response" | "prompt";
@@ -52,6 +96,56 @@ export interface ScanStats {
   scan_duration_ms: number;
 }
 
+export interface Package {
+  name: string;
+  path: string;
+  manager: string;
+}
+
+export interface Repository {
+  name: string;
+  root_dir: string;
+  detected_languages: string[];
+  scan_stats: ScanStats;
+}
+
+export interface CycleEdge {
+  from_id: string;
+  to_id: string;
+  labels: string[];
+}
+
+export interface DependencyCycle {
+  level: "component" | "file" | "directory" | "package";
+  members: string[];
+  edges: CycleEdge[];
+}
+
+export interface ModuleMetrics {
+  name: string;
+  components: number;
+  afferent: number;
+  efferent: number;
+  instability: number;
+}
+
+export interface DeadComponent {
+  id: string;
+  reason: string;
+}
+
+export interface Hotspot {
+  id: string;
+  score: number;
+}
+
+export interface Analysis {
+  cycles?: DependencyCycle[];
+  modules?: ModuleMetrics[];
+  dead?: DeadComponent[];
+  hotspots?: Hotspot[];
+}
+
 export interface SysVistaOutput {
   version: string;
   scanned_at: string;
@@ -62,4 +156,7 @@ export interface SysVistaOutput {
   edges: DetectedEdge[];
   workflows: Workflow[];
   scan_stats: ScanStats;
+  packages?: Package[];
+  repositories?: Repository[];
+  analysis?: Analysis;
 }